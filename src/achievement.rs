@@ -0,0 +1,138 @@
+use std::collections::VecDeque;
+use crate::persistence::Store;
+
+/// 🏆 Milestones a run can earn; unlocks persist across runs
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Achievement {
+    FirstBlood,   // First kill of the run
+    NoHitWave,    // Clear a wave without taking a hit
+    HundredKills, // 100 kills in one run
+    Survivor,     // Alive for five minutes
+}
+
+impl Achievement {
+    pub const ALL: [Achievement; 4] = [
+        Achievement::FirstBlood,
+        Achievement::NoHitWave,
+        Achievement::HundredKills,
+        Achievement::Survivor,
+    ];
+
+    // Persistence key
+    fn key(self) -> &'static str {
+        match self {
+            Achievement::FirstBlood => "achievement.first_blood",
+            Achievement::NoHitWave => "achievement.no_hit_wave",
+            Achievement::HundredKills => "achievement.hundred_kills",
+            Achievement::Survivor => "achievement.survivor",
+        }
+    }
+
+    pub fn title(self) -> &'static str {
+        match self {
+            Achievement::FirstBlood => "First Blood",
+            Achievement::NoHitWave => "No-Hit Wave",
+            Achievement::HundredKills => "100 Kills",
+            Achievement::Survivor => "Survive 5 Minutes",
+        }
+    }
+}
+
+// Toast lifetime and the slide in/out at either end of it
+pub const TOAST_TIME: f32 = 3.0;
+pub const TOAST_SLIDE_TIME: f32 = 0.3;
+
+/// A toast announcing an achievement, `age` seconds after it appeared
+#[derive(Debug, Clone, Copy)]
+pub struct Toast {
+    pub achievement: Achievement,
+    pub age: f32,
+    /// Earned for the first time ever, not just this run
+    pub new_unlock: bool,
+}
+
+/// 🏆 Permanent unlocks, this run's earnings and the toast queue
+#[derive(Debug, Clone)]
+pub struct Achievements {
+    path: &'static str,
+    unlocked: Vec<Achievement>,
+    unsaved: Vec<Achievement>, // Unlocked since the last `save`
+    earned: Vec<Achievement>,
+    toasts: VecDeque<Toast>,
+}
+
+impl Achievements {
+    /// 🏆 Nothing unlocked yet; unlocks will save to the store at `path`
+    pub fn new(path: &'static str) -> Self {
+        Self {
+            path,
+            unlocked: Vec::new(),
+            unsaved: Vec::new(),
+            earned: Vec::new(),
+            toasts: VecDeque::new(),
+        }
+    }
+
+    /// 💾 Load permanent unlocks from the store at `path`
+    pub fn load(path: &'static str) -> Self {
+        let store = Store::load(path);
+        let unlocked = Achievement::ALL
+            .into_iter()
+            .filter(|a| store.get::<bool>(a.key()).unwrap_or(false))
+            .collect();
+        Self { unlocked, ..Self::new(path) }
+    }
+
+    /// 💾 Write out unlocks since the last save, if any
+    pub fn save(&mut self) {
+        if self.unsaved.is_empty() {
+            return;
+        }
+        let mut store = Store::load(self.path);
+        for achievement in self.unsaved.drain(..) {
+            store.set(achievement.key(), true);
+        }
+        store.save();
+    }
+
+    /// Award an achievement; a no-op if this run already earned it
+    pub fn earn(&mut self, achievement: Achievement) {
+        if self.earned.contains(&achievement) {
+            return;
+        }
+        self.earned.push(achievement);
+
+        let new_unlock = !self.unlocked.contains(&achievement);
+        if new_unlock {
+            self.unlocked.push(achievement);
+            self.unsaved.push(achievement);
+        }
+        self.toasts.push_back(Toast { achievement, age: 0.0, new_unlock });
+    }
+
+    /// Earned in any run so far
+    pub fn is_unlocked(&self, achievement: Achievement) -> bool {
+        self.unlocked.contains(&achievement)
+    }
+
+    /// Age the front toast, dismissing it once its time is up
+    pub fn update(&mut self, dt: f32) {
+        if let Some(toast) = self.toasts.front_mut() {
+            toast.age += dt;
+            if toast.age >= TOAST_TIME {
+                self.toasts.pop_front();
+            }
+        }
+    }
+
+    /// Toast to show right now (they queue, one at a time)
+    pub fn toast(&self) -> Option<&Toast> {
+        self.toasts.front()
+    }
+
+    /// Forget this run's earnings, keeping permanent unlocks
+    pub fn new_run(&mut self) {
+        self.earned.clear();
+        self.toasts.clear();
+    }
+}
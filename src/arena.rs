@@ -0,0 +1,188 @@
+use std::ops::{Index, IndexMut};
+
+/// 🔖 Handle to an entity in an [`Arena`]. Goes stale once the entity is
+/// removed, even if its slot is later reused by another one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Id {
+    index: u32,
+    generation: u32,
+}
+
+// A slot's generation moves on every time its entity is removed, so old
+// ids no longer match whatever moves in next
+#[derive(Debug, Clone)]
+struct Slot<T> {
+    generation: u32,
+    value: Option<T>,
+}
+
+/// 🗃️ Generational arena: entities keep a stable [`Id`] for as long as
+/// they live, freed slots are reused, and iteration runs in slot order
+#[derive(Debug, Clone)]
+pub struct Arena<T> {
+    slots: Vec<Slot<T>>,
+    free: Vec<u32>,
+    len: usize,
+}
+
+impl<T> Default for Arena<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Arena<T> {
+    pub fn new() -> Self {
+        Self { slots: Vec::new(), free: Vec::new(), len: 0 }
+    }
+
+    /// Store `value`, returning its id
+    pub fn insert(&mut self, value: T) -> Id {
+        self.len += 1;
+        match self.free.pop() {
+            Some(index) => {
+                let slot = &mut self.slots[index as usize];
+                slot.value = Some(value);
+                Id { index, generation: slot.generation }
+            }
+            None => {
+                self.slots.push(Slot { generation: 0, value: Some(value) });
+                Id { index: self.slots.len() as u32 - 1, generation: 0 }
+            }
+        }
+    }
+
+    /// Take out the entity `id` names; None if it's already gone
+    pub fn remove(&mut self, id: Id) -> Option<T> {
+        let slot = self.slots.get_mut(id.index as usize)?;
+        if slot.generation != id.generation {
+            return None;
+        }
+        let value = slot.value.take()?;
+        slot.generation = slot.generation.wrapping_add(1);
+        self.free.push(id.index);
+        self.len -= 1;
+        Some(value)
+    }
+
+    pub fn get(&self, id: Id) -> Option<&T> {
+        self.slots
+            .get(id.index as usize)
+            .filter(|slot| slot.generation == id.generation)?
+            .value
+            .as_ref()
+    }
+
+    pub fn get_mut(&mut self, id: Id) -> Option<&mut T> {
+        self.slots
+            .get_mut(id.index as usize)
+            .filter(|slot| slot.generation == id.generation)?
+            .value
+            .as_mut()
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Every live entity with its id
+    pub fn iter(&self) -> impl Iterator<Item = (Id, &T)> {
+        self.slots.iter().enumerate().filter_map(|(index, slot)| {
+            let id = Id { index: index as u32, generation: slot.generation };
+            slot.value.as_ref().map(|value| (id, value))
+        })
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (Id, &mut T)> {
+        self.slots.iter_mut().enumerate().filter_map(|(index, slot)| {
+            let id = Id { index: index as u32, generation: slot.generation };
+            slot.value.as_mut().map(|value| (id, value))
+        })
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &T> {
+        self.slots.iter().filter_map(|slot| slot.value.as_ref())
+    }
+
+    pub fn values_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        self.slots.iter_mut().filter_map(|slot| slot.value.as_mut())
+    }
+
+    /// Keep only the entities `keep` returns true for
+    pub fn retain(&mut self, mut keep: impl FnMut(&mut T) -> bool) {
+        let removed: Vec<Id> = self.iter_mut().filter_map(|(id, value)| (!keep(value)).then_some(id)).collect();
+        for id in removed {
+            self.remove(id);
+        }
+    }
+
+    /// Remove everything; ids handed out so far all go stale
+    pub fn clear(&mut self) {
+        let removed: Vec<Id> = self.iter().map(|(id, _)| id).collect();
+        for id in removed {
+            self.remove(id);
+        }
+    }
+}
+
+impl<T> Index<Id> for Arena<T> {
+    type Output = T;
+
+    fn index(&self, id: Id) -> &T {
+        self.get(id).expect("stale arena id")
+    }
+}
+
+impl<T> IndexMut<Id> for Arena<T> {
+    fn index_mut(&mut self, id: Id) -> &mut T {
+        self.get_mut(id).expect("stale arena id")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn inserted_values_come_back_by_id() {
+        let mut arena = Arena::new();
+        let a = arena.insert("a");
+        let b = arena.insert("b");
+        assert_eq!((arena[a], arena[b]), ("a", "b"));
+        assert_eq!(arena.len(), 2);
+    }
+
+    #[test]
+    fn a_removed_id_goes_stale() {
+        let mut arena = Arena::new();
+        let a = arena.insert("a");
+        assert_eq!(arena.remove(a), Some("a"));
+        assert_eq!(arena.get(a), None);
+        assert_eq!(arena.remove(a), None);
+        assert_eq!(arena.len(), 0);
+    }
+
+    #[test]
+    fn a_reused_slot_does_not_answer_to_the_old_id() {
+        let mut arena = Arena::new();
+        let old = arena.insert("old");
+        arena.remove(old);
+        let new = arena.insert("new");
+        assert_ne!(old, new);
+        assert_eq!(arena.get(old), None);
+        assert_eq!(arena[new], "new");
+        assert_eq!(arena.slots.len(), 1);
+    }
+
+    #[test]
+    fn retain_and_clear_stale_the_removed_ids() {
+        let mut arena = Arena::new();
+        let ids: Vec<Id> = (0..4).map(|n| arena.insert(n)).collect();
+        arena.retain(|n| *n % 2 == 0);
+        assert_eq!(arena.values().copied().collect::<Vec<_>>(), vec![0, 2]);
+        assert_eq!(arena.get(ids[1]), None);
+        arena.clear();
+        assert!(ids.iter().all(|&id| arena.get(id).is_none()));
+        assert_eq!(arena.len(), 0);
+    }
+}
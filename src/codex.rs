@@ -0,0 +1,64 @@
+use crate::enemy::EnemyKind;
+use crate::persistence::Store;
+
+// Persistence key for one kind, e.g. `codex.chaser`
+fn key(kind: EnemyKind) -> String {
+    format!("codex.{}", kind.name())
+}
+
+/// 📖 Enemy kinds met so far, across every run. Only kinds this build knows
+/// are read back, so stale or future keys in the store are simply ignored.
+#[derive(Debug, Clone)]
+pub struct Codex {
+    path: &'static str,
+    discovered: Vec<EnemyKind>,
+    unsaved: Vec<EnemyKind>, // Discovered since the last `save`
+}
+
+impl Codex {
+    /// 📖 An empty codex that will save to the store at `path`
+    pub fn new(path: &'static str) -> Self {
+        Self { path, discovered: Vec::new(), unsaved: Vec::new() }
+    }
+
+    /// 💾 Load discoveries from the store at `path`
+    pub fn load(path: &'static str) -> Self {
+        let store = Store::load(path);
+        let discovered = EnemyKind::ALL
+            .into_iter()
+            .filter(|&kind| store.get::<bool>(&key(kind)).unwrap_or(false))
+            .collect();
+        Self { discovered, ..Self::new(path) }
+    }
+
+    /// Note an encounter; true the first time a kind is ever seen. Kept
+    /// in memory until the next [`Self::save`].
+    pub fn discover(&mut self, kind: EnemyKind) -> bool {
+        if self.is_discovered(kind) {
+            return false;
+        }
+        self.discovered.push(kind);
+        self.unsaved.push(kind);
+        true
+    }
+
+    /// 💾 Write out discoveries made since the last save, if any
+    pub fn save(&mut self) {
+        if self.unsaved.is_empty() {
+            return;
+        }
+        let mut store = Store::load(self.path);
+        for kind in self.unsaved.drain(..) {
+            store.set(&key(kind), true);
+        }
+        store.save();
+    }
+
+    pub fn is_discovered(&self, kind: EnemyKind) -> bool {
+        self.discovered.contains(&kind)
+    }
+
+    pub fn discovered_count(&self) -> usize {
+        self.discovered.len()
+    }
+}
@@ -0,0 +1,1298 @@
+use crate::achievement::Achievement;
+use crate::enemy::EnemyKind;
+use crate::joystick::StickGate;
+use crate::persistence::Store;
+use crate::player::{ThrustCurve, DEFAULT_DAMPING, HITBOX_RADIUS};
+
+const SETTINGS_FILE: &str = "settings.txt";
+
+/// 🎨 Rendering quality preset
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Quality {
+    High,
+    Medium,
+    Low,
+}
+
+impl Quality {
+    /// Background particle count
+    pub fn particle_count(self) -> usize {
+        match self {
+            Quality::High => 150,
+            Quality::Medium => 90,
+            Quality::Low => 40,
+        }
+    }
+
+    /// Whether to stack multiple translucent circles for glows
+    pub fn layered_glow(self) -> bool {
+        self != Quality::Low
+    }
+
+    /// Layers for a stacked-circle glow designed with `full` layers;
+    /// Low keeps only the inner half
+    pub fn glow_layers(self, full: u32) -> u32 {
+        if self.layered_glow() {
+            full
+        } else {
+            full / 2
+        }
+    }
+
+    /// Real render-target bloom on top of the stacked-circle glows
+    pub fn bloom(self) -> bool {
+        self == Quality::High
+    }
+
+    /// RGB-split flash when the player takes a hit
+    pub fn hit_aberration(self) -> bool {
+        self != Quality::Low
+    }
+
+    /// Faded hull copies smeared behind a fast-moving ship (0 disables
+    /// motion blur)
+    pub fn motion_blur_copies(self) -> u32 {
+        match self {
+            Quality::High => 4,
+            Quality::Medium => 2,
+            Quality::Low => 0,
+        }
+    }
+
+    /// Maximum player trail segments
+    pub fn trail_cap(self) -> usize {
+        match self {
+            Quality::High => 20,
+            Quality::Medium => 14,
+            Quality::Low => 8,
+        }
+    }
+
+    /// Maximum enemy trail segments, shared by all enemies (0 disables them)
+    pub fn enemy_trail_cap(self) -> usize {
+        match self {
+            Quality::High => 160,
+            Quality::Medium => 80,
+            Quality::Low => 0,
+        }
+    }
+
+    pub fn next(self) -> Quality {
+        match self {
+            Quality::High => Quality::Medium,
+            Quality::Medium => Quality::Low,
+            Quality::Low => Quality::High,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Quality::High => "high",
+            Quality::Medium => "medium",
+            Quality::Low => "low",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Quality> {
+        [Quality::High, Quality::Medium, Quality::Low]
+            .into_iter()
+            .find(|q| q.name() == name)
+    }
+}
+
+/// 🚀 Ship paint presets; the extras are unlocked by achievements
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShipColor {
+    Blue,
+    Orange,
+    Green,
+    Purple,
+    Gold,
+    Crimson,
+}
+
+impl ShipColor {
+    pub const ALL: [ShipColor; 6] = [
+        ShipColor::Blue,
+        ShipColor::Orange,
+        ShipColor::Green,
+        ShipColor::Purple,
+        ShipColor::Gold,
+        ShipColor::Crimson,
+    ];
+
+    /// Mid-tone of the hull; every other ship shade is derived from it
+    pub fn base(self) -> (u8, u8, u8) {
+        match self {
+            ShipColor::Blue => (120, 200, 255),
+            ShipColor::Orange => (255, 200, 120),
+            ShipColor::Green => (120, 255, 170),
+            ShipColor::Purple => (200, 150, 255),
+            ShipColor::Gold => (255, 220, 100),
+            ShipColor::Crimson => (255, 120, 130),
+        }
+    }
+
+    /// Outer engine flame
+    pub fn flame(self) -> (u8, u8, u8) {
+        match self {
+            ShipColor::Blue | ShipColor::Gold => (255, 100, 30),
+            ShipColor::Orange => (255, 70, 40),
+            ShipColor::Green => (170, 255, 60),
+            ShipColor::Purple => (255, 80, 210),
+            ShipColor::Crimson => (255, 50, 40),
+        }
+    }
+
+    /// Achievement needed before the preset can be picked
+    pub fn unlocked_by(self) -> Option<Achievement> {
+        match self {
+            ShipColor::Blue | ShipColor::Orange => None,
+            ShipColor::Green => Some(Achievement::FirstBlood),
+            ShipColor::Purple => Some(Achievement::NoHitWave),
+            ShipColor::Gold => Some(Achievement::HundredKills),
+            ShipColor::Crimson => Some(Achievement::Survivor),
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            ShipColor::Blue => "blue",
+            ShipColor::Orange => "orange",
+            ShipColor::Green => "green",
+            ShipColor::Purple => "purple",
+            ShipColor::Gold => "gold",
+            ShipColor::Crimson => "crimson",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<ShipColor> {
+        ShipColor::ALL.into_iter().find(|c| c.name() == name)
+    }
+}
+
+/// 🕹️ How the touch/mouse pilot steers and shoots
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ControlScheme {
+    /// One stick moves, the other aims and fires
+    TwinStick,
+    /// One stick moves; the ship aims at the nearest enemy (or where it's
+    /// heading) and fires on its own
+    SingleStick,
+}
+
+impl ControlScheme {
+    pub fn name(self) -> &'static str {
+        match self {
+            ControlScheme::TwinStick => "twin-stick",
+            ControlScheme::SingleStick => "single-stick",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<ControlScheme> {
+        [ControlScheme::TwinStick, ControlScheme::SingleStick]
+            .into_iter()
+            .find(|c| c.name() == name)
+    }
+
+    pub fn toggled(self) -> ControlScheme {
+        match self {
+            ControlScheme::TwinStick => ControlScheme::SingleStick,
+            ControlScheme::SingleStick => ControlScheme::TwinStick,
+        }
+    }
+}
+
+/// 🌬️ Global force pushing everything in the arena around
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ForceField {
+    Off,
+    /// Constant pull in one direction (`force_angle`)
+    Gravity,
+    /// Swirl around the middle of the screen, drawing slightly inward
+    Vortex,
+}
+
+impl ForceField {
+    pub fn name(self) -> &'static str {
+        match self {
+            ForceField::Off => "off",
+            ForceField::Gravity => "gravity",
+            ForceField::Vortex => "vortex",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<ForceField> {
+        [ForceField::Off, ForceField::Gravity, ForceField::Vortex]
+            .into_iter()
+            .find(|f| f.name() == name)
+    }
+}
+
+/// 🎯 What to draw along the aim while the aim stick is held
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AimIndicator {
+    /// Faint line out from the nose
+    Line,
+    /// Crosshair at a fixed distance ahead
+    Reticle,
+    None,
+}
+
+impl AimIndicator {
+    pub fn name(self) -> &'static str {
+        match self {
+            AimIndicator::Line => "line",
+            AimIndicator::Reticle => "reticle",
+            AimIndicator::None => "none",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<AimIndicator> {
+        [AimIndicator::Line, AimIndicator::Reticle, AimIndicator::None]
+            .into_iter()
+            .find(|a| a.name() == name)
+    }
+}
+
+/// 💥 What touching an enemy does to a pilot
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContactResponse {
+    /// Any contact costs a heart and destroys the enemy
+    Lethal,
+    /// Slow contact bounces both apart; ramming at speed hurts whoever
+    /// was hit, so a fast pilot damages the enemy and a fast enemy the pilot
+    Bump,
+}
+
+impl ContactResponse {
+    pub fn name(self) -> &'static str {
+        match self {
+            ContactResponse::Lethal => "lethal",
+            ContactResponse::Bump => "bump",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<ContactResponse> {
+        [ContactResponse::Lethal, ContactResponse::Bump]
+            .into_iter()
+            .find(|c| c.name() == name)
+    }
+}
+
+/// 🧱 What happens at the screen edge
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Boundary {
+    /// Leave one side, come back in on the other
+    Wrap,
+    /// The edge is solid: ships stop, enemies bounce
+    Walls,
+}
+
+impl Boundary {
+    pub fn name(self) -> &'static str {
+        match self {
+            Boundary::Wrap => "wrap",
+            Boundary::Walls => "walls",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Boundary> {
+        [Boundary::Wrap, Boundary::Walls]
+            .into_iter()
+            .find(|b| b.name() == name)
+    }
+
+    /// Per-entity policies this preset stands for; bullets always leave
+    pub fn edges(self) -> EdgePolicies {
+        let ships = match self {
+            Boundary::Wrap => EdgePolicy::Wrap,
+            Boundary::Walls => EdgePolicy::Clamp,
+        };
+        EdgePolicies { player: ships, enemies: ships, bullets: EdgePolicy::Destroy }
+    }
+}
+
+/// 🧱 What one kind of entity does at the screen edge
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EdgePolicy {
+    /// Leave one side, come back in on the other
+    Wrap,
+    /// The edge is solid: ships stop, enemies and bullets bounce
+    Clamp,
+    /// Gone once it leaves
+    Destroy,
+}
+
+impl EdgePolicy {
+    pub fn name(self) -> &'static str {
+        match self {
+            EdgePolicy::Wrap => "wrap",
+            EdgePolicy::Clamp => "clamp",
+            EdgePolicy::Destroy => "destroy",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<EdgePolicy> {
+        [EdgePolicy::Wrap, EdgePolicy::Clamp, EdgePolicy::Destroy]
+            .into_iter()
+            .find(|p| p.name() == name)
+    }
+}
+
+/// 🧱 Edge policy for each kind of entity, set independently
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EdgePolicies {
+    pub player: EdgePolicy,
+    pub enemies: EdgePolicy,
+    /// Both pilot and enemy shots
+    pub bullets: EdgePolicy,
+}
+
+/// 🎚️ How much pressure the game is allowed to pile on
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Difficulty {
+    Easy,
+    Normal,
+    Hard,
+}
+
+impl Difficulty {
+    pub const ALL: [Difficulty; 3] = [Difficulty::Easy, Difficulty::Normal, Difficulty::Hard];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Difficulty::Easy => "easy",
+            Difficulty::Normal => "normal",
+            Difficulty::Hard => "hard",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Difficulty> {
+        Difficulty::ALL
+            .into_iter()
+            .find(|d| d.name() == name)
+    }
+
+    /// How far Shooters lead a moving pilot (0 = direct fire, 1 = perfect lead)
+    pub fn shooter_lead(self) -> f32 {
+        match self {
+            Difficulty::Easy => 0.0,
+            Difficulty::Normal => 0.5,
+            Difficulty::Hard => 0.9,
+        }
+    }
+
+    /// Live enemies at which wave spawning waits for kills
+    pub fn max_concurrent_enemies(self) -> usize {
+        match self {
+            Difficulty::Easy => 12,
+            Difficulty::Normal => 24,
+            Difficulty::Hard => 40,
+        }
+    }
+}
+
+/// 🎒 Starting kit picked on the title screen, layered over the weapon
+/// settings for every pilot in the run
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Loadout {
+    /// The standard kit
+    Blaster,
+    /// A short-range fan of extra pellets and a quicker dash
+    Spread,
+    /// Slow, fast, piercing shots; no dash, but slow fields don't hold it back
+    Sniper,
+}
+
+impl Loadout {
+    pub const ALL: [Loadout; 3] = [Loadout::Blaster, Loadout::Spread, Loadout::Sniper];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Loadout::Blaster => "blaster",
+            Loadout::Spread => "spread",
+            Loadout::Sniper => "sniper",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Loadout> {
+        Loadout::ALL.into_iter().find(|l| l.name() == name)
+    }
+
+    /// One line for the title screen
+    pub fn description(self) -> &'static str {
+        match self {
+            Loadout::Blaster => "Balanced shots, dash",
+            Loadout::Spread => "Wide short fan, quick dash",
+            Loadout::Sniper => "Piercing long shots, ignores slow fields",
+        }
+    }
+
+    /// Multiplier on shot speed and on range (shot life)
+    pub fn shot_scale(self) -> (f32, f32) {
+        match self {
+            Loadout::Blaster => (1.0, 1.0),
+            Loadout::Spread => (0.9, 0.6),
+            Loadout::Sniper => (1.5, 1.3),
+        }
+    }
+
+    /// Replacement look for the standard shot; None keeps the blaster's
+    pub fn bullet_look(self) -> Option<BulletLook> {
+        match self {
+            Loadout::Blaster => None,
+            Loadout::Spread => Some(BulletLook { glow: (255, 170, 90), core: (255, 215, 160), radius: 3.5, trail: 0.03 }),
+            Loadout::Sniper => Some(BulletLook { glow: (120, 180, 255), core: (205, 228, 255), radius: 4.0, trail: 0.1 }),
+        }
+    }
+
+    /// Multiplier on the time between trigger pulls
+    pub fn fire_interval_scale(self) -> f32 {
+        match self {
+            Loadout::Blaster => 1.0,
+            Loadout::Spread => 1.3,
+            Loadout::Sniper => 2.0,
+        }
+    }
+
+    /// Multiplier on the angle between neighbouring pellets
+    pub fn fan_scale(self) -> f32 {
+        match self {
+            Loadout::Spread => 1.6,
+            _ => 1.0,
+        }
+    }
+
+    /// Enemies every shot passes through on top of the weapon level's
+    pub fn extra_pierce(self) -> u32 {
+        match self {
+            Loadout::Sniper => 1,
+            _ => 0,
+        }
+    }
+
+    /// Weapon level a run starts on (index into `weapon_levels`)
+    pub fn starting_weapon_level(self) -> usize {
+        match self {
+            Loadout::Spread => 1,
+            _ => 0,
+        }
+    }
+
+    /// Multiplier on the dash cooldown; None if the kit has no dash
+    pub fn dash_cooldown_scale(self) -> Option<f32> {
+        match self {
+            Loadout::Blaster => Some(1.0),
+            Loadout::Spread => Some(0.6),
+            Loadout::Sniper => None,
+        }
+    }
+
+    pub fn slow_field_immune(self) -> bool {
+        self == Loadout::Sniper
+    }
+}
+
+/// 🎮 Rules for a run
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GameMode {
+    /// Waves, hearts and a scored run
+    Normal,
+    /// Solo run on a seed shared by everyone playing that day, with its own best
+    Daily,
+    /// No death, manual spawns, nothing recorded
+    Sandbox,
+    /// A fixed run of escalating bosses; clearing it wins, best time is kept
+    BossRush,
+    /// One heart, no healing, no spare lives; every run is logged and
+    /// ranked on its own board
+    Hardcore,
+    /// A single boss fight picked from the codex; nothing recorded
+    Practice,
+}
+
+impl GameMode {
+    pub fn name(self) -> &'static str {
+        match self {
+            GameMode::Normal => "normal",
+            GameMode::Daily => "daily",
+            GameMode::Sandbox => "sandbox",
+            GameMode::BossRush => "boss_rush",
+            GameMode::Hardcore => "hardcore",
+            GameMode::Practice => "practice",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<GameMode> {
+        [GameMode::Normal, GameMode::Daily, GameMode::Sandbox, GameMode::BossRush, GameMode::Hardcore]
+            .into_iter()
+            .find(|m| m.name() == name)
+    }
+}
+
+/// 🧯 Soft limits on live entities; generous so they only bite in
+/// runaway situations
+#[derive(Debug, Clone)]
+pub struct EntityCaps {
+    pub bullets: usize,
+    pub enemy_bullets: usize,
+    pub enemies: usize,
+    pub explosions: usize,
+    pub floating_texts: usize,
+}
+
+impl Default for EntityCaps {
+    fn default() -> Self {
+        Self {
+            bullets: 600,
+            enemy_bullets: 400,
+            enemies: 200,
+            explosions: 150,
+            floating_texts: 80,
+        }
+    }
+}
+
+/// 📱 HUD insets in pixels from each screen edge, to keep it clear of
+/// notches and rounded corners. macroquad can't query the platform's
+/// safe area, so these come from settings.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SafeArea {
+    pub left: f32,
+    pub top: f32,
+    pub right: f32,
+    pub bottom: f32,
+}
+
+/// 🕹️ Size and look of the touch joysticks
+#[derive(Debug, Clone, Copy)]
+pub struct StickStyle {
+    /// How far the thumb can travel from the base center
+    pub radius: f32,
+    /// Opacity of the base ring (0.0 - 1.0)
+    pub base_opacity: f32,
+    pub thumb_radius: f32,
+    /// Opacity of the thumb (0.0 - 1.0)
+    pub thumb_opacity: f32,
+    /// Circular or square thumb travel
+    pub gate: StickGate,
+}
+
+/// 🔥 Overdrive: a power spike earned by keeping a combo going
+#[derive(Debug, Clone, Copy)]
+pub struct Overdrive {
+    /// Combo count that triggers it (again at every multiple); 0 disables
+    pub combo: i32,
+    /// Seconds it lasts, the last of which winds back down to normal
+    pub duration: f32,
+    /// Fire-rate multiplier at full power
+    pub fire_rate: f32,
+    /// Movement speed multiplier at full power
+    pub speed: f32,
+}
+
+/// 📈 Adaptive difficulty: after each wave, spawn rate and enemy speed
+/// drift toward how well the pilots are doing, never past the bounds
+#[derive(Debug, Clone, Copy)]
+pub struct Adaptive {
+    /// Off by default; never applies outside normal mode
+    pub enabled: bool,
+    /// Lowest and highest scale on spawn rate and enemy speed
+    pub min: f32,
+    pub max: f32,
+    /// Largest change a single wave can make
+    pub step: f32,
+}
+
+/// 💥 Hit-stop per event: seconds the simulation freezes when it lands
+/// (0 disables that event's stop)
+#[derive(Debug, Clone)]
+pub struct HitStop {
+    /// Any bullet that damages a boss
+    pub boss_hit: f32,
+    /// Two or more enemies destroyed in the same frame
+    pub multi_kill: f32,
+    /// Firing the special ring
+    pub special: f32,
+    /// Most frozen time one burst of stops can add up to. Spent budget
+    /// comes back steadily, all of it over `window` seconds.
+    pub budget: f32,
+    pub window: f32,
+}
+
+impl Default for HitStop {
+    fn default() -> Self {
+        Self {
+            boss_hit: 0.015,
+            multi_kill: 0.05,
+            special: 0.08,
+            budget: 0.15,
+            window: 1.0,
+        }
+    }
+}
+
+/// 🎨 How a shot is drawn
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BulletLook {
+    /// Halo and tracer tint
+    pub glow: (u8, u8, u8),
+    /// Solid body tint
+    pub core: (u8, u8, u8),
+    /// Body radius (px); drawing only, hits use the shot's own radius
+    pub radius: f32,
+    /// Seconds of travel the tracer streak trails behind (0 for none)
+    pub trail: f32,
+}
+
+/// 🔫 Shot tunables for one weapon; `speed * life` is its range
+#[derive(Debug, Clone, Copy)]
+pub struct WeaponStats {
+    /// Muzzle speed (px/s)
+    pub speed: f32,
+    /// Seconds before a shot fizzles out
+    pub life: f32,
+    /// Random deviation either side of the aim, in degrees (0 is pinpoint)
+    pub spread: f32,
+    pub look: BulletLook,
+}
+
+/// 🔫 Burst fire for the standard shot: each trigger pull lets off `size`
+/// rounds `delay` seconds apart before the full fire interval
+#[derive(Debug, Clone, Copy)]
+pub struct BurstFire {
+    /// Rounds per burst (1 is steady auto-fire)
+    pub size: u32,
+    /// Seconds between rounds within a burst
+    pub delay: f32,
+}
+
+/// ⬆️ Shot pattern at one weapon level
+#[derive(Debug, Clone, Copy)]
+pub struct WeaponLevel {
+    /// Shots per trigger pull, fanned around the aim
+    pub pellets: u32,
+    /// Seconds between trigger pulls
+    pub fire_interval: f32,
+    /// Extra enemies each shot passes through (on top of the Pierce power-up)
+    pub pierce: u32,
+}
+
+/// 🌠 Player trail colour by speed: `cruise` up to `cruise_speed`, blending
+/// to `top` at `top_speed` and beyond
+#[derive(Debug, Clone, Copy)]
+pub struct TrailColors {
+    pub cruise_speed: f32,
+    pub top_speed: f32,
+    pub cruise: (u8, u8, u8),
+    pub top: (u8, u8, u8),
+    /// Flashed while the pilot is invulnerable after a hit
+    pub warning: (u8, u8, u8),
+}
+
+/// 🌠 Engine trail shape: how many segments each pilot leaves and how
+/// quickly they fade and shrink (per second)
+#[derive(Debug, Clone, Copy)]
+pub struct TrailStyle {
+    /// Segments kept per pilot; the quality preset's cap still applies on top
+    pub max_segments: usize,
+    pub fade_rate: f32,
+    pub shrink_rate: f32,
+}
+
+/// ⚙️ Tunable game settings
+#[derive(Debug, Clone)]
+pub struct Config {
+    /// Record the player's path so the next run can race its ghost
+    pub ghost_recording: bool,
+    /// Seconds between recorded ghost samples
+    pub ghost_sample_interval: f32,
+    /// Maximum ghost samples kept (oldest are dropped first)
+    pub ghost_max_samples: usize,
+    /// Draw edge arrows pointing at enemies outside the visible area
+    pub offscreen_indicators: bool,
+    /// Combo counts that trigger a center-screen streak announcement
+    pub streak_tiers: Vec<(i32, String)>,
+    /// Kills landing within this many seconds of each other build a chain
+    pub chain_window: f32,
+    /// Chain bonus: `chain_bonus * (links - 1) ^ chain_growth` for each
+    /// kill after the first in a chain
+    pub chain_bonus: i32,
+    pub chain_growth: f32,
+    /// Extra share of kill score paid while nobody has been hit this run
+    pub hitless_bonus: f32,
+    /// Radius of the slow fields some waves lay down
+    pub slow_field_radius: f32,
+    /// Share of its normal speed anything keeps inside a slow field
+    pub slow_field_factor: f32,
+    /// How quickly the HUD score counts up to the real one (per second,
+    /// exponential; higher is snappier)
+    pub score_tick_rate: f32,
+    /// Rendering quality preset (cycled in-game with Q)
+    pub quality: Quality,
+    /// Simulate in fixed steps of this many seconds (rendering interpolates
+    /// between them) for reproducible runs; `None` steps once per frame.
+    pub fixed_timestep: Option<f32>,
+    /// Frame rate limit; `None` runs uncapped
+    pub fps_cap: Option<u32>,
+    pub difficulty: Difficulty,
+    /// Wave spawns pause while this many enemies are alive; set from
+    /// `difficulty`. `caps.enemies` stays as the hard safety net
+    pub max_concurrent_enemies: usize,
+    /// Shooter aim from direct (0.0) to a perfect intercept (1.0); set from `difficulty`
+    pub shooter_lead: f32,
+    /// Per-kind entity limits enforced at the end of each update
+    pub caps: EntityCaps,
+    /// Aim guide drawn for the twin-stick pilot
+    pub aim_indicator: AimIndicator,
+    /// Twin- or single-stick for the touch/mouse pilot (toggled on the title with M)
+    pub controls: ControlScheme,
+    /// Accessibility: no shake, strobing, flashes, slow-mo, hit-stop,
+    /// trails or drifting background (toggled in game with F8)
+    pub reduced_motion: bool,
+    /// Paint for the first pilot's ship (C cycles through unlocked ones)
+    pub ship_color: ShipColor,
+    /// Starting kit, picked on the title screen (the last one used is kept)
+    pub loadout: Loadout,
+    pub trail_colors: TrailColors,
+    pub trail: TrailStyle,
+    /// Seconds the fade-in from black takes at the start of a run; any
+    /// input skips it
+    pub intro_duration: f32,
+    /// The first-launch walkthrough has been finished or skipped
+    pub tutorial_completed: bool,
+    /// Append each finished run's stats to `runs.jsonl`
+    pub stats_log: bool,
+    /// Faint reference grid behind everything (toggled in game with G)
+    pub grid: bool,
+    /// Pixels between grid lines
+    pub grid_spacing: f32,
+    /// Grid line color as RGBA
+    pub grid_color: [u8; 4],
+    /// Fraction of the lead pilot's velocity the grid scrolls against;
+    /// 0 holds it still
+    pub grid_parallax: f32,
+    /// Camera zoom on the lead pilot: 1.0 shows the whole arena, higher
+    /// zooms in and follows (the view never leaves the arena)
+    pub zoom: f32,
+    /// How much of the extra zoom is let out when moving fast or with
+    /// enemies closing in (0 keeps the zoom fixed, 1 pulls back fully)
+    pub dynamic_zoom: f32,
+    /// Region the HUD is anchored within
+    pub safe_area: SafeArea,
+    /// Freeze-frame lengths for impactful events
+    pub hitstop: HitStop,
+    pub overdrive: Overdrive,
+    /// The standard shot
+    pub blaster: WeaponStats,
+    /// How the standard shot's trigger pulls are split into bursts
+    pub burst: BurstFire,
+    /// Shots fired while the Pierce power-up is active: faster, shorter range
+    pub pierce_shot: WeaponStats,
+    /// Lobbed shells fired while the Mortar power-up is active; they burst
+    /// when they land (run out of life) or strike an enemy
+    pub mortar_shot: WeaponStats,
+    /// Downward pull on mortar shells (px/s²), which bends them into an arc
+    pub mortar_gravity: f32,
+    /// Enemy shots
+    pub enemy_bullet_look: BulletLook,
+    /// Enemy shots parried back, now the pilots'
+    pub reflected_look: BulletLook,
+    /// Weapon upgrade curve: the run starts at the first entry and each
+    /// weapon pickup climbs one step, up to the last
+    pub weapon_levels: Vec<WeaponLevel>,
+    /// Taking a hit drops the weapon a level
+    pub lose_level_on_hit: bool,
+    /// A downed pilot with a spare life respawns instead of dying
+    pub extra_lives: bool,
+    /// Scores that each award a spare life, in ascending order
+    pub extra_life_scores: Vec<i32>,
+    /// Fire rate at the lightest aim-stick touch, as a fraction of the
+    /// weapon's full rate (reached at full deflection)
+    pub min_fire_rate: f32,
+    /// Screen-edge behaviour per entity; the `boundary` setting picks a
+    /// preset and `player_edges` etc. override single entries
+    pub edges: EdgePolicies,
+    /// Room enemies get past the screen edge (where they also spawn) before
+    /// the edge policy applies, on top of their own size so big ones don't
+    /// pop at the seam
+    pub enemy_edge_margin: f32,
+    /// Seconds an enemy (other than a boss) may spend wholly off-screen
+    /// before it's quietly removed
+    pub enemy_cull_time: f32,
+    /// World force field acting on ships and enemies
+    pub force_field: ForceField,
+    /// Field acceleration in px/s² (capped below ship thrust)
+    pub force_strength: f32,
+    /// Gravity direction in degrees clockwise from screen right (90 = down)
+    pub force_angle: f32,
+    /// Bullets drift in the field too
+    pub force_on_bullets: bool,
+    /// Player velocity decay rate per second (higher = less drift)
+    pub player_damping: f32,
+    /// Thrust response to stick deflection (physics, not input shaping)
+    pub thrust_curve: ThrustCurve,
+    /// Movement multiplier while the focus button is held
+    pub focus_speed: f32,
+    /// Collision radius of the player ship (the sprite itself is larger)
+    pub hitbox_radius: f32,
+    /// Distance outside the hitbox within which a passing enemy bullet counts as a graze
+    pub graze_radius: f32,
+    /// Enemies within this distance of a Support are immune to bullets
+    pub support_shield_radius: f32,
+    /// Hearts an armed mine takes off the pilot who touches it
+    pub mine_damage: i32,
+    /// Seconds after being dropped before a mine goes live
+    pub mine_arm_delay: f32,
+    /// Seconds an untouched mine stays on the field
+    pub mine_lifetime: f32,
+    /// Hearts restored to each living pilot when a wave is cleared
+    pub wave_clear_heal: i32,
+    /// Wave spawns never land closer than this to a living pilot
+    pub spawn_exclusion_radius: f32,
+    /// Score for each grazed enemy bullet
+    pub graze_score: i32,
+    /// Special meter gained per kill (scaled by the combo multiplier)
+    pub special_per_kill: f32,
+    /// Special meter gained per graze
+    pub special_per_graze: f32,
+    /// How far the special ring expands before fading out; None reaches
+    /// every corner of the screen, a radius makes it a local mini-bomb
+    pub special_ring_radius: Option<f32>,
+    /// Seconds of invulnerability for the pilot who fires the ring
+    pub special_iframes: f32,
+    /// Damage the special ring deals to each enemy it sweeps over
+    pub special_ring_damage: i32,
+    /// Seconds the Freeze power-up holds enemies and their bullets still
+    pub freeze_duration: f32,
+    /// Seconds an enemy flashes white after taking a hit
+    pub enemy_hit_flash: f32,
+    /// Poise per enemy kind: damage it soaks in quick succession before
+    /// its armor breaks and it staggers. Kinds not listed never stagger
+    pub poise: Vec<(EnemyKind, f32)>,
+    /// Poise recovered per second while not staggered
+    pub poise_regen: f32,
+    /// Seconds a broken enemy stays staggered
+    pub stagger_time: f32,
+    /// Damage multiplier against a staggered enemy
+    pub stagger_damage: i32,
+    /// Player shots knock enemy shots out of the air (both are destroyed)
+    pub bullet_cancel: bool,
+    /// Speed (px/s) a non-lethal shot knocks a Chaser-sized enemy back;
+    /// scaled down for bigger enemies. 0 disables knockback
+    pub knockback_impulse: f32,
+    /// Speed (px/s) an explosion shoves bullets, enemies and pilots one
+    /// blast-size from its center; stronger closer in and for bigger
+    /// blasts. 0 disables shockwaves
+    pub shockwave_impulse: f32,
+    /// Spawned enemies are scaled up to this fraction bigger or smaller than
+    /// their kind's base size; big ones are slower, tougher and worth more
+    pub size_variation: f32,
+    /// Damage a dash deals to each enemy it passes through
+    pub dash_damage: i32,
+    /// Enemies one dash can hit before it stops doing damage
+    pub dash_max_hits: usize,
+    /// Pilot-vs-enemy contact rules
+    pub contact: ContactResponse,
+    /// Closing speed (px/s) that turns a bump into a ram
+    pub ram_speed: f32,
+    /// Damage a pilot's ram deals to the enemy
+    pub ram_damage: i32,
+    /// Seconds of an active wave without a kill before enemies enrage
+    /// (faster spawns and movement); `None` disables it
+    pub enrage_after: Option<f32>,
+    /// Seconds for enrage to ramp from nothing to full strength
+    pub enrage_ramp: f32,
+    pub adaptive: Adaptive,
+    /// Two local pilots sharing the keyboard instead of one twin-stick pilot
+    pub coop: bool,
+    /// Ruleset for the current run (picked on the title screen)
+    pub mode: GameMode,
+    /// Boss fought in a practice run
+    pub practice_boss: EnemyKind,
+    /// Keep generating waves after the authored table; when off, clearing
+    /// its last wave wins the run
+    pub endless: bool,
+    /// Fixed run seed to replay a shared run; `None` rolls a fresh one each run
+    pub seed: Option<u32>,
+    /// Distance at which Circlers stop closing in and start orbiting
+    pub circler_orbit_radius: f32,
+    /// Seconds a Circler orbits before committing to its dive
+    pub circler_orbit_time: f32,
+    /// Furthest a Blinker jumps toward its target in one blink
+    pub blink_distance: f32,
+    /// Seconds a Blinker stays put between blinks
+    pub blink_cooldown: f32,
+    /// Scavenger flee speed (px/s, before size scaling)
+    pub scavenger_speed: f32,
+    /// Seconds a Scavenger must survive before it escapes with its loot
+    pub scavenger_escape_time: f32,
+    /// Score an escaping Scavenger steals (along with the nearest power-up)
+    pub scavenger_steal: i32,
+    /// Bonus on top of its score for catching one
+    pub scavenger_bonus: i32,
+    /// Carrier flee speed (px/s, before size scaling)
+    pub carrier_speed: f32,
+    /// Seconds a Carrier must survive before it escapes with its power-up
+    pub carrier_escape_time: f32,
+    /// A Carrier joins every this many waves, as the wave opens (0: never)
+    pub carrier_every: u32,
+    /// Seconds behind the lead pilot a Mimic replays their movement
+    pub mimic_delay: f32,
+    /// Movement stick on the right half of the screen, aim on the left
+    pub swap_sticks: bool,
+    pub sticks: StickStyle,
+    /// Flip the vertical axis of the aim stick
+    pub invert_aim_y: bool,
+    /// Multiplier on both sticks' deflection (output still caps at full tilt)
+    pub input_sensitivity: f32,
+    /// Stick smoothing: share of the previous value kept each 60Hz frame.
+    /// 0 disables it; the default only filters jitter, it doesn't add lag.
+    pub input_smoothing: f32,
+    /// Developer tools (god mode, spawn keys, wave skip, overlay). Off
+    /// unless `debug=true` is in the settings file, and only honoured in
+    /// debug builds.
+    pub debug: bool,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            ghost_recording: true,
+            ghost_sample_interval: 0.1,
+            ghost_max_samples: 3000, // 5 minutes at 10 samples/sec
+            offscreen_indicators: true,
+            score_tick_rate: 6.0,
+            chain_window: 0.5,
+            chain_bonus: 25,
+            chain_growth: 1.5,
+            hitless_bonus: 0.25,
+            slow_field_radius: 110.0,
+            slow_field_factor: 0.5,
+            streak_tiers: vec![
+                (2, "DOUBLE KILL".to_owned()),
+                (3, "TRIPLE KILL".to_owned()),
+                (5, "5x STREAK".to_owned()),
+                (10, "RAMPAGE".to_owned()),
+                (20, "UNSTOPPABLE".to_owned()),
+            ],
+            quality: Quality::High,
+            fixed_timestep: None,
+            fps_cap: Some(60),
+            difficulty: Difficulty::Normal,
+            loadout: Loadout::Blaster,
+            max_concurrent_enemies: Difficulty::Normal.max_concurrent_enemies(),
+            shooter_lead: Difficulty::Normal.shooter_lead(),
+            caps: EntityCaps::default(),
+            hitstop: HitStop::default(),
+            overdrive: Overdrive {
+                combo: 15,
+                duration: 6.0,
+                fire_rate: 1.6,
+                speed: 1.3,
+            },
+            blaster: WeaponStats {
+                speed: 600.0,
+                life: 2.0,
+                spread: 0.0,
+                look: BulletLook { glow: (100, 255, 200), core: (150, 255, 220), radius: 5.0, trail: 0.04 },
+            },
+            burst: BurstFire { size: 1, delay: 0.06 },
+            pierce_shot: WeaponStats {
+                speed: 750.0,
+                life: 1.2,
+                spread: 0.0,
+                look: BulletLook { glow: (190, 150, 255), core: (225, 205, 255), radius: 4.5, trail: 0.06 },
+            },
+            mortar_shot: WeaponStats {
+                speed: 420.0,
+                life: 1.1,
+                spread: 4.0,
+                look: BulletLook { glow: (255, 140, 60), core: (255, 170, 90), radius: 6.0, trail: 0.0 },
+            },
+            mortar_gravity: 480.0,
+            enemy_bullet_look: BulletLook { glow: (255, 60, 60), core: (255, 120, 100), radius: 5.0, trail: 0.04 },
+            reflected_look: BulletLook { glow: (255, 210, 80), core: (255, 240, 160), radius: 5.0, trail: 0.04 },
+            weapon_levels: vec![
+                WeaponLevel { pellets: 1, fire_interval: 0.15, pierce: 0 },
+                WeaponLevel { pellets: 2, fire_interval: 0.14, pierce: 0 },
+                WeaponLevel { pellets: 3, fire_interval: 0.13, pierce: 0 },
+                WeaponLevel { pellets: 3, fire_interval: 0.11, pierce: 1 },
+                WeaponLevel { pellets: 5, fire_interval: 0.10, pierce: 1 },
+            ],
+            lose_level_on_hit: true,
+            extra_lives: true,
+            extra_life_scores: vec![10_000, 30_000, 60_000, 100_000],
+            min_fire_rate: 0.35,
+            safe_area: SafeArea::default(),
+            aim_indicator: AimIndicator::Line,
+            controls: ControlScheme::TwinStick,
+            reduced_motion: false,
+            ship_color: ShipColor::Blue,
+            trail_colors: TrailColors {
+                cruise_speed: 340.0,
+                top_speed: 800.0,
+                cruise: (150, 220, 255),
+                top: (230, 255, 255),
+                warning: (255, 110, 90),
+            },
+            trail: TrailStyle {
+                max_segments: 20,
+                fade_rate: 2.0,
+                shrink_rate: 40.0,
+            },
+            intro_duration: 2.0,
+            tutorial_completed: false,
+            stats_log: false,
+            grid: false,
+            zoom: 1.0,
+            dynamic_zoom: 0.5,
+            grid_spacing: 64.0,
+            grid_color: [60, 80, 150, 40],
+            grid_parallax: 0.05,
+            edges: Boundary::Wrap.edges(),
+            enemy_edge_margin: 30.0,
+            enemy_cull_time: 6.0,
+            force_field: ForceField::Off,
+            force_strength: 0.0,
+            force_angle: 90.0,
+            force_on_bullets: false,
+            player_damping: DEFAULT_DAMPING,
+            thrust_curve: ThrustCurve::Linear,
+            focus_speed: 0.4,
+            hitbox_radius: HITBOX_RADIUS,
+            graze_radius: 18.0,
+            spawn_exclusion_radius: 220.0,
+            wave_clear_heal: 1,
+            mine_damage: 1,
+            mine_arm_delay: 0.8,
+            mine_lifetime: 10.0,
+            support_shield_radius: 160.0,
+            graze_score: 20,
+            special_per_kill: 0.03,
+            special_per_graze: 0.02,
+            special_ring_radius: None,
+            special_iframes: 1.0,
+            special_ring_damage: 5,
+            freeze_duration: 4.0,
+            bullet_cancel: false,
+            poise: vec![
+                (EnemyKind::Splitter, 3.0),
+                (EnemyKind::Support, 3.0),
+                (EnemyKind::Boss, 12.0),
+            ],
+            poise_regen: 2.0,
+            stagger_time: 1.2,
+            stagger_damage: 2,
+            enemy_hit_flash: 0.1,
+            knockback_impulse: 260.0,
+            shockwave_impulse: 150.0,
+            size_variation: 0.25,
+            dash_damage: 2,
+            dash_max_hits: 3,
+            contact: ContactResponse::Lethal,
+            ram_speed: 220.0,
+            ram_damage: 2,
+            enrage_after: Some(20.0),
+            enrage_ramp: 15.0,
+            adaptive: Adaptive {
+                enabled: false,
+                min: 0.85,
+                max: 1.15,
+                step: 0.05,
+            },
+            coop: false,
+            mode: GameMode::Normal,
+            practice_boss: EnemyKind::Boss,
+            endless: true,
+            seed: None,
+            circler_orbit_radius: 180.0,
+            circler_orbit_time: 2.0,
+            blink_distance: 180.0,
+            blink_cooldown: 2.5,
+            scavenger_speed: 200.0,
+            scavenger_escape_time: 8.0,
+            scavenger_steal: 500,
+            scavenger_bonus: 300,
+            carrier_speed: 150.0,
+            carrier_escape_time: 12.0,
+            carrier_every: 4,
+            mimic_delay: 1.0,
+            swap_sticks: false,
+            sticks: StickStyle {
+                radius: 80.0,
+                base_opacity: 0.24,
+                thumb_radius: 20.0,
+                thumb_opacity: 0.24,
+                gate: StickGate::Circle,
+            },
+            invert_aim_y: false,
+            input_sensitivity: 1.0,
+            input_smoothing: 0.3,
+            debug: false,
+        }
+    }
+}
+
+impl Config {
+    /// 💾 Defaults overlaid with whatever the player saved last time
+    pub fn load() -> Self {
+        let mut config = Self::default();
+        let store = Store::load(SETTINGS_FILE);
+        if let Some(quality) = store.get::<String>("quality").and_then(|q| Quality::from_name(&q)) {
+            config.quality = quality;
+        }
+        if let Some(boundary) = store.get::<String>("boundary").and_then(|b| Boundary::from_name(&b)) {
+            config.edges = boundary.edges();
+        }
+        let edges = &mut config.edges;
+        for (key, policy) in [
+            ("player_edges", &mut edges.player),
+            ("enemy_edges", &mut edges.enemies),
+            ("bullet_edges", &mut edges.bullets),
+        ] {
+            if let Some(p) = store.get::<String>(key).and_then(|p| EdgePolicy::from_name(&p)) {
+                *policy = p;
+            }
+        }
+        if let Some(difficulty) = store.get::<String>("difficulty").and_then(|d| Difficulty::from_name(&d)) {
+            config.set_difficulty(difficulty);
+        }
+        if let Some(loadout) = store.get::<String>("loadout").and_then(|l| Loadout::from_name(&l)) {
+            config.loadout = loadout;
+        }
+        if let Some(contact) = store.get::<String>("contact").and_then(|c| ContactResponse::from_name(&c)) {
+            config.contact = contact;
+        }
+        if let Some(hz) = store.get::<f32>("fixed_hz") {
+            // 0 means variable timestep
+            config.fixed_timestep = Some(hz).filter(|&hz| hz > 0.0).map(|hz| 1.0 / hz);
+        }
+        if let Some(fps) = store.get::<u32>("fps_cap") {
+            // 0 means uncapped
+            config.fps_cap = Some(fps).filter(|&fps| fps > 0);
+        }
+        // Seeds are shared in the hex form shown on the game-over screen
+        config.seed = store.get::<String>("seed").and_then(|s| u32::from_str_radix(&s, 16).ok());
+        let inset = &mut config.safe_area;
+        inset.left = store.get("safe_left").unwrap_or(inset.left);
+        inset.top = store.get("safe_top").unwrap_or(inset.top);
+        inset.right = store.get("safe_right").unwrap_or(inset.right);
+        inset.bottom = store.get("safe_bottom").unwrap_or(inset.bottom);
+        let overdrive = &mut config.overdrive;
+        overdrive.combo = store.get("overdrive_combo").unwrap_or(overdrive.combo);
+        overdrive.duration = store.get("overdrive_duration").unwrap_or(overdrive.duration);
+        overdrive.fire_rate = store.get("overdrive_fire_rate").unwrap_or(overdrive.fire_rate);
+        overdrive.speed = store.get("overdrive_speed").unwrap_or(overdrive.speed);
+        config.blaster.spread = store.get("blaster_spread").unwrap_or(config.blaster.spread);
+        config.burst.size = store.get("burst_size").unwrap_or(config.burst.size);
+        config.burst.delay = store.get("burst_delay").unwrap_or(config.burst.delay);
+        config.pierce_shot.spread = store.get("pierce_spread").unwrap_or(config.pierce_shot.spread);
+        let trail = &mut config.trail;
+        trail.max_segments = store.get("trail_length").unwrap_or(trail.max_segments);
+        trail.fade_rate = store.get("trail_fade").unwrap_or(trail.fade_rate);
+        trail.shrink_rate = store.get("trail_shrink").unwrap_or(trail.shrink_rate);
+        let adaptive = &mut config.adaptive;
+        adaptive.enabled = store.get("adaptive_difficulty").unwrap_or(adaptive.enabled);
+        adaptive.min = store.get("adaptive_min").unwrap_or(adaptive.min);
+        adaptive.max = store.get("adaptive_max").unwrap_or(adaptive.max);
+        adaptive.step = store.get("adaptive_step").unwrap_or(adaptive.step);
+        if let Some(color) = store.get::<String>("ship_color").and_then(|c| ShipColor::from_name(&c)) {
+            config.ship_color = color;
+        }
+        if let Some(controls) = store.get::<String>("controls").and_then(|c| ControlScheme::from_name(&c)) {
+            config.controls = controls;
+        }
+        if let Some(mode) = store.get::<String>("mode").and_then(|m| GameMode::from_name(&m)) {
+            config.mode = mode;
+        }
+        if let Some(curve) = store.get::<String>("thrust_curve").and_then(|c| ThrustCurve::from_name(&c)) {
+            config.thrust_curve = curve;
+        }
+        if let Some(field) = store.get::<String>("force_field").and_then(|f| ForceField::from_name(&f)) {
+            config.force_field = field;
+        }
+        config.force_strength = store.get("force_strength").unwrap_or(config.force_strength);
+        config.force_angle = store.get("force_angle").unwrap_or(config.force_angle);
+        config.force_on_bullets = store.get("force_on_bullets").unwrap_or(config.force_on_bullets);
+        if let Some(indicator) = store.get::<String>("aim_indicator").and_then(|a| AimIndicator::from_name(&a)) {
+            config.aim_indicator = indicator;
+        }
+        config.grid = store.get("grid").unwrap_or(config.grid);
+        config.zoom = store.get("zoom").unwrap_or(config.zoom);
+        config.dynamic_zoom = store.get("dynamic_zoom").unwrap_or(config.dynamic_zoom);
+        config.extra_lives = store.get("extra_lives").unwrap_or(config.extra_lives);
+        config.stats_log = store.get("stats_log").unwrap_or(config.stats_log);
+        config.endless = store.get("endless").unwrap_or(config.endless);
+        config.tutorial_completed = store.get("tutorial_completed").unwrap_or(config.tutorial_completed);
+        config.intro_duration = store.get("intro_duration").unwrap_or(config.intro_duration);
+        config.reduced_motion = store.get("reduced_motion").unwrap_or(config.reduced_motion);
+        config.bullet_cancel = store.get("bullet_cancel").unwrap_or(config.bullet_cancel);
+        // 0 (or anything not positive) is screen-wide
+        if let Some(radius) = store.get::<f32>("special_radius") {
+            config.special_ring_radius = Some(radius).filter(|&r| r > 0.0);
+        }
+        config.special_iframes = store.get("special_iframes").unwrap_or(config.special_iframes);
+        config.swap_sticks = store.get("swap_sticks").unwrap_or(config.swap_sticks);
+        let sticks = &mut config.sticks;
+        sticks.radius = store.get("stick_radius").unwrap_or(sticks.radius);
+        sticks.base_opacity = store.get("stick_opacity").unwrap_or(sticks.base_opacity);
+        sticks.thumb_radius = store.get("thumb_radius").unwrap_or(sticks.thumb_radius);
+        sticks.thumb_opacity = store.get("thumb_opacity").unwrap_or(sticks.thumb_opacity);
+        if let Some(gate) = store.get::<String>("stick_gate").and_then(|g| StickGate::from_name(&g)) {
+            sticks.gate = gate;
+        }
+        config.invert_aim_y = store.get("invert_aim_y").unwrap_or(config.invert_aim_y);
+        config.input_sensitivity = store.get("sensitivity").unwrap_or(config.input_sensitivity);
+        config.input_smoothing = store.get("smoothing").unwrap_or(config.input_smoothing);
+        config.debug = store.get("debug").unwrap_or(config.debug);
+        config
+    }
+
+    /// 🎚️ Switch difficulty along with the settings it drives
+    pub fn set_difficulty(&mut self, difficulty: Difficulty) {
+        self.difficulty = difficulty;
+        self.max_concurrent_enemies = difficulty.max_concurrent_enemies();
+        self.shooter_lead = difficulty.shooter_lead();
+    }
+
+    /// 💾 Persist the player-facing options
+    pub fn save(&self) {
+        let mut store = Store::load(SETTINGS_FILE);
+        store.set("quality", self.quality.name());
+        store.set("player_edges", self.edges.player.name());
+        store.set("enemy_edges", self.edges.enemies.name());
+        store.set("bullet_edges", self.edges.bullets.name());
+        store.set("contact", self.contact.name());
+        store.set("difficulty", self.difficulty.name());
+        store.set("fps_cap", self.fps_cap.unwrap_or(0));
+        store.set("fixed_hz", self.fixed_timestep.map_or(0.0, |step| (1.0 / step).round()));
+        store.set("ship_color", self.ship_color.name());
+        store.set("loadout", self.loadout.name());
+        store.set("aim_indicator", self.aim_indicator.name());
+        store.set("controls", self.controls.name());
+        // Practice is picked from the codex, never reopened as the last mode
+        if self.mode != GameMode::Practice {
+            store.set("mode", self.mode.name());
+        }
+        store.set("thrust_curve", self.thrust_curve.name());
+        store.set("grid", self.grid);
+        store.set("zoom", self.zoom);
+        store.set("dynamic_zoom", self.dynamic_zoom);
+        store.set("tutorial_completed", self.tutorial_completed);
+        store.set("reduced_motion", self.reduced_motion);
+        store.set("bullet_cancel", self.bullet_cancel);
+        store.set("adaptive_difficulty", self.adaptive.enabled);
+        store.set("swap_sticks", self.swap_sticks);
+        store.set("stick_radius", self.sticks.radius);
+        store.set("stick_opacity", self.sticks.base_opacity);
+        store.set("thumb_radius", self.sticks.thumb_radius);
+        store.set("thumb_opacity", self.sticks.thumb_opacity);
+        store.set("stick_gate", self.sticks.gate.name());
+        store.set("invert_aim_y", self.invert_aim_y);
+        store.set("sensitivity", self.input_sensitivity);
+        store.set("smoothing", self.input_smoothing);
+        store.save();
+    }
+}
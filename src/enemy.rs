@@ -0,0 +1,592 @@
+use macroquad::prelude::Vec2;
+use crate::config::Config;
+use crate::game::PowerUpKind;
+
+/// 👾 Enemy archetypes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EnemyKind {
+    Drifter,       // Slow, flies straight along its spawn heading
+    Chaser,        // Homes in on the player
+    Splitter,      // Tanky chaser that bursts into children on death
+    SplitterChild, // Tiny fast fragment of a Splitter
+    Boss,          // Huge, slow and very tough
+    Shooter,       // Keeps its distance and fires aimed bullets
+    Circler,       // Orbits the player for a moment, then dives
+    Support,       // Hangs back and shields nearby enemies from bullets
+    Layer,         // Chases while dropping mines behind it
+    Blinker,       // Creeps, then vanishes and reappears closer to the player
+    Scavenger,     // Flees the player; escapes with stolen score if not caught
+    Carrier,       // Flees the player; drops the power-up it holds when caught
+    Mimic,         // Copies the player's movement a moment later
+}
+
+/// ✨ Blinker teleport cycle
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlinkPhase {
+    Visible,   // Creeping along, waiting out the cooldown
+    Vanishing, // Fading out; can't be hit
+    Appearing, // Fading back in at the new spot; can't be hit
+}
+
+/// 🌀 Circler behaviour cycle
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CirclerPhase {
+    Approach, // Close in to orbit radius
+    Orbit,    // Circle the player tangentially
+    Dive,     // Straight committed lunge
+}
+
+impl EnemyKind {
+    pub const ALL: [EnemyKind; 13] = [
+        EnemyKind::Drifter,
+        EnemyKind::Chaser,
+        EnemyKind::Splitter,
+        EnemyKind::SplitterChild,
+        EnemyKind::Shooter,
+        EnemyKind::Circler,
+        EnemyKind::Support,
+        EnemyKind::Layer,
+        EnemyKind::Blinker,
+        EnemyKind::Scavenger,
+        EnemyKind::Carrier,
+        EnemyKind::Mimic,
+        EnemyKind::Boss,
+    ];
+
+    /// Lowercase name used in data tables and save files
+    pub fn name(self) -> &'static str {
+        match self {
+            EnemyKind::Drifter => "drifter",
+            EnemyKind::Chaser => "chaser",
+            EnemyKind::Splitter => "splitter",
+            EnemyKind::SplitterChild => "splitter_child",
+            EnemyKind::Boss => "boss",
+            EnemyKind::Shooter => "shooter",
+            EnemyKind::Circler => "circler",
+            EnemyKind::Support => "support",
+            EnemyKind::Layer => "layer",
+            EnemyKind::Blinker => "blinker",
+            EnemyKind::Scavenger => "scavenger",
+            EnemyKind::Carrier => "carrier",
+            EnemyKind::Mimic => "mimic",
+        }
+    }
+
+    /// Display name for the codex
+    pub fn title(self) -> &'static str {
+        match self {
+            EnemyKind::SplitterChild => "Fragment",
+            EnemyKind::Drifter => "Drifter",
+            EnemyKind::Chaser => "Chaser",
+            EnemyKind::Splitter => "Splitter",
+            EnemyKind::Boss => "Boss",
+            EnemyKind::Shooter => "Shooter",
+            EnemyKind::Circler => "Circler",
+            EnemyKind::Support => "Support",
+            EnemyKind::Layer => "Layer",
+            EnemyKind::Blinker => "Blinker",
+            EnemyKind::Scavenger => "Scavenger",
+            EnemyKind::Carrier => "Carrier",
+            EnemyKind::Mimic => "Mimic",
+        }
+    }
+
+    /// One-line codex entry: what it does and how to handle it
+    pub fn description(self) -> &'static str {
+        match self {
+            EnemyKind::Drifter => "Drifts in a straight line. Easy points.",
+            EnemyKind::Chaser => "Homes in on you. Keep moving.",
+            EnemyKind::Splitter => "Bursts into fragments when destroyed.",
+            EnemyKind::SplitterChild => "Small and fast. Spawned by Splitters.",
+            EnemyKind::Boss => "Huge and tough, with changing attacks.",
+            EnemyKind::Shooter => "Keeps its distance and fires aimed shots.",
+            EnemyKind::Circler => "Orbits you, then dives in.",
+            EnemyKind::Support => "Shields nearby enemies. Take it out first.",
+            EnemyKind::Layer => "Leaves mines behind as it chases.",
+            EnemyKind::Blinker => "Vanishes and reappears closer to you.",
+            EnemyKind::Scavenger => "Runs off with your score unless caught.",
+            EnemyKind::Carrier => "Holds a power-up. Catch it before it escapes.",
+            EnemyKind::Mimic => "Copies your moves a moment later.",
+        }
+    }
+
+    /// (health, size, speed, score value)
+    pub fn stats(self) -> (i32, f32, f32, i32) {
+        match self {
+            EnemyKind::Drifter => (1, 20.0, 90.0, 50),
+            EnemyKind::Chaser => (2, 25.0, 150.0, 100),
+            EnemyKind::Splitter => (3, 32.0, 110.0, 150),
+            EnemyKind::SplitterChild => (1, 12.0, 190.0, 25),
+            EnemyKind::Boss => (40, 70.0, 70.0, 5000),
+            EnemyKind::Shooter => (2, 24.0, 100.0, 120),
+            EnemyKind::Circler => (2, 22.0, 170.0, 150),
+            EnemyKind::Support => (3, 24.0, 80.0, 200),
+            EnemyKind::Layer => (2, 22.0, 120.0, 130),
+            EnemyKind::Blinker => (2, 22.0, 40.0, 160),
+            EnemyKind::Scavenger => (1, 18.0, 200.0, 250),
+            EnemyKind::Carrier => (3, 22.0, 150.0, 150),
+            EnemyKind::Mimic => (3, 24.0, 80.0, 220),
+        }
+    }
+
+    /// Bosses can be practiced on their own once they've been met
+    pub fn is_boss(self) -> bool {
+        self == EnemyKind::Boss
+    }
+
+    /// Parse the lowercase name used in data tables
+    pub fn from_name(name: &str) -> Option<EnemyKind> {
+        EnemyKind::ALL.into_iter().find(|k| k.name() == name)
+    }
+}
+
+/// Speed of every enemy shot (px/s)
+pub const ENEMY_BULLET_SPEED: f32 = 260.0;
+
+/// Longest a Shooter (or anything else with a fire timer) waits between shots
+pub const SHOOTER_FIRE_INTERVAL: f32 = 2.0;
+
+/// Most knockback that can stack up on one enemy (px/s)
+pub const KNOCKBACK_MAX_SPEED: f32 = 450.0;
+
+// Shooters hover at this range; Supports stop closing in further out to
+// stay behind the pack
+const SHOOTER_RANGE: f32 = 250.0;
+const SUPPORT_RANGE: f32 = 320.0;
+
+// Layers drop a mine this often
+const LAYER_DROP_INTERVAL: f32 = 1.5;
+
+// Circler dive: speed multiplier and how long it commits before re-approaching
+const CIRCLER_DIVE_BOOST: f32 = 1.8;
+const CIRCLER_DIVE_TIME: f32 = 1.2;
+
+// Blinker: seconds to fade out or in, and the gap it always leaves to the pilot
+const BLINK_FADE_TIME: f32 = 0.35;
+const BLINK_MIN_GAP: f32 = 90.0;
+
+// Scavenger speed-up once it makes a break for it
+const SCAVENGER_ESCAPE_BOOST: f32 = 1.5;
+
+// Enemies moving at least this fast (plus the boss) leave a short red trail
+const ENEMY_TRAIL_MIN_SPEED: f32 = 140.0;
+
+// Where a shot fired from `origin` at `speed` meets a target moving at a
+// constant `velocity`: the smallest t > 0 with |target + velocity*t - origin| = speed*t.
+// None when the target outruns the shot.
+fn intercept_point(origin: Vec2, target: Vec2, velocity: Vec2, speed: f32) -> Option<Vec2> {
+    let offset = target - origin;
+    let a = velocity.length_squared() - speed * speed;
+    let b = 2.0 * offset.dot(velocity);
+    let c = offset.length_squared();
+    let t = if a.abs() < 1e-3 {
+        // Target as fast as the shot: the quadratic degenerates to linear
+        -c / b
+    } else {
+        let discriminant = b * b - 4.0 * a * c;
+        if discriminant < 0.0 {
+            return None;
+        }
+        let root = discriminant.sqrt();
+        let (t1, t2) = ((-b - root) / (2.0 * a), (-b + root) / (2.0 * a));
+        match (t1 > 0.0, t2 > 0.0) {
+            (true, true) => t1.min(t2),
+            (true, false) => t1,
+            (false, true) => t2,
+            (false, false) => return None,
+        }
+    };
+    (t.is_finite() && t > 0.0).then(|| target + velocity * t)
+}
+
+/// 🔫 Enemy volley shapes
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BulletPattern {
+    Radial { count: u32 },                  // Evenly around, starting at the aim
+    Spiral { count: u32, turn_rate: f32 }, // Radial, base angle turning (rad/s) over time
+    Aimed { count: u32, spread: f32 },      // Fanned around the aim, `spread` rad apart
+}
+
+impl BulletPattern {
+    /// Heading of every bullet in one volley, given the aim and the time
+    pub fn angles(self, aim: f32, time: f32) -> Vec<f32> {
+        let ring = |count: u32, base: f32| {
+            let step = std::f32::consts::TAU / count.max(1) as f32;
+            (0..count).map(|i| base + step * i as f32).collect()
+        };
+        match self {
+            BulletPattern::Radial { count } => ring(count, aim),
+            BulletPattern::Spiral { count, turn_rate } => ring(count, turn_rate * time),
+            BulletPattern::Aimed { count, spread } => (0..count)
+                .map(|i| aim + (i as f32 - (count as f32 - 1.0) / 2.0) * spread)
+                .collect(),
+        }
+    }
+}
+
+// Boss attack for its remaining health: aimed fans, then radial bursts,
+// then a fast spiral at the end (pattern, seconds between volleys)
+fn boss_pattern(health_fraction: f32) -> (BulletPattern, f32) {
+    if health_fraction > 0.66 {
+        (BulletPattern::Aimed { count: 3, spread: 0.2 }, 1.6)
+    } else if health_fraction > 0.33 {
+        (BulletPattern::Radial { count: 12 }, 1.8)
+    } else {
+        (BulletPattern::Spiral { count: 5, turn_rate: 1.4 }, 0.3)
+    }
+}
+
+/// 📣 What an enemy's behaviour needs from the rest of the world this step
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EnemyAction {
+    /// Leave a mine at `pos` (Layers)
+    DropMine { pos: Vec2 },
+    /// Fire one volley of `pattern` from `muzzle` (Shooters, Bosses)
+    Fire { muzzle: Vec2, pattern: BulletPattern, aim: f32 },
+    /// Made a break for it, leaving the arena for good (Scavengers, Carriers)
+    Escape,
+}
+
+/// 🚀 Enemy rocket
+pub struct Enemy {
+    pub born: u32, // Spawn order, so blasts can spare what came after them
+    pub kind: EnemyKind,
+    pub pos: Vec2,
+    pub prev_pos: Vec2, // Position before the last update, for interpolation
+    pub velocity: Vec2,
+    pub rotation: f32,
+    pub health: i32,
+    pub max_health: i32,
+    pub size: f32,
+    pub speed: f32,
+    pub score_value: i32,
+    pub fire_timer: f32,
+    pub phase: CirclerPhase,
+    pub blink: BlinkPhase,
+    pub phase_timer: f32, // Seconds in the current Circler or Blinker phase (Scavengers, Carriers: alive)
+    pub hit_flash_timer: f32, // > 0 right after a hit; flashes the body white
+    pub knockback: Vec2,      // Shove from hits, on top of its own motion; dies away
+    pub shielded: bool,       // Inside a Support's bubble: bullets do no damage
+    pub poise: f32,           // Damage left before the armor breaks
+    pub max_poise: f32,       // 0 for kinds that never stagger
+    pub stagger_timer: f32,   // > 0 while broken: stopped and taking extra damage
+    pub offscreen_time: f32,  // Seconds spent wholly off-screen, for culling strays
+    pub cargo: Option<PowerUpKind>, // Guaranteed drop (Carriers only)
+}
+
+impl Enemy {
+    pub fn new(kind: EnemyKind, pos: Vec2) -> Self {
+        let (health, size, speed, score_value) = kind.stats();
+        Self {
+            born: 0, // Assigned by GameState::add_enemy
+            kind,
+            pos,
+            prev_pos: pos,
+            velocity: Vec2::ZERO,
+            rotation: 0.0,
+            health,
+            max_health: health,
+            size,
+            speed,
+            score_value,
+            fire_timer: SHOOTER_FIRE_INTERVAL, // Staggered by GameState::add_enemy
+            phase: CirclerPhase::Approach,
+            blink: BlinkPhase::Visible,
+            phase_timer: 0.0,
+            hit_flash_timer: 0.0,
+            knockback: Vec2::ZERO,
+            shielded: false,
+            poise: 0.0,
+            max_poise: 0.0,
+            stagger_timer: 0.0,
+            offscreen_time: 0.0,
+            cargo: None,
+        }
+    }
+
+    /// 🧭 One step of this kind's behaviour against the nearest pilot, at
+    /// `target` and moving at `target_velocity`: sets velocity and heading,
+    /// and says what else should happen. Mimics copy `mimic` (velocity,
+    /// heading) when there's a move to copy.
+    pub fn steer(&mut self, target: Vec2, target_velocity: Vec2, mimic: Option<(Vec2, f32)>, dt: f32, config: &Config) -> Option<EnemyAction> {
+        let to_target = target - self.pos;
+        let distance = to_target.length();
+        let direction = if distance > 0.0 { to_target / distance } else { Vec2::X };
+
+        self.recover_poise(dt, config);
+        if self.stagger_timer > 0.0 {
+            // Staggered: dead in the water until it recovers
+            self.velocity = Vec2::ZERO;
+            return None;
+        }
+        match self.kind {
+            EnemyKind::Circler => self.steer_circler(direction, distance, dt, config),
+            EnemyKind::Blinker => self.step_blinker(direction, distance, dt, config),
+            EnemyKind::Mimic => {
+                // Replays the pilot's motion; with nothing to replay yet
+                // (or a gap in it) it just creeps toward them
+                (self.velocity, self.rotation) = mimic.unwrap_or((direction * self.speed, direction.to_angle()));
+            }
+            EnemyKind::Scavenger | EnemyKind::Carrier => {
+                return self.flee(direction, dt, config).then_some(EnemyAction::Escape);
+            }
+            EnemyKind::Drifter => {}
+            _ if distance > 0.0 => return self.chase(target, target_velocity, dt, config),
+            _ => {}
+        }
+        None
+    }
+
+    // Head straight for the target. Supports hang back, Layers seed the
+    // path behind them with mines, Shooters hold at range and fire, and
+    // Bosses cycle attacks as they wear down.
+    fn chase(&mut self, target: Vec2, target_velocity: Vec2, dt: f32, config: &Config) -> Option<EnemyAction> {
+        let to_target = target - self.pos;
+        let distance = to_target.length();
+        let direction = to_target / distance;
+        self.velocity = direction * self.speed;
+        self.rotation = direction.y.atan2(direction.x);
+
+        let kind = self.kind;
+        match kind {
+            EnemyKind::Support if distance < SUPPORT_RANGE => self.velocity = Vec2::ZERO,
+            EnemyKind::Layer if self.reload(dt, LAYER_DROP_INTERVAL) => {
+                return Some(EnemyAction::DropMine { pos: self.pos - direction * self.size });
+            }
+            EnemyKind::Shooter => {
+                if distance < SHOOTER_RANGE {
+                    self.velocity = Vec2::ZERO;
+                }
+                if self.reload(dt, SHOOTER_FIRE_INTERVAL) {
+                    // Aim part of the way from the pilot toward where
+                    // they'll be, so strafing alone doesn't dodge
+                    let muzzle = self.pos + direction * self.size;
+                    let lead = intercept_point(muzzle, target, target_velocity, ENEMY_BULLET_SPEED)
+                        .map_or(target, |hit| target.lerp(hit, config.shooter_lead));
+                    let aim = (lead - muzzle).try_normalize().unwrap_or(direction);
+                    let pattern = BulletPattern::Aimed { count: 1, spread: 0.0 };
+                    return Some(EnemyAction::Fire { muzzle, pattern, aim: aim.to_angle() });
+                }
+            }
+            EnemyKind::Boss => {
+                let (pattern, interval) = boss_pattern(self.health as f32 / self.max_health as f32);
+                if self.reload(dt, interval) {
+                    let muzzle = self.pos + direction * self.size;
+                    return Some(EnemyAction::Fire { muzzle, pattern, aim: direction.to_angle() });
+                }
+            }
+            _ => {}
+        }
+        None
+    }
+
+    // Run the fire timer down; true (and rearmed for `interval`) when it's time
+    fn reload(&mut self, dt: f32, interval: f32) -> bool {
+        self.fire_timer -= dt;
+        if self.fire_timer > 0.0 {
+            return false;
+        }
+        self.fire_timer = interval;
+        true
+    }
+
+    /// Shove by `impulse` (px/s). It rides on top of the enemy's own motion
+    /// and dies away, so the chase (or drift) carries on underneath; stacked
+    /// shoves are capped so chained blasts can't fling it across the arena.
+    pub fn knock(&mut self, impulse: Vec2) {
+        self.knockback = (self.knockback + impulse).clamp_length_max(KNOCKBACK_MAX_SPEED);
+    }
+
+    /// Take `damage` (more while staggered) and flash. Hits that land in
+    /// quick succession wear down poise; emptying it breaks into a stagger.
+    pub fn take_hit(&mut self, damage: i32, config: &Config) {
+        let staggered = self.stagger_timer > 0.0;
+        self.health -= if staggered { damage * config.stagger_damage } else { damage };
+        self.hit_flash_timer = config.enemy_hit_flash;
+        if self.max_poise > 0.0 && !staggered {
+            self.poise -= damage as f32;
+            if self.poise <= 0.0 {
+                self.stagger_timer = config.stagger_time;
+            }
+        }
+    }
+
+    // Count down a stagger (refilling poise at its end), or regain poise
+    fn recover_poise(&mut self, dt: f32, config: &Config) {
+        if self.stagger_timer > 0.0 {
+            self.stagger_timer -= dt;
+            if self.stagger_timer <= 0.0 {
+                self.poise = self.max_poise;
+            }
+        } else {
+            self.poise = (self.poise + config.poise_regen * dt).min(self.max_poise);
+        }
+    }
+
+    /// Grow or shrink by `scale`. Health and score follow the area, speed
+    /// falls off inversely: big means slow, tanky and valuable.
+    pub fn scaled(mut self, scale: f32) -> Self {
+        let area = scale * scale;
+        self.size *= scale;
+        self.speed /= scale;
+        self.health = ((self.health as f32 * area).round() as i32).max(1);
+        self.max_health = self.health;
+        self.score_value = ((self.score_value as f32 * area).round() as i32).max(1);
+        self.max_poise *= area;
+        self.poise = self.max_poise;
+        self
+    }
+
+    // Approach to orbit radius, circle the target, then lunge straight
+    // through where it was; the dive heading is locked in at launch
+    fn steer_circler(&mut self, to_target: Vec2, distance: f32, dt: f32, config: &Config) {
+        self.phase_timer += dt;
+        match self.phase {
+            CirclerPhase::Approach => {
+                self.velocity = to_target * self.speed;
+                if distance <= config.circler_orbit_radius {
+                    self.phase = CirclerPhase::Orbit;
+                    self.phase_timer = 0.0;
+                }
+            }
+            CirclerPhase::Orbit => {
+                // Tangent plus a gentle correction back onto the ring
+                let tangent = to_target.rotate(Vec2::Y);
+                let drift = (distance - config.circler_orbit_radius) / config.circler_orbit_radius;
+                self.velocity = (tangent + to_target * drift).normalize_or_zero() * self.speed;
+                if self.phase_timer >= config.circler_orbit_time {
+                    self.phase = CirclerPhase::Dive;
+                    self.phase_timer = 0.0;
+                    self.velocity = to_target * self.speed * CIRCLER_DIVE_BOOST;
+                }
+            }
+            CirclerPhase::Dive => {
+                if self.phase_timer >= CIRCLER_DIVE_TIME {
+                    self.phase = CirclerPhase::Approach;
+                    self.phase_timer = 0.0;
+                }
+            }
+        }
+        self.rotation = self.velocity.y.atan2(self.velocity.x);
+    }
+
+    // Creep toward the target, then fade out, jump most of the way in and
+    // fade back. The jump skips interpolation so it reads as a teleport.
+    fn step_blinker(&mut self, to_target: Vec2, distance: f32, dt: f32, config: &Config) {
+        self.phase_timer += dt;
+        self.rotation = to_target.y.atan2(to_target.x);
+        self.velocity = match self.blink {
+            BlinkPhase::Visible => to_target * self.speed,
+            _ => Vec2::ZERO,
+        };
+        match self.blink {
+            BlinkPhase::Visible if self.phase_timer >= config.blink_cooldown => {
+                self.blink = BlinkPhase::Vanishing;
+                self.phase_timer = 0.0;
+            }
+            BlinkPhase::Vanishing if self.phase_timer >= BLINK_FADE_TIME => {
+                let hop = config.blink_distance.min((distance - BLINK_MIN_GAP).max(0.0));
+                self.pos += to_target * hop;
+                self.prev_pos = self.pos;
+                self.blink = BlinkPhase::Appearing;
+                self.phase_timer = 0.0;
+            }
+            BlinkPhase::Appearing if self.phase_timer >= BLINK_FADE_TIME => {
+                self.blink = BlinkPhase::Visible;
+                self.phase_timer = 0.0;
+            }
+            _ => {}
+        }
+    }
+
+    // Run straight away from the target until the escape time, then keep
+    // running without stopping at the edge. True on the step it turns to go.
+    fn flee(&mut self, to_target: Vec2, dt: f32, config: &Config) -> bool {
+        let was_escaping = self.escaping(config);
+        self.phase_timer += dt;
+        let away = -to_target;
+        let speed = if self.escaping(config) { self.speed * SCAVENGER_ESCAPE_BOOST } else { self.speed };
+        self.velocity = away * speed;
+        self.rotation = away.y.atan2(away.x);
+        !was_escaping && self.escaping(config)
+    }
+
+    /// A Scavenger or Carrier that has outlasted its escape time and is leaving
+    pub fn escaping(&self, config: &Config) -> bool {
+        let escape_time = match self.kind {
+            EnemyKind::Scavenger => config.scavenger_escape_time,
+            EnemyKind::Carrier => config.carrier_escape_time,
+            _ => return false,
+        };
+        self.phase_timer >= escape_time
+    }
+
+    /// Mid-blink enemies aren't really there: shots and ships pass through
+    pub fn blinking(&self) -> bool {
+        self.blink != BlinkPhase::Visible
+    }
+
+    /// Draw opacity, fading through a blink
+    pub fn visibility(&self) -> f32 {
+        let t = (self.phase_timer / BLINK_FADE_TIME).min(1.0);
+        match self.blink {
+            BlinkPhase::Visible => 1.0,
+            BlinkPhase::Vanishing => 1.0 - t,
+            BlinkPhase::Appearing => t,
+        }
+    }
+
+    /// Fast movers and the boss streak so their motion reads in a crowd
+    pub fn leaves_trail(&self) -> bool {
+        self.kind == EnemyKind::Boss || self.velocity.length() >= ENEMY_TRAIL_MIN_SPEED
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_leading_shot_meets_a_steady_target() {
+        let (origin, target, velocity, speed) = (Vec2::ZERO, Vec2::new(300.0, 0.0), Vec2::new(0.0, 120.0), 260.0);
+        let hit = intercept_point(origin, target, velocity, speed).unwrap();
+        // The shot and the target get there at the same moment
+        let t = (hit - target).length() / velocity.length();
+        assert!(((hit - origin).length() - speed * t).abs() < 0.01);
+        assert!(hit.y > 0.0 && hit.x == 300.0);
+    }
+
+    #[test]
+    fn a_target_faster_than_the_shot_and_fleeing_cannot_be_met() {
+        assert_eq!(intercept_point(Vec2::ZERO, Vec2::new(300.0, 0.0), Vec2::new(400.0, 0.0), 260.0), None);
+        let still = intercept_point(Vec2::ZERO, Vec2::new(300.0, 0.0), Vec2::ZERO, 260.0).unwrap();
+        assert_eq!(still, Vec2::new(300.0, 0.0));
+    }
+
+    #[test]
+    fn a_bigger_enemy_is_slower_tougher_and_worth_more() {
+        let small = Enemy::new(EnemyKind::Support, Vec2::ZERO).scaled(0.8);
+        let large = Enemy::new(EnemyKind::Support, Vec2::ZERO).scaled(1.4);
+        assert!(large.size > small.size);
+        assert!(large.speed < small.speed);
+        assert!(large.health > small.health);
+        assert_eq!(large.health, large.max_health);
+        assert!(large.score_value > small.score_value);
+    }
+
+    #[test]
+    fn rapid_hits_stagger_but_spaced_ones_do_not() {
+        let config = Config::default();
+        let armored = || Enemy { health: 100, max_poise: 4.0, poise: 4.0, ..Enemy::new(EnemyKind::Support, Vec2::ZERO) };
+        let landed = |gap: f32| {
+            let mut enemy = armored();
+            for _ in 0..5 {
+                enemy.take_hit(1, &config);
+                enemy.recover_poise(gap, &config);
+            }
+            enemy.stagger_timer > 0.0
+        };
+        assert!(landed(0.05));
+        assert!(!landed(1.0 / config.poise_regen));
+    }
+}
@@ -1,6 +1,183 @@
 use macroquad::prelude::*;
-use crate::joystick::{Joystick, Vec2 as JoyVec2};
-use crate::player::Player;
+use crate::joystick::{InputFilter, Joystick, Vec2 as JoyVec2};
+use crate::player::{Player, THRUST, VISUAL_SIZE};
+use crate::config::{AimIndicator, BulletLook, Config, ContactResponse, ControlScheme, Difficulty, EdgePolicy, ForceField, GameMode, Loadout, Quality, SafeArea, ShipColor, TrailColors};
+use crate::ghost::GhostRecorder;
+use crate::enemy::{BulletPattern, CirclerPhase, Enemy, EnemyAction, EnemyKind, ENEMY_BULLET_SPEED, SHOOTER_FIRE_INTERVAL};
+use crate::wave::{SpawnGroup, SpawnPattern, WaveManager, WavePhase};
+use crate::postfx::PostFx;
+use crate::persistence::{append_line, Store};
+use crate::save::{RunSave, SavedEnemy, SavedPilot};
+use crate::score::{wave_clear_bonus, RunStats, ScoreBreakdown};
+use crate::menu::Menu;
+use crate::rng::{daily_seed, fresh_seed, today, Rng};
+use crate::achievement::{Achievement, Achievements, TOAST_SLIDE_TIME, TOAST_TIME};
+use crate::codex::Codex;
+use crate::input::{InputSource, PilotInput, StepInput};
+use crate::script::ScriptedInput;
+use crate::arena::{Arena, Id};
+use crate::lifetime::{reap, reap_arena, Lifetime, Transient};
+
+// Mirror a velocity off a surface with the given unit normal
+fn reflect(v: Vec2, normal: Vec2) -> Vec2 {
+    v - normal * 2.0 * v.dot(normal)
+}
+
+// Fake glow: `layers` concentric circles growing by `spread`, each outer
+// one fainter (alpha divided by its layer number). Drawn outside-in.
+fn draw_glow_circle(center: Vec2, radius: f32, spread: f32, color: Color, layers: u32) {
+    for k in (0..layers).rev() {
+        let alpha = color.a / (k + 1) as f32;
+        draw_circle(center.x, center.y, radius + spread * k as f32, Color { a: alpha, ..color });
+    }
+}
+
+// Distance from `point` to the segment `a`-`b`
+fn segment_distance(point: Vec2, a: Vec2, b: Vec2) -> f32 {
+    let ab = b - a;
+    let t = if ab.length_squared() > 0.0 {
+        ((point - a).dot(ab) / ab.length_squared()).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+    (point - (a + ab * t)).length()
+}
+
+// Border `depth` pixels deep around the screen, `color` at the very edge
+// fading out toward the middle
+fn draw_edge_glow(color: Color, depth: f32) {
+    let (w, h) = (screen_width(), screen_height());
+    let layers = 5;
+    for i in 0..layers {
+        let inset = i as f32 * depth / layers as f32;
+        let thickness = depth / layers as f32;
+        let color = Color { a: color.a * (1.0 - i as f32 / layers as f32), ..color };
+        draw_rectangle(inset, inset, w - inset * 2.0, thickness, color);
+        draw_rectangle(inset, h - inset - thickness, w - inset * 2.0, thickness, color);
+        draw_rectangle(inset, inset + thickness, thickness, h - (inset + thickness) * 2.0, color);
+        draw_rectangle(w - inset - thickness, inset + thickness, thickness, h - (inset + thickness) * 2.0, color);
+    }
+}
+
+// `count` points evenly spaced around a circle, the first at `start` radians
+fn ring_points(center: Vec2, radius: f32, count: u32, start: f32) -> Vec<Vec2> {
+    let step = std::f32::consts::TAU / count.max(1) as f32;
+    (0..count)
+        .map(|i| center + Vec2::from_angle(start + step * i as f32) * radius)
+        .collect()
+}
+
+// Acceleration the world force field applies at `pos` in a view of `view_size`
+fn field_force(config: &Config, view_size: Vec2, pos: Vec2) -> Vec2 {
+    let strength = config.force_strength.clamp(0.0, THRUST * FORCE_FIELD_MAX);
+    match config.force_field {
+        ForceField::Off => Vec2::ZERO,
+        ForceField::Gravity => Vec2::from_angle(config.force_angle.to_radians()) * strength,
+        ForceField::Vortex => {
+            let inward = (view_size / 2.0 - pos).normalize_or_zero();
+            (inward.perp() + inward * 0.3).normalize_or_zero() * strength
+        }
+    }
+}
+
+// The entry after (or before) `current` in `all`, wrapping around
+fn cycle<T: Copy + PartialEq>(all: &[T], current: T, forward: bool) -> T {
+    let index = all.iter().position(|&item| item == current).unwrap_or(0);
+    let step = if forward { 1 } else { all.len() - 1 };
+    all[(index + step) % all.len()]
+}
+
+// A key, click or new touch this frame
+fn any_press() -> bool {
+    get_last_key_pressed().is_some()
+        || is_mouse_button_pressed(MouseButton::Left)
+        || touches().iter().any(|touch| touch.phase == TouchPhase::Started)
+}
+
+// Hearts each pilot starts (and respawns) with
+fn max_health(mode: GameMode) -> i32 {
+    if mode == GameMode::Hardcore { HARDCORE_HEALTH } else { PLAYER_HEALTH }
+}
+
+// A `screen`-sized view less the safe-area insets
+fn safe_rect(inset: SafeArea, screen: Vec2) -> Rect {
+    Rect::new(
+        inset.left,
+        inset.top,
+        (screen.x - inset.left - inset.right).max(0.0),
+        (screen.y - inset.top - inset.bottom).max(0.0),
+    )
+}
+
+// Where heart `slot` of pilot `seat` sits in the HUD `area`: even seats
+// fill in from the top left, odd seats from the top right, a row lower
+// for each pair of seats
+fn heart_center(area: Rect, seat: usize, slot: i32) -> Vec2 {
+    let y = area.y + 30.0 + 30.0 * (seat / 2) as f32;
+    let step = 30.0 * slot as f32;
+    let x = if seat.is_multiple_of(2) { area.x + 30.0 + step } else { area.right() - 30.0 - step };
+    Vec2::new(x, y)
+}
+
+// Score-store key for a per-mode win record, e.g. `boss_rush.best_time`
+fn clear_key(mode: GameMode, field: &str) -> String {
+    format!("{}.{}", mode.name(), field)
+}
+
+// `m:ss.s` for clear and survival times
+fn clock_text(seconds: f32) -> String {
+    format!("{}:{:04.1}", (seconds / 60.0) as i32, seconds % 60.0)
+}
+
+// `mm:ss.mmm` for speed records, where tenths can't separate two clears
+fn split_text(seconds: f64) -> String {
+    let millis = (seconds * 1000.0).round() as u64;
+    format!("{:02}:{:02}.{:03}", millis / 60_000, millis / 1000 % 60, millis % 1000)
+}
+
+// What a clamping edge does to an entity's outward velocity
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum EdgeResponse {
+    Stop,   // Kill it (ships)
+    Bounce, // Reflect it (enemies, bullets)
+}
+
+// Apply an entity's edge policy within a `bounds`-sized arena. `margin` is
+// how far past the edge the entity may travel. Returns false if it should be removed.
+fn apply_boundary(policy: EdgePolicy, response: EdgeResponse, bounds: Vec2, pos: &mut Vec2, velocity: &mut Vec2, margin: f32) -> bool {
+    let min = Vec2::splat(-margin);
+    let max = bounds + Vec2::splat(margin);
+    if pos.cmpge(min).all() && pos.cmple(max).all() {
+        return true;
+    }
+    
+    match policy {
+        EdgePolicy::Destroy => return false,
+        EdgePolicy::Wrap => {
+            if pos.x < min.x { pos.x = max.x; } else if pos.x > max.x { pos.x = min.x; }
+            if pos.y < min.y { pos.y = max.y; } else if pos.y > max.y { pos.y = min.y; }
+        }
+        EdgePolicy::Clamp => {
+            let outward = Vec2::new(
+                if pos.x < min.x { -1.0 } else if pos.x > max.x { 1.0 } else { 0.0 },
+                if pos.y < min.y { -1.0 } else if pos.y > max.y { 1.0 } else { 0.0 },
+            );
+            *pos = pos.clamp(min, max);
+            for axis in 0..2 {
+                if outward[axis] * velocity[axis] > 0.0 {
+                    velocity[axis] = if response == EdgeResponse::Bounce { -velocity[axis] } else { 0.0 };
+                }
+            }
+        }
+    }
+    true
+}
+
+// How far past the screen edge an enemy of `size` roams before the edge
+// policy catches it, and how far out it spawns
+fn edge_buffer(config: &Config, size: f32) -> f32 {
+    config.enemy_edge_margin + size
+}
 
 // Helper to convert between our Vec2 and Macroquad's Vec2
 fn to_mac_vec2(v: JoyVec2) -> Vec2 {
@@ -11,22 +188,60 @@ fn from_mac_vec2(v: Vec2) -> JoyVec2 {
     JoyVec2::new(v.x, v.y)
 }
 
+// Starfield depth layers: (parallax depth, size range). Nearer layers are
+// bigger and brighter, and drift and scroll faster.
+const STAR_LAYERS: [(f32, (f32, f32)); 3] = [
+    (0.3, (0.6, 1.4)),
+    (0.6, (1.0, 2.2)),
+    (1.0, (1.8, 3.0)),
+];
+
+// Fraction of the lead pilot's velocity the nearest star layer scrolls against
+const STAR_PARALLAX: f32 = 0.1;
+
 // Particle for background atmosphere
 struct Particle {
     pos: Vec2,
     velocity: Vec2,
     size: f32,
     alpha: f32,
+    depth: f32, // Parallax factor from its layer, 1.0 = nearest
 }
 
-// Trail segment for player movement
+impl Particle {
+    fn random(rng: &mut Rng, screen_width: f32, screen_height: f32) -> Self {
+        let (depth, (min_size, max_size)) = STAR_LAYERS[rng.below(STAR_LAYERS.len())];
+        Self {
+            pos: Vec2::new(
+                rng.range(0.0, screen_width),
+                rng.range(0.0, screen_height),
+            ),
+            velocity: Vec2::new(
+                rng.range(-15.0, 15.0),
+                rng.range(-15.0, 15.0),
+            ),
+            size: rng.range(min_size, max_size),
+            alpha: rng.range(0.1, 0.4) * Self::brightness(depth),
+            depth,
+        }
+    }
+    
+    // Distant layers are dimmer
+    fn brightness(depth: f32) -> f32 {
+        0.4 + 0.6 * depth
+    }
+}
+
+// Trail segment for player (and fast enemy) movement
 struct TrailSegment {
     pos: Vec2,
     life: f32,
     size: f32,
+    color: Color, // Alpha is set by `life` when drawn
 }
 
 // Obstacle in the world
+#[allow(dead_code)]
 struct Obstacle {
     pos: Vec2,
     size: f32,
@@ -36,121 +251,1580 @@ struct Obstacle {
 // Bullet projectile
 struct Bullet {
     pos: Vec2,
+    prev_pos: Vec2, // Position last frame, for swept hits and the tracer
     velocity: Vec2,
-    life: f32,
+    life: Lifetime,  // Range left; the shot fades over the end of it
+    reflected: bool, // Enemy shot parried back by the player
+    pierce: u32,     // Extra enemies this bullet can pass through
+    hit_ids: Vec<Id>,  // Enemies already pierced, so none is hit twice
+    grazed: bool,      // Enemy shot already paid out a graze bonus
+    accel: Vec2,       // Constant pull (gravity on mortar shells)
+    shell: bool,       // Bursts into area damage wherever it ends
+    look: BulletLook,  // Set from the weapon that fired it
+}
+
+impl Transient for Bullet {
+    fn lifetime(&mut self) -> &mut Lifetime {
+        &mut self.life
+    }
+}
+
+// Push from a blast of `size` felt `offset` away from its center: along
+// the offset, inverse to the distance, nothing past the blast's reach
+fn shockwave_impulse(offset: Vec2, size: f32, strength: f32) -> Vec2 {
+    let distance = offset.length();
+    if distance >= size * SHOCKWAVE_REACH {
+        return Vec2::ZERO;
+    }
+    let speed = strength * size / distance.max(size * SHOCKWAVE_INNER);
+    offset.normalize_or(Vec2::X) * speed.min(SHOCKWAVE_MAX_IMPULSE)
+}
+
+// Push one volley of `pattern` from `origin` into `bullets`
+fn fire_pattern(bullets: &mut Vec<Bullet>, origin: Vec2, pattern: BulletPattern, aim: f32, time: f32, look: BulletLook) {
+    for angle in pattern.angles(aim, time) {
+        bullets.push(Bullet {
+            pos: origin,
+            prev_pos: origin,
+            velocity: Vec2::from_angle(angle) * ENEMY_BULLET_SPEED,
+            life: Lifetime::new(4.0),
+            reflected: false,
+            pierce: 0,
+            hit_ids: Vec::new(),
+            grazed: false,
+            accel: Vec2::ZERO,
+            shell: false,
+            look,
+        });
+    }
+}
+
+/// 🎁 Collectible dropped by destroyed enemies
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PowerUpKind {
+    Pierce, // Shots pass through extra enemies for a while
+    Freeze, // Stops every enemy and enemy bullet for a few seconds
+    Weapon, // Upgrades the team's weapon one level
+    Mortar, // Shots become lobbed shells that burst where they land
+}
+
+impl PowerUpKind {
+    const ALL: [PowerUpKind; 4] = [PowerUpKind::Pierce, PowerUpKind::Freeze, PowerUpKind::Weapon, PowerUpKind::Mortar];
+    
+    pub fn name(self) -> &'static str {
+        match self {
+            PowerUpKind::Pierce => "pierce",
+            PowerUpKind::Freeze => "freeze",
+            PowerUpKind::Weapon => "weapon",
+            PowerUpKind::Mortar => "mortar",
+        }
+    }
+    
+    pub fn from_name(name: &str) -> Option<PowerUpKind> {
+        PowerUpKind::ALL.into_iter().find(|k| k.name() == name)
+    }
+    
+    // Tint and letter it's drawn with
+    fn look(self) -> (Color, &'static str) {
+        match self {
+            PowerUpKind::Pierce => (Color::from_rgba(255, 220, 80, 255), "P"),
+            PowerUpKind::Freeze => (Color::from_rgba(140, 220, 255, 255), "F"),
+            PowerUpKind::Weapon => (Color::from_rgba(120, 255, 140, 255), "W"),
+            PowerUpKind::Mortar => (Color::from_rgba(255, 150, 90, 255), "M"),
+        }
+    }
+    
+    // Popup shown on pickup
+    fn label(self) -> &'static str {
+        match self {
+            PowerUpKind::Pierce => "PIERCE",
+            PowerUpKind::Freeze => "FREEZE",
+            PowerUpKind::Weapon => "WEAPON UP",
+            PowerUpKind::Mortar => "MORTAR",
+        }
+    }
 }
 
-// Enemy rocket
-struct Enemy {
+struct PowerUp {
     pos: Vec2,
-    velocity: Vec2,
-    rotation: f32,
+    kind: PowerUpKind,
+    life: Lifetime,
+}
+
+impl Transient for PowerUp {
+    fn lifetime(&mut self) -> &mut Lifetime {
+        &mut self.life
+    }
+}
+
+// Power-up drop odds per kill (bosses always drop) and how long effects last
+const POWER_UP_DROP_CHANCE: f32 = 0.08;
+const POWER_UP_LIFETIME: f32 = 10.0;
+const POWER_UP_RADIUS: f32 = 35.0;
+const PIERCE_DURATION: f32 = 8.0;
+const PIERCE_COUNT: u32 = 2;
+const MORTAR_DURATION: f32 = 8.0;
+
+// Mortar shell burst
+const MORTAR_BLAST_RADIUS: f32 = 80.0;
+const MORTAR_BLAST_DAMAGE: i32 = 2;
+
+// Collision radius of a player shot, and how close it must pass an enemy
+// shot to cancel it
+const BULLET_HIT_RADIUS: f32 = 10.0;
+const BULLET_CANCEL_RADIUS: f32 = 8.0;
+
+// Slowest analog fire rate allowed whatever the config says, so a
+// barely-touched aim stick still fires now and then
+const MIN_FIRE_RATE_FLOOR: f32 = 0.1;
+
+// Angle between neighbouring pellets of a multi-shot (radians)
+const PELLET_SPREAD: f32 = 0.1;
+
+// Player shots start fading over this last fraction of their life
+const BULLET_FADE_FRACTION: f32 = 0.3;
+
+// Floating score/label popup
+struct FloatingText {
+    pos: Vec2,
+    text: String,
+    life: Lifetime,
+    color: Color,
+}
+
+impl Transient for FloatingText {
+    fn lifetime(&mut self) -> &mut Lifetime {
+        &mut self.life
+    }
+}
+
+// A blown mine hits enemies in its blast too
+const MINE_RADIUS: f32 = 10.0;
+const MINE_BLAST_RADIUS: f32 = 70.0;
+const MINE_ENEMY_DAMAGE: i32 = 2;
+
+// A dying Splitter bursts, hurting whatever is packed in around it
+const SPLITTER_BURST_RADIUS: f32 = 60.0;
+const SPLITTER_BURST_DAMAGE: i32 = 1;
+
+// Zone that slows pilots and enemies alike; lasts for the wave that laid it
+struct SlowField {
+    pos: Vec2,
+    radius: f32,
+}
+
+// Slow fields keep at least this far from every pilot when laid, and give
+// up looking for such a spot after this many tries
+const SLOW_FIELD_CLEARANCE: f32 = 150.0;
+const SLOW_FIELD_PLACE_TRIES: usize = 10;
+
+// Warning marker where a wave's opening burst is about to land
+struct SpawnTelegraph {
+    kind: EnemyKind,
+    pos: Vec2,
+    life: Lifetime, // The enemy appears once this runs out
+}
+
+impl Transient for SpawnTelegraph {
+    fn lifetime(&mut self) -> &mut Lifetime {
+        &mut self.life
+    }
+}
+
+// How long a burst is telegraphed, how far in from the edges it lands, and
+// the size the warning ring starts at before closing in
+const BURST_TELEGRAPH_TIME: f32 = 1.0;
+const BURST_EDGE_INSET: f32 = 60.0;
+const TELEGRAPH_RADIUS: f32 = 40.0;
+
+// Stationary hazard left behind by a Layer
+struct Mine {
+    pos: Vec2,
+    arm_timer: f32, // Harmless until this runs out
+    life: Lifetime,
+}
+
+impl Transient for Mine {
+    fn lifetime(&mut self) -> &mut Lifetime {
+        &mut self.life
+    }
+}
+
+const ENEMY_BULLET_RADIUS: f32 = 5.0;
+
+// Extra boss health per boss rush stage after the first (fraction of base)
+const BOSS_RUSH_HEALTH_STEP: f32 = 0.25;
+
+// Mimic: how often the lead pilot's path is sampled for it to copy, and
+// the speed past which a copied step must be a wrap or teleport (not copied)
+const MIMIC_SAMPLE_INTERVAL: f32 = 1.0 / 30.0;
+const MIMIC_MAX_SPEED: f32 = 1500.0;
+
+// How far off-screen an escaping Scavenger gets before it's gone
+const SCAVENGER_GONE_MARGIN: f32 = 80.0;
+
+// Enrage at full strength: extra spawn rate and enemy speed
+const ENRAGE_SPAWN_BOOST: f32 = 2.0;
+const ENRAGE_SPEED_BOOST: f32 = 0.5;
+
+// Adaptive difficulty: the wave-end performance (0.0 - 1.0) that leaves
+// the scale where it is
+const ADAPTIVE_PAR: f32 = 0.5;
+
+// Force field: strongest pull as a fraction of ship thrust (so a pilot can
+// always fight it), and how much of the field steering enemies give in to
+// (seconds of acceleration they carry as drift)
+const FORCE_FIELD_MAX: f32 = 0.6;
+const ENEMY_FIELD_DRIFT: f32 = 0.5;
+
+// Fixed timestep: longest frame simulated in one go, and the biggest
+// per-step move still drawn interpolated
+const MAX_FRAME_CATCHUP: f32 = 0.25;
+const INTERPOLATION_MAX_JUMP: f32 = 200.0;
+
+// Aim guide: line from the nose out to this distance, or reticle this far ahead
+const AIM_LINE_LENGTH: f32 = 220.0;
+const AIM_RETICLE_DISTANCE: f32 = 180.0;
+
+// Achievement thresholds
+const ACHIEVEMENT_KILLS: i32 = 100;
+const ACHIEVEMENT_SURVIVAL: f32 = 300.0;
+
+// Attempts at an edge spawn clear of the pilots before settling
+const SPAWN_REROLLS: usize = 8;
+
+// Formations: gap between neighbours in a line or V, and how far a ring
+// sits beyond the spawn-exclusion radius
+const FORMATION_SPACING: f32 = 60.0;
+const FORMATION_RING_MARGIN: f32 = 60.0;
+
+// Knockback: full impulse at this enemy size (scaled inversely), and how
+// fast a shove dies away (per second, exponential)
+const KNOCKBACK_REFERENCE_SIZE: f32 = 25.0;
+const KNOCKBACK_DECAY: f32 = 8.0;
+
+// Shockwaves reach this many blast-sizes out, treat anything closer than
+// the inner fraction as that close, and never shove harder than the cap
+const SHOCKWAVE_REACH: f32 = 2.5;
+const SHOCKWAVE_INNER: f32 = 0.25;
+const SHOCKWAVE_MAX_IMPULSE: f32 = 450.0;
+
+// Special ring expansion speed (px/s)
+const SPECIAL_RING_SPEED: f32 = 900.0;
+
+// Timed parry: reflects enemy bullets entering the shield radius
+const PARRY_WINDOW: f32 = 0.25;
+const PARRY_COOLDOWN: f32 = 1.0;
+const PARRY_RADIUS: f32 = 60.0;
+
+// Single-stick mode auto-targets enemies within this distance
+const AUTO_AIM_RANGE: f32 = 450.0;
+
+// Attract mode: idle seconds on the title before the demo starts and the
+// longest a demo round runs
+const ATTRACT_DELAY: f32 = 20.0;
+const ATTRACT_LENGTH: f32 = 60.0;
+
+// Autopilot: distance it likes to keep from its target, and how near an
+// enemy shot gets before it sidesteps
+const AUTOPILOT_STANDOFF: f32 = 250.0;
+const AUTOPILOT_DODGE_RANGE: f32 = 120.0;
+
+// Dash: a burst of speed with i-frames that rams anything in the way
+const DASH_SPEED: f32 = 900.0;
+const DASH_TIME: f32 = 0.18;
+const DASH_COOLDOWN: f32 = 0.9;
+
+// Motion blur: speed where the smear starts (a little under full-thrust
+// cruising) and where it's at full strength, plus how many seconds of
+// travel the smear covers
+const MOTION_BLUR_MIN_SPEED: f32 = 300.0;
+const MOTION_BLUR_FULL_SPEED: f32 = 900.0;
+const MOTION_BLUR_SPAN: f32 = 0.04;
+
+// Keys for a keyboard-driven pilot
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct KeyBindings {
+    up: KeyCode,
+    down: KeyCode,
+    left: KeyCode,
+    right: KeyCode,
+    fire: KeyCode,
+    parry: KeyCode,
+    focus: KeyCode,
+    special: KeyCode,
+    dash: KeyCode,
+}
+
+const WASD_KEYS: KeyBindings = KeyBindings {
+    up: KeyCode::W,
+    down: KeyCode::S,
+    left: KeyCode::A,
+    right: KeyCode::D,
+    fire: KeyCode::Space,
+    parry: KeyCode::LeftShift,
+    focus: KeyCode::LeftControl,
+    special: KeyCode::E,
+    dash: KeyCode::LeftAlt,
+};
+
+const ARROW_KEYS: KeyBindings = KeyBindings {
+    up: KeyCode::Up,
+    down: KeyCode::Down,
+    left: KeyCode::Left,
+    right: KeyCode::Right,
+    fire: KeyCode::Enter,
+    parry: KeyCode::RightShift,
+    focus: KeyCode::RightControl,
+    special: KeyCode::Slash,
+    dash: KeyCode::RightAlt,
+};
+
+// Who flies a controller: `tick` reads the window for live pilots, the
+// autopilot decides for itself each step, and scripted pilots take
+// whatever input `update` is handed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Control {
+    TwinStick,            // Touch/mouse dual joysticks
+    Keyboard(KeyBindings), // Move with keys, aim where heading
+    Autopilot,            // Simple heuristic for the title-screen demo
+    Scripted,             // Played back from a script or a test
+}
+
+// Seat layout: spawn x (fraction of screen width) and input per pilot.
+// Adding another local pilot means adding a row here.
+const SOLO_SEATS: [(f32, Control); 1] = [(0.5, Control::TwinStick)];
+const COOP_SEATS: [(f32, Control); 2] = [
+    (0.4, Control::Keyboard(WASD_KEYS)),
+    (0.6, Control::Keyboard(ARROW_KEYS)),
+];
+
+// This frame's keys, sticks and buttons, handed to each of the steps the
+// frame runs (one on the variable-dt path). Presses go to the first step
+// only, so a fixed timestep can't see one twice.
+struct LiveInput {
+    pilots: Vec<PilotInput>,
+    steps: std::vec::IntoIter<f32>,
+}
+
+impl InputSource for LiveInput {
+    fn next_step(&mut self) -> Option<StepInput> {
+        let dt = self.steps.next()?;
+        let input = StepInput { dt, pilots: self.pilots.clone() };
+        self.pilots.iter_mut().for_each(PilotInput::spend_presses);
+        Some(input)
+    }
+}
+
+// One ship plus everything specific to the person flying it
+struct PlayerController {
+    player: Player,
+    prev_position: JoyVec2, // Before the last update, for interpolation
+    control: Control,
+    seat: usize, // Index into the seat table - picks palette and HUD corner
     health: i32,
-    size: f32,
+    invuln_timer: f32,
+    shoot_cooldown: f32,
+    burst_fired: u32,    // Rounds of the current burst already out
+    parry_timer: f32,    // > 0 while the parry shield is up
+    parry_cooldown: f32,
+    parry_flash: f32,    // Flash after a successful reflect
+    pierce_timer: f32,   // > 0 while the Pierce power-up is active
+    mortar_timer: f32,   // > 0 while the Mortar power-up is active
+    dash_timer: f32,     // > 0 mid-dash: can't be hit, rams enemies
+    dash_cooldown: f32,
+    dash_hit_ids: Vec<Id>, // Enemies this dash has already rammed
+    focused: bool,
+    // Movement resolved for the current frame (drives the engine flames)
+    movement: JoyVec2,
+    hearts_shown: i32, // Health the HUD last animated to
+    heart_fx: Vec<HeartFx>,
+}
+
+// A HUD heart being lost (grows, then shatters) or gained (pops in)
+struct HeartFx {
+    slot: i32,
+    gained: bool,
+    age: f32,
+}
+
+// HUD heart fill, and the faint outline left where one is missing
+const HEART_COLOR: Color = Color::new(1.0, 0.31, 0.43, 1.0);
+const HEART_EMPTY_COLOR: Color = Color::new(1.0, 0.31, 0.43, 0.24);
+
+// Heart animation lengths; the lost heart swells for the first part
+// before breaking into debris
+const HEART_LOSS_TIME: f32 = 0.6;
+const HEART_GAIN_TIME: f32 = 0.35;
+const HEART_SWELL_FRACTION: f32 = 0.35;
+const HEART_DEBRIS: usize = 5;
+
+impl PlayerController {
+    // Burst fire state machine: count off a round and return the wait
+    // before the next one, the short `delay` mid-burst or the full
+    // `interval` once all `size` rounds are out (starting the next burst)
+    fn next_shot_delay(&mut self, size: u32, delay: f32, interval: f32) -> f32 {
+        self.burst_fired += 1;
+        if self.burst_fired >= size.max(1) {
+            self.burst_fired = 0;
+            interval
+        } else {
+            delay
+        }
+    }
+    
+    fn new(player: Player, control: Control, seat: usize, health: i32) -> Self {
+        Self {
+            prev_position: player.position,
+            player,
+            control,
+            seat,
+            health,
+            invuln_timer: 0.0,
+            shoot_cooldown: 0.0,
+            burst_fired: 0,
+            parry_timer: 0.0,
+            parry_cooldown: 0.0,
+            parry_flash: 0.0,
+            pierce_timer: 0.0,
+            mortar_timer: 0.0,
+            dash_timer: 0.0,
+            dash_cooldown: 0.0,
+            dash_hit_ids: Vec::new(),
+            focused: false,
+            movement: JoyVec2::ZERO,
+            hearts_shown: health,
+            heart_fx: Vec::new(),
+        }
+    }
+    
+    // Start a HUD animation for every heart lost or gained since last
+    // step, and age the running ones
+    fn animate_hearts(&mut self, dt: f32) {
+        for fx in &mut self.heart_fx {
+            fx.age += dt;
+        }
+        self.heart_fx.retain(|fx| fx.age < if fx.gained { HEART_GAIN_TIME } else { HEART_LOSS_TIME });
+        let health = self.health.max(0);
+        while self.hearts_shown > health {
+            self.hearts_shown -= 1;
+            self.heart_fx.push(HeartFx { slot: self.hearts_shown, gained: false, age: 0.0 });
+        }
+        while self.hearts_shown < health {
+            self.heart_fx.push(HeartFx { slot: self.hearts_shown, gained: true, age: 0.0 });
+            self.hearts_shown += 1;
+        }
+    }
+    
+    fn alive(&self) -> bool {
+        self.health > 0
+    }
+    
+    // Alive and outside any grace period or dash i-frames
+    fn vulnerable(&self) -> bool {
+        self.alive() && self.invuln_timer <= 0.0 && self.dash_timer <= 0.0
+    }
+    
+    // Trail colour for this instant: flashing a warning while invulnerable
+    // after a hit, otherwise heating up with speed (dashes run hottest)
+    fn trail_color(&self, colors: &TrailColors, time: f32) -> Color {
+        if self.invuln_timer > 0.0 && (time * 15.0).sin() > 0.0 {
+            let (r, g, b) = colors.warning;
+            return Color::from_rgba(r, g, b, 255);
+        }
+        let span = (colors.top_speed - colors.cruise_speed).max(1.0);
+        let heat = ((self.player.velocity.length() - colors.cruise_speed) / span).clamp(0.0, 1.0);
+        let mix = |cruise: u8, top: u8| (cruise as f32 + (top as f32 - cruise as f32) * heat) / 255.0;
+        Color::new(
+            mix(colors.cruise.0, colors.top.0),
+            mix(colors.cruise.1, colors.top.1),
+            mix(colors.cruise.2, colors.top.2),
+            1.0,
+        )
+    }
+}
+
+// Hearts per player (hardcore gets one) and invulnerability after taking
+// a hit (or a respawn)
+const PLAYER_HEALTH: i32 = 3;
+const HARDCORE_HEALTH: i32 = 1;
+const HIT_INVULN_TIME: f32 = 1.5;
+const RESPAWN_INVULN_TIME: f32 = 3.0;
+
+// Last-heart warning pulse, in beats per second
+const HEARTBEAT_RATE: f32 = 1.2;
+
+// Chromatic-aberration flash on a hit: duration and peak channel offset
+const HIT_FLASH_TIME: f32 = 0.3;
+const HIT_ABERRATION_PIXELS: f32 = 6.0;
+
+// Last stand: the world slows down around the final explosion
+struct DeathSequence {
+    elapsed: f32,
+    pos: Vec2,
+}
+
+const DEATH_SEQUENCE_TIME: f32 = 1.5;
+const DEATH_TIME_SCALE: f32 = 0.2;
+
+// Camera zoom: the most the config may ask for, how quickly the dynamic
+// zoom eases (per second), and the pilot speed that counts as flat out
+const MAX_ZOOM: f32 = 3.0;
+const ZOOM_EASE_RATE: f32 = 2.0;
+const ZOOM_FAST_SPEED: f32 = 330.0;
+
+// "WAVE CLEAR" flourish with the bonus it paid
+struct WaveClear {
+    bonus: i32,
+    life: f32,
+}
+
+// Seconds the wave-clear flourish stays up
+const WAVE_CLEAR_TIME: f32 = 1.8;
+
+// First-launch walkthrough: each step waits for the player to hold its
+// action for `TUTORIAL_HOLD_TIME`, and no enemies spawn until it's done
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TutorialStep {
+    Move,
+    Aim,
+}
+
+struct Tutorial {
+    step: TutorialStep,
+    progress: f32,
+}
+
+const TUTORIAL_HOLD_TIME: f32 = 1.0;
+
+/// 📣 Something that happened during a step. Game logic raises these and
+/// `dispatch_events` turns them into popups, flashes and achievements, so
+/// effects don't have to be poked inline from the collision code.
+#[derive(Debug, Clone, PartialEq)]
+pub enum GameEvent {
+    EnemyKilled { kind: EnemyKind, pos: Vec2, size: f32, points: i32 },
+    PlayerHit { pos: Vec2, fatal: bool },
+    PowerUpCollected { kind: PowerUpKind, pos: Vec2 },
+    WaveCleared { bonus: i32, no_hit: bool },
+}
+
+// Center-screen streak callout
+struct Announcement {
+    text: String,
+    life: f32,
+    tier: usize,
+}
+
+// Game-over tally: seconds per line count-up and the stagger between lines
+const TALLY_LINE_TIME: f32 = 0.6;
+const TALLY_STAGGER: f32 = 0.25;
+
+// Seconds a finished practice fight's result stays up before the codex returns
+const PRACTICE_RESULT_TIME: f32 = 4.0;
+const SCORES_FILE: &str = "scores.txt";
+const RUN_SAVE_FILE: &str = "run_save.txt";
+const RUN_LOG_FILE: &str = "runs.jsonl";
+
+// Seconds a combo survives without another kill
+const COMBO_WINDOW: f32 = 2.0;
+
+// Seconds at the end of overdrive spent easing back to normal
+const OVERDRIVE_WIND_DOWN: f32 = 1.0;
+
+// Enemy separation steering: push strength and cap as a fraction of speed
+const SEPARATION_STRENGTH: f32 = 160.0;
+const SEPARATION_MAX_FRACTION: f32 = 0.6;
+
+// Enemies closer than this contribute to the danger vignette
+const THREAT_RADIUS: f32 = 300.0;
+
+// World draw layers, bottom to top. Each kind of visual sits on one layer
+// and `draw_world` paints them in this order, so a new effect only needs a
+// slot here (and an arm in `draw_layer`) to land above or below the rest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DrawLayer {
+    Background, // Clear color, grid and starfield
+    Ghost,
+    PlayerTrail,
+    Bullets,
+    EnemyBullets,
+    PowerUps,
+    SlowFields,
+    Telegraphs,
+    Mines,
+    EnemyTrails,
+    Enemies,
+    SpecialRings,
+    Explosions,
+    FloatingTexts,
+    Players,
+    Hitboxes, // Dev tools only
+}
+
+impl DrawLayer {
+    const ORDER: [DrawLayer; 16] = [
+        DrawLayer::Background,
+        DrawLayer::Ghost,
+        DrawLayer::PlayerTrail,
+        DrawLayer::Bullets,
+        DrawLayer::EnemyBullets,
+        DrawLayer::PowerUps,
+        DrawLayer::SlowFields,
+        DrawLayer::Telegraphs,
+        DrawLayer::Mines,
+        DrawLayer::EnemyTrails,
+        DrawLayer::Enemies,
+        DrawLayer::SpecialRings,
+        DrawLayer::Explosions,
+        DrawLayer::FloatingTexts,
+        DrawLayer::Players,
+        DrawLayer::Hitboxes,
+    ];
+}
+
+// Explosion flavours: a quick spark for grazes and glancing hits, the
+// classic fireball for kills, and a big debris blast for bosses and bombs
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ExplosionKind {
+    Spark,
+    Standard,
+    Large,
+}
+
+impl ExplosionKind {
+    // Starting life (decays at 2/s) and also the peak alpha
+    fn life(self) -> f32 {
+        match self {
+            ExplosionKind::Spark => 0.25,
+            ExplosionKind::Standard => 0.5,
+            ExplosionKind::Large => 1.0,
+        }
+    }
+    
+    // Expansion speed (px/s)
+    fn growth(self) -> f32 {
+        match self {
+            ExplosionKind::Spark => 60.0,
+            ExplosionKind::Standard => 100.0,
+            ExplosionKind::Large => 160.0,
+        }
+    }
+    
+    // Outer glow and hot core colors
+    fn colors(self) -> ((u8, u8, u8), (u8, u8, u8)) {
+        match self {
+            ExplosionKind::Spark => ((255, 230, 150), (255, 255, 235)),
+            ExplosionKind::Standard => ((255, 180, 80), (255, 255, 200)),
+            ExplosionKind::Large => ((255, 120, 50), (255, 240, 180)),
+        }
+    }
+    
+    // Debris flecks thrown outward
+    fn debris(self) -> usize {
+        match self {
+            ExplosionKind::Spark => 4,
+            ExplosionKind::Standard => 0,
+            ExplosionKind::Large => 12,
+        }
+    }
 }
 
 // Explosion effect
 struct Explosion {
+    kind: ExplosionKind,
     pos: Vec2,
-    life: f32,
+    life: Lifetime, // Doubles as the peak alpha
     size: f32,
+    spin: f32, // Debris angle offset so repeated blasts don't look stamped
+    shoved: bool, // Shockwave already sent out
+}
+
+impl Transient for Explosion {
+    const DECAY: f32 = 2.0;
+
+    fn lifetime(&mut self) -> &mut Lifetime {
+        &mut self.life
+    }
+}
+
+impl Explosion {
+    fn new(kind: ExplosionKind, pos: Vec2, size: f32, rng: &mut Rng) -> Self {
+        Self {
+            kind,
+            pos,
+            life: Lifetime::new(kind.life()),
+            size,
+            spin: rng.range(0.0, std::f32::consts::TAU),
+            shoved: kind == ExplosionKind::Spark, // Too small to push anything
+        }
+    }
+}
+
+// Special attack: a damage ring expanding from where it was fired
+struct SpecialRing {
+    pos: Vec2,
+    radius: f32,
+    max_radius: f32, // Where it stops; screen-wide rings reach the far corner
+    hit_ids: Vec<Id>, // Each enemy is only damaged once per ring
+}
+
+// Area damage waiting to go off. Enemies born from `spare_from` on
+// came after it (splitter fragments) and are left alone, so a chain
+// can't keep feeding itself.
+struct Blast {
+    pos: Vec2,
+    radius: f32,
+    damage: i32,
+    spare_from: u32,
+}
+
+// Top-level screen
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Screen {
+    Title,
+    Playing, // Includes the last stand and game-over overlay
+    Victory, // Final wave cleared; results over the still-animating world
+}
+
+// Title menu entries: a run (ruleset and whether it's co-op), the
+// loadout picker, or the settings
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TitleAction {
+    Play(GameMode, bool),
+    Loadout,
+    Codex,
+    Settings,
 }
+const TITLE_MENU: [(&str, TitleAction); 9] = [
+    ("SOLO", TitleAction::Play(GameMode::Normal, false)),
+    ("CO-OP", TitleAction::Play(GameMode::Normal, true)),
+    ("DAILY", TitleAction::Play(GameMode::Daily, false)),
+    ("BOSS RUSH", TitleAction::Play(GameMode::BossRush, false)),
+    ("HARDCORE", TitleAction::Play(GameMode::Hardcore, false)),
+    ("SANDBOX", TitleAction::Play(GameMode::Sandbox, false)),
+    ("LOADOUT", TitleAction::Loadout),
+    ("CODEX", TitleAction::Codex),
+    ("SETTINGS", TitleAction::Settings),
+];
+// Packed a little tighter than the default so all entries clear the hint
+const TITLE_ITEM_HEIGHT: f32 = 44.0;
+
+// Codex row spacing (13 rows have to fit a 720px window) and the largest
+// sprite it draws, so the boss doesn't swamp the panel
+const CODEX_ITEM_HEIGHT: f32 = 30.0;
+const CODEX_SPRITE_SIZE: f32 = 36.0;
+
+// Settings screen rows; Left/Right (or picking a row) changes the value
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Setting {
+    ShipColor,
+    Controls,
+    Difficulty,
+    Quality,
+    ReducedMotion,
+    Back,
+}
+const SETTINGS_MENU: [Setting; 6] = [
+    Setting::ShipColor,
+    Setting::Controls,
+    Setting::Difficulty,
+    Setting::Quality,
+    Setting::ReducedMotion,
+    Setting::Back,
+];
+
+// Pause menu entries. The browser can't close its tab, so Quit goes to the title there.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PauseAction {
+    Resume,
+    Restart,
+    Settings,
+    SaveQuit,
+    Quit,
+}
+const PAUSE_MENU: [(&str, PauseAction); 5] = [
+    ("RESUME", PauseAction::Resume),
+    ("RESTART", PauseAction::Restart),
+    ("SETTINGS", PauseAction::Settings),
+    ("SAVE & QUIT", PauseAction::SaveQuit),
+    (if cfg!(target_arch = "wasm32") { "QUIT TO TITLE" } else { "QUIT" }, PauseAction::Quit),
+];
+
+// Sandbox spawn keys (enemy appears at the cursor)
+const SANDBOX_SPAWN_KEYS: [(KeyCode, EnemyKind); 12] = [
+    (KeyCode::Key1, EnemyKind::Drifter),
+    (KeyCode::Key2, EnemyKind::Chaser),
+    (KeyCode::Key3, EnemyKind::Splitter),
+    (KeyCode::Key4, EnemyKind::Shooter),
+    (KeyCode::Key5, EnemyKind::Circler),
+    (KeyCode::Key6, EnemyKind::Boss),
+    (KeyCode::Key7, EnemyKind::Support),
+    (KeyCode::Key8, EnemyKind::Layer),
+    (KeyCode::Key9, EnemyKind::Blinker),
+    (KeyCode::Key0, EnemyKind::Scavenger),
+    (KeyCode::Minus, EnemyKind::Mimic),
+    (KeyCode::Equal, EnemyKind::Carrier),
+];
 
 pub struct GameState {
+    config: Config,
     left_joystick: Joystick,
     right_joystick: Joystick,
-    player: Player,
+    move_filter: InputFilter,
+    aim_filter: InputFilter,
+    players: Vec<PlayerController>,
     left_touch_id: Option<u64>,
     right_touch_id: Option<u64>,
     
     // Visual enhancements
     particles: Vec<Particle>,
+    grid_offset: Vec2,
     trail: Vec<TrailSegment>,
+    enemy_trail: Vec<TrailSegment>,
+    #[allow(dead_code)]
     obstacles: Vec<Obstacle>,
     
     // Combat
     bullets: Vec<Bullet>,
-    enemies: Vec<Enemy>,
+    enemy_bullets: Vec<Bullet>,
+    enemies: Arena<Enemy>,
+    enemies_spawned: u32,
+    power_ups: Arena<PowerUp>,
     explosions: Vec<Explosion>,
-    shoot_cooldown: f32,
-    enemy_spawn_timer: f32,
+    special_rings: Vec<SpecialRing>,
+    blasts: Vec<Blast>, // Queued by kills, resolved once per step
+    mines: Arena<Mine>,
+    slow_fields: Vec<SlowField>,
+    telegraphs: Vec<SpawnTelegraph>,
+    floating_texts: Vec<FloatingText>,
+    events: Vec<GameEvent>, // Raised by the last step, kept until the next one
+    waves: WaveManager,
+    
+    // Run seed and the streams drawn from it. Cosmetic rolls get their own
+    // stream so quality settings (particle counts) can't shift spawns.
+    seed: u32,
+    rng: Rng,
+    fx_rng: Rng,
+    
+    // Fixed-timestep bookkeeping (unused on the variable-dt path)
+    accumulator: f32,
+    render_alpha: f32,
     
     // Game state
-    health: i32,
+    screen: Screen,
+    view_size: Vec2, // Window size last frame, to detect resizes
+    title_menu: Menu,
+    pause_menu: Menu,
+    settings_menu: Menu,
+    settings_open: bool, // Over the title or pause menu, taking their input
+    codex: Codex,
+    codex_menu: Menu, // One row per enemy kind, then BACK
+    codex_open: bool,
+    saved_run: Option<RunSave>, // Found on launch; the title offers to continue it
+    resume_menu: Menu,          // CONTINUE or DISCARD for that run
+    paused: bool,
+    quit: bool, // Leave the main loop (desktop Quit)
+    unspent_presses: Vec<PilotInput>, // Live presses no step has seen yet
+    script: Option<ScriptedInput>, // Drives the first pilot, one frame per step, while it lasts
+    idle_time: f32, // Seconds on the title without input
+    attract: Option<Box<GameState>>, // Demo round playing behind the title
+    demo: bool, // This is that demo: autopiloted, nothing recorded
     score: i32,
+    displayed_score: f32, // HUD score, counting up toward `score`
+    kills: i32,
+    hitless: bool,       // No pilot has been hit yet this run
+    hitless_waves: u32,  // Waves cleared before the first hit
+    combo: i32,
+    combo_timer: f32,
+    chain_count: u32, // Back-to-back kills, each within `chain_window` of the last
+    chain_timer: f32,
+    adaptive_scale: f32, // Rubber-banding on spawn rate and enemy speed; 1.0 when off
+    overdrive_timer: f32, // Seconds of overdrive left; 0 when inactive
+    special_meter: f32, // 0.0 - 1.0, fires the special ring when full
+    weapon_level: usize, // Index into `config.weapon_levels`, shared by the team
+    extra_lives: u32,       // Spare lives, shared by the team
+    next_life_score: usize, // Index of the next `config.extra_life_scores` to reach
+    stall_timer: f32,   // Seconds of an active wave since the last kill
+    announcement: Option<Announcement>,
+    wave_clear: Option<WaveClear>,
+    tutorial: Option<Tutorial>,
+    threat: f32,
+    camera_zoom: f32, // Eased toward `target_zoom`; 1.0 shows the whole arena
     time: f32,
+    run_clock: f64, // Time actually played, to the millisecond; frozen while dying
     intro_alpha: f32,
     game_started: bool,
     safe_time: f32,
     game_over: bool,
+    game_over_time: f32,
+    death: Option<DeathSequence>,
+    hit_flash: f32,
+    hitstop_timer: f32, // > 0 while the simulation is held for impact
+    hitstop_booked: f32, // Frozen seconds granted lately; drains at the budget's rate
+    freeze_timer: f32, // > 0 while the Freeze power-up holds enemies still
+    god_mode: bool,
+    dev_overlay: bool,   // Dev tools: FPS/wave/pilot readout
+    show_hitboxes: bool, // Dev tools: outline every collision circle
+    
+    // Run statistics; only the final total is persisted
+    stats: RunStats,
+    breakdown: ScoreBreakdown,
+    best_score: i32,
+    best_survival: f32, // Longest-lasting run, tracked apart from score
+    best_hitless_waves: u32,
+    hardcore_best: i32, // Hardcore scores are ranked apart from normal runs
+    today: String,    // UTC date the run started, for the daily best
+    daily_best: i32,  // Best score on today's daily seed
+    best_clear_time: Option<f64>, // Fastest win in this mode
+    achievements: Achievements,
+    wave_start_hits: u32, // `stats.hits_taken` when the current wave began
+    
+    // Disk IO stays out of `update`: steps only flag what needs saving and
+    // `tick` writes it once they're done
+    persistent: bool,        // Reads and writes the score store; off for headless runs
+    run_ended: bool,         // The run just ended; its records aren't saved yet
+    tutorial_finished: bool, // The walkthrough just ended; settings aren't saved yet
+    
+    // Ghost replay: this run's recording and the previous run's path
+    ghost: GhostRecorder,
+    previous_ghost: Option<GhostRecorder>,
+    mimic_track: GhostRecorder, // Short, fine-grained path Mimics copy
+    
+    // Post-processing (None if unsupported - glows fall back to stacked circles)
+    postfx: Option<PostFx>,
 }
 
 impl GameState {
     pub fn new() -> Self {
-        let screen_width = screen_width();
-        let screen_height = screen_height();
+        Self::with_config(Config::load())
+    }
+
+    pub fn with_config(config: Config) -> Self {
+        let seed = Self::run_seed(&config);
+        let mut state = Self::build(config, seed, Vec2::new(screen_width(), screen_height()));
+        state.persistent = true;
+        state.load_records();
+        state.codex = Codex::load(SCORES_FILE);
+        state.achievements = Achievements::load(SCORES_FILE);
+        state.postfx = PostFx::new();
+        // Settings may name a paint this save hasn't earned
+        if !state.color_unlocked(state.config.ship_color) {
+            state.config.ship_color = ShipColor::Blue;
+        }
+        state.saved_run = RunSave::load(RUN_SAVE_FILE);
+        if state.saved_run.as_ref().is_some_and(|save| !state.resumable(save)) {
+            state.saved_run = None;
+            RunSave::discard(RUN_SAVE_FILE);
+        }
+        state.screen = Screen::Title;
+        state
+    }
+
+    /// 🧪 A run on `seed` in a `view_size` arena that never touches the
+    /// window or the disk: no records are read or saved. Step it with
+    /// [`Self::update`]; live pilots stay idle unless they're handed input.
+    pub fn headless(config: Config, seed: u32, view_size: Vec2) -> Self {
+        Self::build(config, seed, view_size)
+    }
+
+    // Everything a run needs, laid out for a `view_size` arena. Touches no
+    // window state or files, so runs can be built (and stepped) without either.
+    fn build(config: Config, seed: u32, view_size: Vec2) -> Self {
+        let (screen_width, screen_height) = (view_size.x, view_size.y);
+        
+        let mut rng = Rng::new(seed);
+        let waves = match config.mode {
+            GameMode::BossRush => WaveManager::boss_rush(rng.fork()),
+            GameMode::Practice => WaveManager::practice(rng.fork(), config.practice_boss),
+            _ => WaveManager::new(rng.fork(), config.endless),
+        };
+        let mut fx_rng = rng.fork();
+        
+        // Sized up front so a mass clear doesn't reallocate mid-frame
+        let explosions = Vec::with_capacity(config.caps.explosions);
+        
+        // Solo touch controls are what the walkthrough explains
+        let first_launch = !config.tutorial_completed && config.mode == GameMode::Normal && !config.coop;
+        let tutorial = first_launch.then_some(Tutorial {
+            step: TutorialStep::Move,
+            progress: 0.0,
+        });
+        
+        let stick_radius = config.sticks.radius.max(20.0);
+        let stick_gate = config.sticks.gate;
+        let camera_zoom = config.zoom.clamp(1.0, MAX_ZOOM);
+        // The title menu opens on the last mode played
+        let last_mode = TITLE_MENU
+            .iter()
+            .position(|&(_, action)| action == TitleAction::Play(config.mode, config.coop))
+            .unwrap_or(0);
         
         // Create atmospheric particles
-        let mut particles = Vec::new();
-        for _ in 0..150 {
-            particles.push(Particle {
-                pos: Vec2::new(
-                    rand::gen_range(0.0, screen_width),
-                    rand::gen_range(0.0, screen_height),
-                ),
-                velocity: Vec2::new(
-                    rand::gen_range(-15.0, 15.0),
-                    rand::gen_range(-15.0, 15.0),
-                ),
-                size: rand::gen_range(1.0, 3.0),
-                alpha: rand::gen_range(0.1, 0.4),
-            });
-        }
+        let particles = (0..config.quality.particle_count())
+            .map(|_| Particle::random(&mut fx_rng, screen_width, screen_height))
+            .collect();
         
         // No obstacles - clean space
         let obstacles = Vec::new();
         
+        let ghost = GhostRecorder::new(config.ghost_sample_interval, config.ghost_max_samples);
+        let mimic_samples = ((config.mimic_delay.max(0.0) + 1.0) / MIMIC_SAMPLE_INTERVAL).ceil() as usize;
+        let mimic_track = GhostRecorder::new(MIMIC_SAMPLE_INTERVAL, mimic_samples);
+        let weapon_level = config.loadout.starting_weapon_level().min(config.weapon_levels.len() - 1);
+        
+        // Solo flies with the twin sticks; co-op splits the keyboard
+        let seats: &[(f32, Control)] = if config.coop { &COOP_SEATS } else { &SOLO_SEATS };
+        let players = seats
+            .iter()
+            .enumerate()
+            .map(|(seat, &(x, control))| {
+                let mut player = Player::new(JoyVec2::new(screen_width * x, screen_height / 2.0));
+                player.damping = config.player_damping;
+                player.thrust_curve = config.thrust_curve;
+                player.hitbox_radius = config.hitbox_radius;
+                PlayerController::new(player, control, seat, max_health(config.mode))
+            })
+            .collect();
+        
         Self {
-            left_joystick: Joystick::new(80.0),
-            right_joystick: Joystick::new(80.0),
-            player: Player::new(JoyVec2::new(screen_width / 2.0, screen_height / 2.0)),
+            config,
+            left_joystick: Joystick::new(stick_radius, stick_gate),
+            right_joystick: Joystick::new(stick_radius, stick_gate),
+            move_filter: InputFilter::default(),
+            aim_filter: InputFilter::default(),
+            players,
             left_touch_id: None,
             right_touch_id: None,
             particles,
+            grid_offset: Vec2::ZERO,
             trail: Vec::new(),
+            enemy_trail: Vec::new(),
             obstacles,
             bullets: Vec::new(),
-            enemies: Vec::new(),
-            explosions: Vec::new(),
-            shoot_cooldown: 0.0,
-            enemy_spawn_timer: 0.0,
-            health: 3,
+            enemy_bullets: Vec::new(),
+            enemies_spawned: 0,
+            power_ups: Arena::new(),
+            enemies: Arena::new(),
+            explosions,
+            special_rings: Vec::new(),
+            blasts: Vec::new(),
+            mines: Arena::new(),
+            slow_fields: Vec::new(),
+            telegraphs: Vec::new(),
+            floating_texts: Vec::new(),
+            events: Vec::new(),
+            waves,
+            seed,
+            rng,
+            fx_rng,
+            accumulator: 0.0,
+            render_alpha: 1.0,
+            screen: Screen::Playing,
+            view_size,
+            title_menu: Menu::new(TITLE_MENU.len()).compact(TITLE_ITEM_HEIGHT).with_selected(last_mode),
+            pause_menu: Menu::new(PAUSE_MENU.len()),
+            settings_menu: Menu::new(SETTINGS_MENU.len()),
+            settings_open: false,
+            codex: Codex::new(SCORES_FILE),
+            codex_menu: Menu::new(EnemyKind::ALL.len() + 1).compact(CODEX_ITEM_HEIGHT),
+            codex_open: false,
+            saved_run: None,
+            resume_menu: Menu::new(2),
+            paused: false,
+            quit: false,
+            unspent_presses: Vec::new(),
+            script: None,
+            idle_time: 0.0,
+            attract: None,
+            demo: false,
             score: 0,
+            displayed_score: 0.0,
+            kills: 0,
+            hitless: true,
+            hitless_waves: 0,
+            combo: 0,
+            combo_timer: 0.0,
+            chain_count: 0,
+            chain_timer: 0.0,
+            adaptive_scale: 1.0,
+            overdrive_timer: 0.0,
+            special_meter: 0.0,
+            weapon_level,
+            extra_lives: 0,
+            next_life_score: 0,
+            stall_timer: 0.0,
+            announcement: None,
+            wave_clear: None,
+            tutorial,
+            threat: 0.0,
+            camera_zoom,
             time: 0.0,
+            run_clock: 0.0,
             intro_alpha: 1.0,
             game_started: false,
             safe_time: 3.0,
             game_over: false,
+            game_over_time: 0.0,
+            death: None,
+            hit_flash: 0.0,
+            hitstop_timer: 0.0,
+            hitstop_booked: 0.0,
+            freeze_timer: 0.0,
+            god_mode: false,
+            dev_overlay: false,
+            show_hitboxes: false,
+            stats: RunStats::default(),
+            breakdown: ScoreBreakdown::default(),
+            best_score: 0,
+            best_survival: 0.0,
+            best_hitless_waves: 0,
+            hardcore_best: 0,
+            today: today(),
+            daily_best: 0,
+            best_clear_time: None,
+            achievements: Achievements::new(SCORES_FILE),
+            persistent: false,
+            run_ended: false,
+            tutorial_finished: false,
+            wave_start_hits: 0,
+            ghost,
+            mimic_track,
+            previous_ghost: None,
+            postfx: None,
         }
     }
 
-    pub fn update(&mut self, dt: f32) {
-        // Check for restart on game over
-        if self.game_over {
-            if is_key_pressed(KeyCode::Space) || is_mouse_button_pressed(MouseButton::Left) {
-                *self = GameState::new();
+    // Personal bests from the score store. The daily best only counts
+    // while it's still that day.
+    fn load_records(&mut self) {
+        let scores = Store::load(SCORES_FILE);
+        self.best_score = scores.get("best").unwrap_or(0);
+        self.best_survival = scores.get("best_survival").unwrap_or(0.0);
+        self.best_hitless_waves = scores.get("best_hitless_waves").unwrap_or(0);
+        self.hardcore_best = scores.get(&clear_key(GameMode::Hardcore, "best")).unwrap_or(0);
+        self.best_clear_time = scores.get(&clear_key(self.config.mode, "best_time"));
+        self.daily_best = match scores.get::<String>("daily_date") {
+            Some(date) if date == self.today => scores.get("daily_best").unwrap_or(0),
+            _ => 0,
+        };
+    }
+
+    /// ⏱️ Frame rate limit the main loop should pace to
+    pub fn fps_cap(&self) -> Option<u32> {
+        self.config.fps_cap
+    }
+
+    /// 🎮 Start a run with the given rules
+    fn start_run(&mut self, mode: GameMode, coop: bool) {
+        self.config.mode = mode;
+        self.config.coop = coop;
+        self.reset();
+    }
+    
+    // Save-and-quit is for real runs that are still going
+    fn can_save(&self) -> bool {
+        let unranked = self.sandbox() || self.practice() || self.demo || self.script.is_some();
+        !unranked && self.screen == Screen::Playing && !self.game_over && self.death.is_none()
+    }
+    
+    // A Daily save only counts on the day it was seeded for; picked up
+    // later it would rank an old seed on today's board
+    fn resumable(&self, save: &RunSave) -> bool {
+        save.mode != GameMode::Daily || save.seed == daily_seed(&self.today)
+    }
+    
+    // The run as it stands, for save-and-quit
+    fn run_save(&self) -> RunSave {
+        RunSave {
+            mode: self.config.mode,
+            coop: self.config.coop,
+            loadout: self.config.loadout,
+            seed: self.seed,
+            rng: self.rng.state(),
+            wave_rng: self.waves.rng_state(),
+            score: self.score,
+            kills: self.kills,
+            time: self.time,
+            run_clock: self.run_clock,
+            weapon_level: self.weapon_level,
+            special_meter: self.special_meter,
+            extra_lives: self.extra_lives,
+            next_life_score: self.next_life_score,
+            hitless: self.hitless,
+            hitless_waves: self.hitless_waves,
+            stats: self.stats.clone(),
+            wave: self.waves.wave(),
+            queue: self.waves.pending(),
+            wave_elapsed: self.waves.wave_elapsed(),
+            pilots: self
+                .players
+                .iter()
+                .map(|c| SavedPilot { x: c.player.position.x, y: c.player.position.y, health: c.health })
+                .collect(),
+            enemies: self
+                .enemies
+                .values()
+                .map(|e| SavedEnemy {
+                    kind: e.kind,
+                    x: e.pos.x,
+                    y: e.pos.y,
+                    vx: e.velocity.x,
+                    vy: e.velocity.y,
+                    rotation: e.rotation,
+                    health: e.health,
+                    max_health: e.max_health,
+                    size: e.size,
+                    speed: e.speed,
+                    score_value: e.score_value,
+                    fire_timer: e.fire_timer,
+                    cargo: e.cargo,
+                })
+                .collect(),
+        }
+    }
+    
+    // Pick a saved run back up: a fresh run on its seed and kit, then the
+    // saved wave, score, pilots and enemies laid over it
+    fn resume_run(&mut self, save: RunSave) {
+        self.config.mode = save.mode;
+        self.config.coop = save.coop;
+        self.config.loadout = save.loadout;
+        self.reset_with_seed(save.seed);
+        self.tutorial = None;
+        
+        self.score = save.score;
+        self.displayed_score = save.score as f32;
+        self.kills = save.kills;
+        self.time = save.time;
+        self.run_clock = save.run_clock;
+        self.weapon_level = save.weapon_level.min(self.config.weapon_levels.len() - 1);
+        self.special_meter = save.special_meter;
+        self.extra_lives = save.extra_lives;
+        self.next_life_score = save.next_life_score;
+        self.hitless = save.hitless;
+        self.hitless_waves = save.hitless_waves;
+        self.wave_start_hits = save.stats.hits_taken;
+        self.stats = save.stats;
+        if self.waves.resume(save.wave, &save.queue, save.wave_elapsed).is_err() {
+            self.waves.jump_to(save.wave);
+        }
+        for (controller, pilot) in self.players.iter_mut().zip(&save.pilots) {
+            controller.player.position = JoyVec2::new(pilot.x, pilot.y);
+            controller.prev_position = controller.player.position;
+            controller.health = pilot.health;
+        }
+        for saved in save.enemies {
+            let enemy = self.restore_enemy(&saved);
+            self.insert_enemy(enemy);
+        }
+        
+        // Carry on both streams from where the save left them, so a seeded
+        // run plays out as it would have without the break
+        self.rng = Rng::from_state(save.rng);
+        self.waves.restore_rng(save.wave_rng);
+    }
+    
+    // A saved enemy as it was, rebuilt without touching the run's RNG:
+    // same size (and the poise that goes with it), speed, heading, health,
+    // worth, fire timer and cargo
+    fn restore_enemy(&self, saved: &SavedEnemy) -> Enemy {
+        let mut enemy = Enemy::new(saved.kind, Vec2::new(saved.x, saved.y));
+        if let Some(&(_, poise)) = self.config.poise.iter().find(|(k, _)| *k == saved.kind) {
+            enemy.max_poise = poise * (saved.size / enemy.size).powi(2);
+            enemy.poise = enemy.max_poise;
+        }
+        enemy.size = saved.size;
+        enemy.speed = saved.speed;
+        enemy.velocity = Vec2::new(saved.vx, saved.vy);
+        enemy.rotation = saved.rotation;
+        enemy.health = saved.health;
+        enemy.max_health = saved.max_health.max(saved.health);
+        enemy.score_value = saved.score_value;
+        enemy.fire_timer = saved.fire_timer;
+        enemy.cargo = saved.cargo;
+        enemy
+    }
+    
+    // The launch-time offer of a saved run: pick it up, or throw it away
+    fn update_resume_offer(&mut self) {
+        let mut chosen = self.resume_menu.update(Self::title_menu_top());
+        if is_key_pressed(KeyCode::Escape) {
+            chosen = Some(1);
+        }
+        match chosen {
+            Some(0) => {
+                if let Some(save) = self.saved_run.take() {
+                    RunSave::discard(RUN_SAVE_FILE);
+                    if self.resumable(&save) {
+                        self.resume_run(save);
+                    }
+                }
+            }
+            Some(_) => {
+                self.saved_run = None;
+                RunSave::discard(RUN_SAVE_FILE);
+            }
+            None => {}
+        }
+    }
+    
+    // Practice rules: nothing hurts, nothing spawns on its own
+    fn sandbox(&self) -> bool {
+        self.config.mode == GameMode::Sandbox
+    }
+    
+    // A lone boss fight from the codex: scored as usual, but it counts
+    // for no records or achievements
+    fn practice(&self) -> bool {
+        self.config.mode == GameMode::Practice
+    }
+    
+    fn invincible(&self) -> bool {
+        self.god_mode || self.sandbox()
+    }
+
+    /// 🔄 Start a fresh run, keeping settings and this run's ghost
+    pub fn reset(&mut self) {
+        self.reset_with_seed(Self::run_seed(&self.config));
+    }
+    
+    // Daily runs share the date's seed; otherwise the configured or a fresh one
+    fn run_seed(config: &Config) -> u32 {
+        match config.mode {
+            GameMode::Daily => daily_seed(&today()),
+            _ => config.seed.unwrap_or_else(fresh_seed),
+        }
+    }
+    
+    // Fresh run on a specific seed (same seed, same spawns and drops)
+    fn reset_with_seed(&mut self, seed: u32) {
+        let config = self.config.clone();
+        let finished_run = std::mem::take(&mut self.ghost);
+        let postfx = self.postfx.take();
+        let (best_score, best_survival, hardcore_best) = (self.best_score, self.best_survival, self.hardcore_best);
+        let best_hitless_waves = self.best_hitless_waves;
+        let (today, daily_best) = (std::mem::take(&mut self.today), self.daily_best);
+        let mut achievements = self.achievements.clone();
+        achievements.new_run();
+        let codex = self.codex.clone();
+        let persistent = self.persistent;
+        *self = GameState::build(config, seed, self.view_size);
+        self.persistent = persistent;
+        if persistent {
+            self.load_records();
+        }
+        self.achievements = achievements;
+        self.codex = codex;
+        self.postfx = postfx;
+        self.best_score = best_score;
+        self.best_survival = best_survival;
+        self.best_hitless_waves = best_hitless_waves;
+        self.hardcore_best = hardcore_best;
+        if self.today == today {
+            self.daily_best = self.daily_best.max(daily_best);
+        }
+        if self.config.ghost_recording {
+            self.previous_ghost = Some(finished_run);
+        }
+    }
+
+    /// ⏱️ Advance by one rendered frame: a single variable step by default,
+    /// or as many fixed steps as have accumulated when a timestep is set
+    pub fn tick(&mut self, frame_dt: f32) {
+        self.resize_to(Vec2::new(screen_width(), screen_height()));
+        
+        // A script sets the pace as well as the input: one frame per tick
+        if let Some(mut script) = self.script.take() {
+            if self.advance(&mut script) {
+                self.script = Some(script);
+            }
+            self.persist();
+            return;
+        }
+        
+        // P pauses a live run; nothing advances until it's pressed again.
+        // Checked once per frame so fixed steps can't toggle it twice.
+        let live = self.screen == Screen::Playing && !self.game_over && self.death.is_none();
+        if live && is_key_pressed(KeyCode::P) {
+            self.set_paused(!self.paused);
+        }
+        
+        // Any press cuts the intro fade short, and once it's over the
+        // tutorial can be skipped. Checked before this frame's steps, so the
+        // press that started the run can't also skip either, nor one press both.
+        if live && !self.paused {
+            self.skip_ahead(any_press(), self.tutorial_skip_pressed());
+        }
+        // The simulation holds still while the pause menu takes the input
+        if self.paused {
+            if self.settings_open {
+                self.update_settings();
+            } else {
+                self.update_pause_menu();
+            }
+            return;
+        }
+        
+        // Menus, hotkeys and the touch sticks read the window once per
+        // frame; the steps below only see what they left behind
+        if self.screen == Screen::Title {
+            if self.settings_open {
+                self.update_settings();
+            } else if self.codex_open {
+                self.update_codex();
+            } else if self.saved_run.is_some() {
+                self.update_resume_offer();
+            } else if self.update_attract(frame_dt.min(MAX_FRAME_CATCHUP)) {
+                self.update_title();
+            }
+            return;
+        }
+        if self.handle_frame_input() {
+            return;
+        }
+        
+        let steps = self.frame_steps(frame_dt);
+        let mut pilots = self.live_input();
+        for (pilot, held) in pilots.iter_mut().zip(&self.unspent_presses) {
+            pilot.latch(held);
+        }
+        let mut live = LiveInput { pilots, steps: steps.into_iter() };
+        while self.advance(&mut live) {}
+        // A frame too short for a fixed step keeps its presses for the next
+        self.unspent_presses = live.pilots;
+        self.persist();
+    }
+    
+    // Step lengths for this frame: the whole frame on the variable-dt
+    // path, else however many fixed steps have accumulated
+    fn frame_steps(&mut self, frame_dt: f32) -> Vec<f32> {
+        let Some(step) = self.config.fixed_timestep else {
+            self.render_alpha = 1.0;
+            return vec![frame_dt];
+        };
+        
+        // Clamp long hitches so we don't spiral trying to catch up
+        self.accumulator += frame_dt.min(MAX_FRAME_CATCHUP);
+        let mut steps = Vec::new();
+        while self.accumulator >= step {
+            steps.push(step);
+            self.accumulator -= step;
+        }
+        self.render_alpha = self.accumulator / step;
+        steps
+    }
+    
+    /// 🎬 Step once on `source`'s next input; false (without stepping)
+    /// once it has run out
+    pub fn advance(&mut self, source: &mut impl InputSource) -> bool {
+        let Some(input) = source.next_step() else {
+            return false;
+        };
+        self.update(input.dt, &input.pilots);
+        true
+    }
+    
+    // Write out what the steps just settled: a finished run's records,
+    // new codex entries and unlocks, the walkthrough being done
+    fn persist(&mut self) {
+        let run_ended = std::mem::take(&mut self.run_ended);
+        let tutorial_finished = std::mem::take(&mut self.tutorial_finished);
+        if !self.persistent {
+            return;
+        }
+        if run_ended {
+            self.record_run();
+        }
+        if tutorial_finished {
+            self.config.save();
+        }
+        self.codex.save();
+        self.achievements.save();
+    }
+    
+    // Draw position between the previous and current step. Jumps larger
+    // than a wrap can produce aren't interpolated (they'd smear across the screen).
+    fn interpolate(&self, prev: Vec2, pos: Vec2) -> Vec2 {
+        if (pos - prev).length() > INTERPOLATION_MAX_JUMP {
+            return pos;
+        }
+        prev.lerp(pos, self.render_alpha)
+    }
+
+    /// 🔄 Advance one step, then let effects react to what happened in it.
+    /// `input` is each pilot's, in seat order (the autopilot ignores it).
+    /// Reads no window, keyboard, touch or file state: `tick` gathers live
+    /// input beforehand and saves records afterwards.
+    pub fn update(&mut self, dt: f32, input: &[PilotInput]) {
+        self.events.clear();
+        self.step(dt, input);
+        self.dispatch_events();
+    }
+    
+    fn step(&mut self, dt: f32, input: &[PilotInput]) {
+        if self.screen == Screen::Title {
+            return;
+        }
+        self.achievements.update(dt);
+        
+        // Snapshot positions so the renderer can interpolate between steps
+        for controller in &mut self.players {
+            controller.prev_position = controller.player.position;
+        }
+        for enemy in self.enemies.values_mut() {
+            enemy.prev_pos = enemy.pos;
+        }
+        
+        // After a win nothing spawns or moves any more, but the last
+        // explosions keep playing out
+        let won = self.screen == Screen::Victory;
+        if self.game_over || won {
+            self.game_over_time += dt;
+            if won {
+                self.update_effects(dt);
+                self.reap_transients(dt);
             }
             return;
         }
         
+        // Last stand runs on its own timer; input is ignored until game over
+        let dying = self.death.is_some();
+        let dt = match &mut self.death {
+            Some(death) => {
+                death.elapsed += dt;
+                if death.elapsed >= DEATH_SEQUENCE_TIME {
+                    self.death = None;
+                    self.finish_run();
+                    return;
+                }
+                dt * self.death_time_scale()
+            }
+            None => dt,
+        };
+        
+        // Hit-stop holds the simulation; flashes and callouts run on real time
+        let real_dt = dt;
+        let refill = self.config.hitstop.budget / self.config.hitstop.window.max(f32::EPSILON);
+        self.hitstop_booked = (self.hitstop_booked - refill * real_dt).max(0.0);
+        let dt = if self.hitstop_timer > 0.0 {
+            // A stop shorter than the step still holds all of it; that counts too
+            self.hitstop_booked += (dt - self.hitstop_timer).max(0.0);
+            self.hitstop_timer -= dt;
+            0.0
+        } else {
+            dt
+        };
+        
         self.time += dt;
+        if !dying {
+            self.run_clock += dt as f64;
+        }
         
         // Fade in intro
         if self.intro_alpha > 0.0 {
-            self.intro_alpha -= dt * 0.5;
-            if self.intro_alpha < 0.0 {
-                self.intro_alpha = 0.0;
-                self.game_started = true;
+            self.intro_alpha -= dt / self.config.intro_duration.max(f32::EPSILON);
+            if self.intro_alpha <= 0.0 {
+                self.skip_intro();
             }
         }
         
@@ -158,229 +1832,2478 @@ impl GameState {
         if self.safe_time > 0.0 {
             self.safe_time -= dt;
         }
-        
-        self.handle_input();
-
-        // Get input from joysticks
-        let movement = self.left_joystick.get_input();
-        let aim = self.right_joystick.get_input();
-
-        // Update player
-        self.player.update(movement, aim, dt);
 
-        // Shooting mechanic - auto-fire when aiming
-        self.shoot_cooldown -= dt;
-        if self.right_joystick.active && self.shoot_cooldown <= 0.0 {
-            self.shoot();
-            self.shoot_cooldown = 0.15; // Fire rate
+        let special_fired = self.update_pilots(dt, input);
+        self.advance_tutorial(real_dt);
+        
+        if special_fired {
+            self.request_hitstop(self.config.hitstop.special);
         }
-
-        // Add trail segment
-        let player_pos = to_mac_vec2(self.player.position);
-        if movement.x.abs() > 0.1 || movement.y.abs() > 0.1 {
-            self.trail.push(TrailSegment {
-                pos: player_pos,
-                life: 1.0,
-                size: 30.0,
-            });
+        
+        // The ghost follows the first pilot
+        if self.config.ghost_recording {
+            let player = &self.players[0].player;
+            self.ghost.record(self.time, to_mac_vec2(player.position), player.rotation);
+        }
+        // Mimics shadow whoever is leading
+        if let Some(lead) = self.players.iter().find(|c| c.alive()) {
+            self.mimic_track.record(self.time, to_mac_vec2(lead.player.position), lead.player.rotation);
         }
         
-        // Update trail
-        self.trail.retain_mut(|seg| {
-            seg.life -= dt * 2.0;
-            seg.size -= dt * 40.0;
-            seg.life > 0.0
-        });
+        self.update_trail(dt);
         
-        // Keep only last 20 trail segments
-        if self.trail.len() > 20 {
-            self.trail.drain(0..self.trail.len() - 20);
+        // Shots drift in the force field only if configured to
+        if self.config.force_on_bullets && self.config.force_field != ForceField::Off {
+            for bullet in &mut self.bullets {
+                bullet.velocity += field_force(&self.config, self.view_size, bullet.pos) * dt;
+            }
+            if self.freeze_timer <= 0.0 {
+                for bullet in &mut self.enemy_bullets {
+                    bullet.velocity += field_force(&self.config, self.view_size, bullet.pos) * dt;
+                }
+            }
         }
-
-        // Wrap player around screen edges
-        let mut pos = self.player.position;
-        if pos.x < 0.0 { pos.x = screen_width(); }
-        if pos.x > screen_width() { pos.x = 0.0; }
-        if pos.y < 0.0 { pos.y = screen_height(); }
-        if pos.y > screen_height() { pos.y = 0.0; }
-        self.player.position = pos;
         
-        // Update bullets
-        self.bullets.retain_mut(|bullet| {
-            bullet.pos += bullet.velocity * dt;
-            bullet.life -= dt;
-            
-            // Remove bullets off screen or expired
-            bullet.life > 0.0 
-                && bullet.pos.x > 0.0 && bullet.pos.x < screen_width()
-                && bullet.pos.y > 0.0 && bullet.pos.y < screen_height()
-        });
+        self.reap_transients(dt);
+        self.send_shockwaves();
+        self.move_bullets(dt);
+        
+        if self.update_spawning(dt, dying) {
+            return;
+        }
+        
+        // Frozen enemies hang in place but can still be shot
+        let frozen = self.freeze_timer > 0.0;
+        if !frozen {
+            self.move_enemies(dt);
+        }
         
-        // Spawn enemies
-        if self.safe_time <= 0.0 {
-            self.enemy_spawn_timer -= dt;
-            if self.enemy_spawn_timer <= 0.0 {
-                self.spawn_enemy();
-                self.enemy_spawn_timer = rand::gen_range(1.0, 2.5); // Spawn every 1-2.5 seconds
+        self.update_enemy_trail(dt, frozen);
+        for enemy in self.enemies.values_mut() {
+            enemy.hit_flash_timer = (enemy.hit_flash_timer - real_dt).max(0.0);
+        }
+        
+        self.raise_shields();
+        self.apply_slow_fields();
+        self.hit_enemies(dt);
+        self.resolve_blasts();
+        self.collect_power_ups();
+        
+        self.hit_flash = (self.hit_flash - real_dt).max(0.0);
+        for controller in &mut self.players {
+            controller.animate_hearts(real_dt);
+        }
+        self.freeze_timer = (self.freeze_timer - dt).max(0.0);
+        
+        // Combo decays if no kill lands within the window
+        if self.combo > 0 {
+            self.combo_timer -= dt;
+            if self.combo_timer <= 0.0 {
+                self.combo = 0;
+                self.announcement = None;
             }
         }
+        self.overdrive_timer = (self.overdrive_timer - dt).max(0.0);
+        self.chain_timer -= dt;
+        if self.chain_timer <= 0.0 {
+            self.chain_count = 0;
+        }
         
-        // Update enemies - they chase the player!
-        for enemy in &mut self.enemies {
-            let player_pos = to_mac_vec2(self.player.position);
-            let to_player = player_pos - enemy.pos;
-            let distance = to_player.length();
-            
-            if distance > 0.0 {
-                // Chase player
-                let direction = to_player / distance;
-                enemy.velocity = direction * 150.0; // Enemy speed
-                enemy.rotation = direction.y.atan2(direction.x);
+        if !self.sandbox() && !self.practice() && !self.demo {
+            self.check_achievements();
+        }
+        
+        if let Some(clear) = &mut self.wave_clear {
+            clear.life -= real_dt;
+            if clear.life <= 0.0 {
+                self.wave_clear = None;
             }
-            
-            enemy.pos += enemy.velocity * dt;
-            
-            // Wrap enemies around screen
-            if enemy.pos.x < -50.0 { enemy.pos.x = screen_width() + 50.0; }
-            if enemy.pos.x > screen_width() + 50.0 { enemy.pos.x = -50.0; }
-            if enemy.pos.y < -50.0 { enemy.pos.y = screen_height() + 50.0; }
-            if enemy.pos.y > screen_height() + 50.0 { enemy.pos.y = -50.0; }
         }
         
-        // Check bullet vs enemy collisions
-        let mut enemies_to_remove = Vec::new();
-        for (i, enemy) in self.enemies.iter_mut().enumerate() {
-            for bullet in &mut self.bullets {
-                let dist = (bullet.pos - enemy.pos).length();
-                if dist < enemy.size + 10.0 {
-                    enemy.health -= 1;
-                    bullet.life = 0.0; // Remove bullet
-                    
-                    if enemy.health <= 0 {
-                        enemies_to_remove.push(i);
-                        self.score += 100;
-                        self.explosions.push(Explosion {
-                            pos: enemy.pos,
-                            life: 0.5,
-                            size: enemy.size * 2.0,
-                        });
-                    }
-                }
+        // Streak callouts fade quickly
+        if let Some(announcement) = &mut self.announcement {
+            announcement.life -= real_dt;
+            if announcement.life <= 0.0 {
+                self.announcement = None;
             }
         }
         
-        // Remove dead enemies
-        for &i in enemies_to_remove.iter().rev() {
-            self.enemies.remove(i);
+        // Threat level from nearby enemies, eased so the vignette doesn't flicker
+        let target_threat = self.compute_threat();
+        self.threat += (target_threat - self.threat) * (dt * 4.0).min(1.0);
+        let target_zoom = self.target_zoom();
+        self.camera_zoom += (target_zoom - self.camera_zoom) * (1.0 - (-ZOOM_EASE_RATE * dt).exp());
+        
+        // Enemy fire and enemies themselves against the pilots
+        self.parry_bullets();
+        self.graze_bullets();
+        self.shoot_pilots();
+        self.ram_enemies();
+        self.collide_pilots();
+        self.trip_mines(dt);
+        
+        self.update_effects(dt);
+        self.award_extra_lives();
+        
+        // HUD score spins up toward the real one
+        let gap = self.score as f32 - self.displayed_score;
+        self.displayed_score += gap * (1.0 - (-self.config.score_tick_rate * real_dt).exp());
+        if (self.score as f32 - self.displayed_score).abs() < 0.5 {
+            self.displayed_score = self.score as f32;
         }
         
-        // Check player vs enemy collisions
-        if self.safe_time <= 0.0 {
-            let player_pos = to_mac_vec2(self.player.position);
-            let mut collision_index = None;
+        self.update_backdrop(dt);
+        
+        // No obstacles to update
+        
+        self.shed_overflow();
+    }
+    
+    // Every live pilot moves, dashes, parries and fires on its input for
+    // this step. True if one of them set off the special.
+    fn update_pilots(&mut self, dt: f32, input: &[PilotInput]) -> bool {
+        let mut special_fired = false;
+        let fire_boost = self.overdrive_boost(self.config.overdrive.fire_rate);
+        let speed_boost = self.overdrive_boost(self.config.overdrive.speed);
+        for i in 0..self.players.len() {
+            if !self.players[i].alive() {
+                continue;
+            }
             
-            for (i, enemy) in self.enemies.iter().enumerate() {
-                let dist = (player_pos - enemy.pos).length();
-                if dist < 40.0 + enemy.size {
-                    collision_index = Some((i, enemy.pos, enemy.size));
-                    break;
-                }
+            // What this pilot is flying with this step
+            let raw = input.get(i).copied().unwrap_or_default();
+            let PilotInput { movement, aim, firing, trigger, parry, focus, special, dash } = self.resolve_input(i, raw, dt);
+            let movement = if focus { movement * self.config.focus_speed } else { movement };
+            let controller = &mut self.players[i];
+            controller.movement = movement;
+            controller.focused = focus;
+            controller.invuln_timer = (controller.invuln_timer - dt).max(0.0);
+            
+            // Parry opens a short shield window, then goes on cooldown
+            controller.parry_timer = (controller.parry_timer - dt).max(0.0);
+            controller.parry_cooldown = (controller.parry_cooldown - dt).max(0.0);
+            controller.parry_flash = (controller.parry_flash - dt).max(0.0);
+            controller.pierce_timer = (controller.pierce_timer - dt).max(0.0);
+            controller.mortar_timer = (controller.mortar_timer - dt).max(0.0);
+            if parry && controller.parry_cooldown <= 0.0 {
+                controller.parry_timer = PARRY_WINDOW;
+                controller.parry_cooldown = PARRY_COOLDOWN;
+            }
+            
+            // Dash along the stick (or the nose when idle)
+            controller.dash_timer = (controller.dash_timer - dt).max(0.0);
+            controller.dash_cooldown = (controller.dash_cooldown - dt).max(0.0);
+            let dash_scale = self.config.loadout.dash_cooldown_scale();
+            if let Some(scale) = dash_scale.filter(|_| dash && controller.dash_cooldown <= 0.0) {
+                let rotation = controller.player.rotation;
+                let heading = if movement.length() > 0.1 {
+                    movement.normalize()
+                } else {
+                    JoyVec2::new(rotation.cos(), rotation.sin())
+                };
+                controller.player.velocity = heading * DASH_SPEED;
+                controller.dash_timer = DASH_TIME;
+                controller.dash_cooldown = DASH_COOLDOWN * scale;
+                controller.dash_hit_ids.clear();
             }
             
-            if let Some((idx, pos, size)) = collision_index {
-                self.enemies.remove(idx);
-                self.explosions.push(Explosion {
+            // A full special meter (shared by the team) fires a ring
+            // and shields its pilot while it clears the way
+            if special && self.special_meter >= 1.0 {
+                self.special_meter = 0.0;
+                special_fired = true;
+                controller.invuln_timer = controller.invuln_timer.max(self.config.special_iframes);
+                let pos = to_mac_vec2(controller.player.position);
+                let view = self.view_size;
+                let far_corner = Vec2::new(pos.x.max(view.x - pos.x), pos.y.max(view.y - pos.y)).length();
+                self.special_rings.push(SpecialRing {
                     pos,
-                    life: 0.5,
-                    size: size * 2.0,
+                    radius: 0.0,
+                    max_radius: self.config.special_ring_radius.unwrap_or(far_corner),
+                    hit_ids: Vec::new(),
                 });
-                
-                // GAME OVER on collision!
-                self.game_over = true;
-                
-                // Also create explosion at player position
-                let player_pos = to_mac_vec2(self.player.position);
-                self.explosions.push(Explosion {
+            }
+
+            // Update player
+            controller.player.update(movement * speed_boost, aim, dt);
+            let push = field_force(&self.config, self.view_size, to_mac_vec2(controller.player.position));
+            controller.player.velocity += from_mac_vec2(push * dt);
+            
+            // Wrap or stop at the screen edges
+            let mut pos = to_mac_vec2(controller.player.position);
+            let mut velocity = to_mac_vec2(controller.player.velocity);
+            apply_boundary(self.config.edges.player, EdgeResponse::Stop, self.view_size, &mut pos, &mut velocity, 0.0);
+            controller.player.position = from_mac_vec2(pos);
+            controller.player.velocity = from_mac_vec2(velocity);
+
+            // Shooting mechanic - auto-fire when aiming
+            controller.shoot_cooldown -= dt;
+            let player_pos = to_mac_vec2(controller.player.position);
+            let rotation = controller.player.rotation;
+            let pierce = if controller.pierce_timer > 0.0 { PIERCE_COUNT } else { 0 };
+            let mortar = controller.mortar_timer > 0.0;
+            let trail_color = controller.trail_color(&self.config.trail_colors, self.time);
+            // A burst, once started, runs to the end even if the trigger lets go
+            if (firing || controller.burst_fired > 0) && controller.shoot_cooldown <= 0.0 {
+                // Feathering the aim stick fires slower, never below the floor
+                let floor = self.config.min_fire_rate.clamp(MIN_FIRE_RATE_FLOOR, 1.0);
+                let rate = (floor + (1.0 - floor) * trigger.clamp(0.0, 1.0)) * fire_boost;
+                let interval = self.config.weapon_levels[self.weapon_level].fire_interval * self.config.loadout.fire_interval_scale();
+                let burst = if mortar { 1 } else { self.config.burst.size };
+                controller.shoot_cooldown = controller.next_shot_delay(burst, self.config.burst.delay, interval / rate);
+                self.shoot(player_pos, rotation, pierce, mortar);
+            }
+
+            // Add trail segment
+            if (movement.x.abs() > 0.1 || movement.y.abs() > 0.1) && !self.reduced_motion() {
+                self.trail.push(TrailSegment {
                     pos: player_pos,
                     life: 1.0,
-                    size: 80.0,
+                    size: 30.0,
+                    color: trail_color,
                 });
             }
         }
-        
-        // Update explosions
-        self.explosions.retain_mut(|exp| {
-            exp.life -= dt * 2.0;
-            exp.size += dt * 100.0;
-            exp.life > 0.0
+        special_fired
+    }
+    
+    // Fade the pilots' trails and keep only the most recent segments
+    fn update_trail(&mut self, dt: f32) {
+        let style = self.config.trail;
+        self.trail.retain_mut(|seg| {
+            seg.life -= dt * style.fade_rate;
+            seg.size -= dt * style.shrink_rate;
+            seg.life > 0.0
         });
         
-        // Update particles (breathing world)
-        for particle in &mut self.particles {
-            particle.pos += particle.velocity * dt;
-            
-            // Wrap particles
-            if particle.pos.x < 0.0 { particle.pos.x = screen_width(); }
-            if particle.pos.x > screen_width() { particle.pos.x = 0.0; }
-            if particle.pos.y < 0.0 { particle.pos.y = screen_height(); }
-            if particle.pos.y > screen_height() { particle.pos.y = 0.0; }
+        // Keep only the most recent trail segments (per pilot)
+        let trail_cap = style.max_segments.min(self.config.quality.trail_cap()) * self.players.len();
+        if self.trail.len() > trail_cap {
+            self.trail.drain(0..self.trail.len() - trail_cap);
+        }
+    }
+    
+    // Fly every shot on; enemy shots hold in place while frozen. Shells
+    // burst where they leave the arena.
+    fn move_bullets(&mut self, dt: f32) {
+        let policy = self.config.edges.bullets;
+        let bounds = self.view_size;
+        let mut shell_bursts = Vec::new();
+        self.bullets.retain_mut(|bullet| {
+            bullet.prev_pos = bullet.pos;
+            bullet.velocity += bullet.accel * dt;
+            bullet.pos += bullet.velocity * dt;
             
-            // Gentle pulse
-            particle.alpha = 0.2 + (self.time * 2.0 + particle.pos.x * 0.01).sin() * 0.1;
+            // Remove bullets off screen; shells burst as they go
+            let alive = apply_boundary(policy, EdgeResponse::Bounce, bounds, &mut bullet.pos, &mut bullet.velocity, 0.0);
+            if !alive && bullet.shell {
+                shell_bursts.push(bullet.pos);
+            }
+            alive
+        });
+        for pos in shell_bursts {
+            self.explosions.push(Explosion::new(ExplosionKind::Large, pos, MORTAR_BLAST_RADIUS, &mut self.fx_rng));
+            self.area_damage(pos, MORTAR_BLAST_RADIUS, MORTAR_BLAST_DAMAGE);
         }
         
-        // No obstacles to update
+        // Update enemy bullets (held in place while frozen)
+        let frozen = self.freeze_timer > 0.0;
+        if !frozen {
+            self.enemy_bullets.retain_mut(|bullet| {
+                bullet.prev_pos = bullet.pos;
+                bullet.pos += bullet.velocity * dt;
+                apply_boundary(policy, EdgeResponse::Bounce, bounds, &mut bullet.pos, &mut bullet.velocity, 0.0)
+            });
+        }
+        if self.config.bullet_cancel {
+            self.cancel_bullets();
+        }
     }
     
-    fn shoot(&mut self) {
-        let player_pos = to_mac_vec2(self.player.position);
-        let rotation = self.player.rotation;
-        
-        // Bullet starts from front of ship
-        let bullet_start = Vec2::new(
-            player_pos.x + rotation.cos() * 45.0,
-            player_pos.y + rotation.sin() * 45.0,
-        );
-        
-        // Bullet velocity
-        let bullet_velocity = Vec2::new(
-            rotation.cos() * 600.0,
-            rotation.sin() * 600.0,
-        );
-        
-        self.bullets.push(Bullet {
-            pos: bullet_start,
-            velocity: bullet_velocity,
-            life: 2.0,
-        });
+    // Spawn enemies as the current wave dictates (sandbox spawns by
+    // hand, from `handle_frame_input`). Telegraphed enemies already
+    // hold their place under the cap. True once the run has been won.
+    fn update_spawning(&mut self, dt: f32, dying: bool) -> bool {
+        let incoming = self.enemies.len() + self.telegraphs.len();
+        if !self.sandbox() && self.safe_time <= 0.0 && !dying && self.tutorial.is_none() && incoming < self.enemy_cap() {
+            // Stalling without kills enrages the wave: spawns come faster
+            let was_enraged = self.enrage() > 0.0;
+            if self.waves.phase() == WavePhase::Spawning {
+                self.stall_timer += dt;
+            }
+            if !was_enraged && self.enrage() > 0.0 {
+                self.announcement = Some(Announcement {
+                    text: "ENRAGED".to_owned(),
+                    life: 1.2,
+                    tier: 0,
+                });
+            }
+            
+            // At the enemy cap the wave waits rather than losing spawns
+            let spawn_dt = dt * (1.0 + ENRAGE_SPAWN_BOOST * self.enrage()) * self.adaptive_scale;
+            let phase_before = self.waves.phase();
+            if let Some(group) = self.waves.update(spawn_dt, incoming) {
+                self.spawn_group(group);
+            }
+            if phase_before != WavePhase::Spawning && self.waves.phase() == WavePhase::Spawning {
+                self.lay_slow_fields(self.waves.slow_fields());
+                self.telegraph_burst();
+                self.schedule_carrier();
+            }
+            if phase_before == WavePhase::Spawning && self.waves.phase() != WavePhase::Spawning {
+                self.on_wave_cleared();
+                if self.waves.is_complete() {
+                    self.win_run();
+                    return true;
+                }
+            }
+        }
+        false
     }
     
-    fn spawn_enemy(&mut self) {
-        let screen_width = screen_width();
-        let screen_height = screen_height();
+    // Steer, move and fence in every enemy, and let go of the ones that
+    // escaped or strayed off for good
+    fn move_enemies(&mut self, dt: f32) {
+        let enemy_speed = (1.0 + ENRAGE_SPEED_BOOST * self.enrage()) * self.adaptive_scale;
+        // Separation steering keeps swarms from collapsing into one blob
+        let separation: Vec<Vec2> = self.enemies.iter().map(|(id, _)| self.separation_force(id)).collect();
         
-        // Spawn from edges
-        let side = rand::gen_range(0, 4);
-        let pos = match side {
-            0 => Vec2::new(rand::gen_range(0.0, screen_width), -50.0), // Top
-            1 => Vec2::new(rand::gen_range(0.0, screen_width), screen_height + 50.0), // Bottom
-            2 => Vec2::new(-50.0, rand::gen_range(0.0, screen_height)), // Left
-            _ => Vec2::new(screen_width + 50.0, rand::gen_range(0.0, screen_height)), // Right
-        };
+        // Update enemies - they chase the nearest player!
+        let targets: Vec<Option<(Vec2, Vec2)>> = self.enemies.values().map(|e| self.nearest_player_motion(e.pos)).collect();
+        let drift: Vec<Vec2> = self.enemies.values().map(|e| field_force(&self.config, self.view_size, e.pos) * ENEMY_FIELD_DRIFT).collect();
+        let mut thefts = Vec::new();
+        let mimic = self.mimic_motion();
+        for (((enemy, push), target), drift) in self.enemies.values_mut().zip(separation).zip(targets).zip(drift) {
+            let (target, target_velocity) = target.unwrap_or((enemy.pos, Vec2::ZERO));
+            match enemy.steer(target, target_velocity, mimic, dt, &self.config) {
+                Some(EnemyAction::DropMine { pos }) => {
+                    self.mines.insert(Mine {
+                        pos,
+                        arm_timer: self.config.mine_arm_delay,
+                        life: Lifetime::new(self.config.mine_lifetime),
+                    });
+                }
+                Some(EnemyAction::Fire { muzzle, pattern, aim }) => {
+                    fire_pattern(&mut self.enemy_bullets, muzzle, pattern, aim, self.time, self.config.enemy_bullet_look);
+                }
+                Some(EnemyAction::Escape) => thefts.push((enemy.kind, enemy.pos)),
+                None => {}
+            }
         
-        self.enemies.push(Enemy {
-            pos,
-            velocity: Vec2::new(0.0, 0.0),
-            rotation: 0.0,
-            health: 2,
-            size: 25.0,
+            enemy.pos += (enemy.velocity * enemy_speed + enemy.knockback + push + drift) * dt;
+            enemy.knockback *= (-KNOCKBACK_DECAY * dt).exp();
+        
+            // Enemies may roam just off-screen (where they spawn);
+            // escaping Scavengers and Carriers are the only things that
+            // leave for good
+            if !enemy.escaping(&self.config) {
+                let margin = edge_buffer(&self.config, enemy.size);
+                let before = enemy.pos;
+                apply_boundary(self.config.edges.enemies, EdgeResponse::Bounce, self.view_size, &mut enemy.pos, &mut enemy.velocity, margin);
+                // A wall also soaks up whatever shove was driving it out
+                if self.config.edges.enemies == EdgePolicy::Clamp {
+                    let held = enemy.pos - before;
+                    for axis in 0..2 {
+                        if held[axis] * enemy.knockback[axis] < 0.0 {
+                            enemy.knockback[axis] = 0.0;
+                        }
+                    }
+                }
+            }
+            let view = Rect::new(-enemy.size, -enemy.size, self.view_size.x + 2.0 * enemy.size, self.view_size.y + 2.0 * enemy.size);
+            enemy.offscreen_time = if view.contains(enemy.pos) { 0.0 } else { enemy.offscreen_time + dt };
+        }
+        for (kind, pos) in thefts {
+            if kind == EnemyKind::Scavenger {
+                self.scavenger_theft(pos);
+            } else {
+                // A Carrier takes its power-up with it
+                self.floating_texts.push(FloatingText {
+                    pos,
+                    text: "GOT AWAY".to_owned(),
+                    life: Lifetime::new(1.5),
+                    color: Color::from_rgba(255, 120, 90, 255),
+                });
+            }
+        }
+        let margin = SCAVENGER_GONE_MARGIN;
+        let arena = Rect::new(-margin, -margin, self.view_size.x + 2.0 * margin, self.view_size.y + 2.0 * margin);
+        // Strays that never came back (walls off or a destroy edge) are
+        // culled too, so they can't pile up unseen; bosses always stay
+        let config = &self.config;
+        self.enemies.retain(|e| {
+            let stray = e.offscreen_time > config.enemy_cull_time && e.kind != EnemyKind::Boss;
+            (!e.escaping(config) || arena.contains(e.pos)) && !stray
         });
     }
-
+    
+    // Enemy trails fade twice as fast as the player's; none at all on Low
+    fn update_enemy_trail(&mut self, dt: f32, frozen: bool) {
+        let enemy_trail_cap = self.config.quality.enemy_trail_cap();
+        if enemy_trail_cap > 0 && !frozen && !self.reduced_motion() {
+            for enemy in self.enemies.values().filter(|e| e.leaves_trail()) {
+                self.enemy_trail.push(TrailSegment {
+                    pos: enemy.pos,
+                    life: 1.0,
+                    size: enemy.size,
+                    color: Color::from_rgba(255, 70, 60, 255),
+                });
+            }
+        }
+        self.enemy_trail.retain_mut(|seg| {
+            seg.life -= dt * 4.0;
+            seg.life > 0.0
+        });
+        if self.enemy_trail.len() > enemy_trail_cap {
+            self.enemy_trail.drain(0..self.enemy_trail.len() - enemy_trail_cap);
+        }
+    }
+    
+    // Supports shield everyone else in range. Recomputed every frame,
+    // so shields drop as soon as the last Support covering them dies.
+    fn raise_shields(&mut self) {
+        let supports: Vec<Vec2> = self
+            .enemies
+            .values()
+            .filter(|e| e.kind == EnemyKind::Support && e.health > 0)
+            .map(|e| e.pos)
+            .collect();
+        let shield_radius = self.config.support_shield_radius;
+        for enemy in self.enemies.values_mut() {
+            enemy.shielded = enemy.kind != EnemyKind::Support
+                && supports.iter().any(|&support| (enemy.pos - support).length() < shield_radius);
+        }
+    }
+    
+    // Player shots and special rings damage the enemies they reach, and
+    // the destroyed ones are scored
+    fn hit_enemies(&mut self, dt: f32) {
+        let mut enemies_to_remove = Vec::new();
+        let mut boss_hit = false;
+        for (id, enemy) in self.enemies.iter_mut() {
+            for bullet in &mut self.bullets {
+                if bullet.life.expired() || enemy.health <= 0 || enemy.blinking() || bullet.hit_ids.contains(&id) {
+                    continue;
+                }
+                // Swept test so fast shots can't tunnel through small enemies
+                let dist = segment_distance(enemy.pos, bullet.prev_pos, bullet.pos);
+                if dist < enemy.size + BULLET_HIT_RADIUS && enemy.shielded {
+                    bullet.life.end(); // Soaked up by the shield
+                } else if dist < enemy.size + BULLET_HIT_RADIUS {
+                    enemy.take_hit(1, &self.config);
+                    boss_hit |= enemy.kind == EnemyKind::Boss;
+                    
+                    // Accuracy counts each of the pilot's own shots once
+                    if !bullet.reflected && bullet.hit_ids.is_empty() {
+                        self.stats.shots_hit += 1;
+                    }
+                    
+                    // Piercing bullets keep going until their pierce runs out
+                    if bullet.pierce > 0 {
+                        bullet.pierce -= 1;
+                        bullet.hit_ids.push(id);
+                    } else {
+                        bullet.life.end(); // Remove bullet
+                    }
+                    
+                    if enemy.health <= 0 {
+                        enemies_to_remove.push(id);
+                    } else if self.config.knockback_impulse > 0.0 {
+                        // Shove along the shot; big enemies barely flinch
+                        let impulse = self.config.knockback_impulse * KNOCKBACK_REFERENCE_SIZE / enemy.size;
+                        enemy.knock(bullet.velocity.normalize_or_zero() * impulse);
+                    }
+                }
+            }
+        }
+        
+        // Special rings expand, damaging enemies and erasing enemy bullets they sweep over
+        for ring in &mut self.special_rings {
+            ring.radius += SPECIAL_RING_SPEED * dt;
+            for (id, enemy) in self.enemies.iter_mut() {
+                if enemy.health <= 0 || ring.hit_ids.contains(&id) {
+                    continue;
+                }
+                if (enemy.pos - ring.pos).length() < ring.radius + enemy.size {
+                    ring.hit_ids.push(id);
+                    enemy.take_hit(self.config.special_ring_damage, &self.config);
+                    if enemy.health <= 0 {
+                        enemies_to_remove.push(id);
+                    }
+                }
+            }
+            self.enemy_bullets.retain(|b| (b.pos - ring.pos).length() > ring.radius);
+        }
+        self.special_rings.retain(|ring| ring.radius < ring.max_radius);
+        
+        if boss_hit {
+            self.request_hitstop(self.config.hitstop.boss_hit);
+        }
+        if enemies_to_remove.len() >= 2 {
+            self.request_hitstop(self.config.hitstop.multi_kill);
+        }
+        
+        // Remove dead enemies and award their score
+        for id in enemies_to_remove {
+            if let Some(enemy) = self.enemies.remove(id) {
+                self.on_enemy_killed(&enemy);
+            }
+        }
+    }
+    
+    // Power-ups get collected by any pilot (or expire on the floor, see
+    // `reap_transients`)
+    fn collect_power_ups(&mut self) {
+        for controller in self.players.iter_mut().filter(|c| c.alive()) {
+            let player_pos = to_mac_vec2(controller.player.position);
+            self.power_ups.retain(|power_up| {
+                if (power_up.pos - player_pos).length() > POWER_UP_RADIUS {
+                    return true;
+                }
+                self.events.push(GameEvent::PowerUpCollected { kind: power_up.kind, pos: power_up.pos });
+                match power_up.kind {
+                    PowerUpKind::Pierce => controller.pierce_timer = PIERCE_DURATION,
+                    PowerUpKind::Freeze => self.freeze_timer = self.config.freeze_duration,
+                    PowerUpKind::Weapon => {
+                        self.weapon_level = (self.weapon_level + 1).min(self.config.weapon_levels.len() - 1);
+                    }
+                    PowerUpKind::Mortar => controller.mortar_timer = MORTAR_DURATION,
+                }
+                false
+            });
+        }
+    }
+    
+    // Parry shields bounce enemy bullets back as player-owned shots
+    fn parry_bullets(&mut self) {
+        for controller in self.players.iter_mut().filter(|c| c.alive() && c.parry_timer > 0.0) {
+            let player_pos = to_mac_vec2(controller.player.position);
+            let mut k = 0;
+            while k < self.enemy_bullets.len() {
+                let offset = self.enemy_bullets[k].pos - player_pos;
+                if offset.length() < PARRY_RADIUS {
+                    let mut bullet = self.enemy_bullets.swap_remove(k);
+                    let normal = offset.normalize_or_zero();
+                    bullet.velocity = reflect(bullet.velocity, normal) * 1.5;
+                    bullet.life = Lifetime::new(2.0);
+                    bullet.reflected = true;
+                    bullet.look = self.config.reflected_look;
+                    bullet.prev_pos = bullet.pos;
+                    bullet.pierce = 1; // Parried shots punch through
+                    self.bullets.push(bullet);
+                    controller.parry_flash = 0.2;
+                    self.floating_texts.push(FloatingText {
+                        pos: player_pos - Vec2::new(0.0, 50.0),
+                        text: "PARRY!".to_owned(),
+                        life: Lifetime::new(0.6),
+                        color: Color::from_rgba(255, 230, 120, 255),
+                    });
+                } else {
+                    k += 1;
+                }
+            }
+        }
+    }
+    
+    // Enemy bullets that skim past a pilot's hitbox pay a small bonus
+    fn graze_bullets(&mut self) {
+        for controller in self.players.iter().filter(|c| c.alive() && c.invuln_timer <= 0.0) {
+            let player = &controller.player;
+            for bullet in self.enemy_bullets.iter_mut().filter(|b| !b.grazed) {
+                let pos = from_mac_vec2(bullet.pos);
+                if player.collides_with(pos, ENEMY_BULLET_RADIUS + self.config.graze_radius)
+                    && !player.collides_with(pos, ENEMY_BULLET_RADIUS)
+                {
+                    bullet.grazed = true;
+                    self.score += self.config.graze_score;
+                    self.stats.graze_points += self.config.graze_score;
+                    self.special_meter = (self.special_meter + self.config.special_per_graze).min(1.0);
+                    self.explosions.push(Explosion::new(ExplosionKind::Spark, bullet.pos, 14.0, &mut self.fx_rng));
+                    self.floating_texts.push(FloatingText {
+                        pos: bullet.pos,
+                        text: "GRAZE".to_owned(),
+                        life: Lifetime::new(0.5),
+                        color: Color::from_rgba(180, 230, 255, 255),
+                    });
+                }
+            }
+        }
+    }
+    
+    // Enemy bullets that reach a pilot cost a heart
+    fn shoot_pilots(&mut self) {
+        if self.safe_time <= 0.0 && !self.invincible() {
+            for p in 0..self.players.len() {
+                let controller = &self.players[p];
+                if !controller.vulnerable() {
+                    continue;
+                }
+                let player = &controller.player;
+                if let Some(k) = self
+                    .enemy_bullets
+                    .iter()
+                    .position(|b| player.collides_with(from_mac_vec2(b.pos), ENEMY_BULLET_RADIUS))
+                {
+                    self.enemy_bullets.swap_remove(k);
+                    self.damage_player(p, 1);
+                }
+            }
+        }
+    }
+    
+    // Dashing pilots ram what they pass through, up to a few enemies per dash
+    fn ram_enemies(&mut self) {
+        let mut rammed = Vec::new();
+        for controller in self.players.iter_mut().filter(|c| c.alive() && c.dash_timer > 0.0) {
+            for (id, enemy) in self.enemies.iter_mut() {
+                if controller.dash_hit_ids.len() >= self.config.dash_max_hits {
+                    break;
+                }
+                if enemy.health <= 0
+                    || controller.dash_hit_ids.contains(&id)
+                    || !controller.player.collides_with(from_mac_vec2(enemy.pos), enemy.size)
+                {
+                    continue;
+                }
+                controller.dash_hit_ids.push(id);
+                enemy.take_hit(self.config.dash_damage, &self.config);
+                self.explosions.push(Explosion::new(ExplosionKind::Spark, enemy.pos, enemy.size, &mut self.fx_rng));
+                if enemy.health <= 0 {
+                    rammed.push(id);
+                }
+            }
+        }
+        for id in rammed {
+            if let Some(enemy) = self.enemies.remove(id) {
+                self.on_enemy_killed(&enemy);
+            }
+        }
+    }
+    
+    // Pilots that fly into an enemy are bumped or hit, as configured
+    fn collide_pilots(&mut self) {
+        if self.safe_time <= 0.0 && !self.invincible() {
+            for p in 0..self.players.len() {
+                let controller = &self.players[p];
+                if !controller.vulnerable() {
+                    continue;
+                }
+                
+                let player = &controller.player;
+                let collision = self
+                    .enemies
+                    .iter()
+                    .find(|(_, enemy)| !enemy.blinking() && player.collides_with(from_mac_vec2(enemy.pos), enemy.size))
+                    .map(|(id, _)| id);
+                
+                match (collision, self.config.contact) {
+                    (Some(id), ContactResponse::Bump) => self.bump(p, id),
+                    (Some(id), ContactResponse::Lethal) => self.contact_hit(p, id),
+                    (None, _) => {}
+                }
+            }
+        }
+    }
+    
+    // Mines arm, and blow when a pilot touches a live one
+    fn trip_mines(&mut self, dt: f32) {
+        for mine in self.mines.values_mut() {
+            mine.arm_timer -= dt;
+        }
+        if self.safe_time <= 0.0 && !self.invincible() {
+            for p in 0..self.players.len() {
+                let controller = &self.players[p];
+                if !controller.vulnerable() {
+                    continue;
+                }
+                let player = &controller.player;
+                let touched = self
+                    .mines
+                    .iter()
+                    .find(|(_, m)| m.arm_timer <= 0.0 && player.collides_with(from_mac_vec2(m.pos), MINE_RADIUS))
+                    .map(|(id, _)| id);
+                if let Some(mine) = touched.and_then(|id| self.mines.remove(id)) {
+                    self.detonate_mine(mine.pos);
+                    self.damage_player(p, self.config.mine_damage);
+                }
+            }
+        }
+    }
+    
+    // Update particles (breathing world); each layer drifts and scrolls
+    // against the lead pilot at its own depth (held still for reduced motion)
+    fn update_backdrop(&mut self, dt: f32) {
+        let still = self.reduced_motion();
+        let scroll = self
+            .players
+            .iter()
+            .find(|c| c.alive())
+            .map_or(Vec2::ZERO, |lead| -to_mac_vec2(lead.player.velocity) * STAR_PARALLAX);
+        let view = self.view_size;
+        for particle in &mut self.particles {
+            if !still {
+                particle.pos += (particle.velocity + scroll) * particle.depth * dt;
+            }
+            
+            // Wrap particles
+            if particle.pos.x < 0.0 { particle.pos.x = view.x; }
+            if particle.pos.x > view.x { particle.pos.x = 0.0; }
+            if particle.pos.y < 0.0 { particle.pos.y = view.y; }
+            if particle.pos.y > view.y { particle.pos.y = 0.0; }
+            
+            // Gentle pulse
+            let pulse = 0.2 + (self.time * 2.0 + particle.pos.x * 0.01).sin() * 0.1;
+            particle.alpha = pulse * Particle::brightness(particle.depth);
+        }
+        
+        // Grid drifts against the lead pilot's motion for a sense of speed
+        if let Some(lead) = self.players.iter().find(|c| c.alive()).filter(|_| !still) {
+            let spacing = self.config.grid_spacing.max(8.0);
+            let offset = self.grid_offset - to_mac_vec2(lead.player.velocity) * self.config.grid_parallax * dt;
+            self.grid_offset = Vec2::new(offset.x.rem_euclid(spacing), offset.y.rem_euclid(spacing));
+        }
+    }
+    
+    // Follow the window to `view_size`, the title screen's demo run too
+    fn resize_to(&mut self, view_size: Vec2) {
+        if view_size != self.view_size {
+            self.on_resize(self.view_size, view_size);
+            self.view_size = view_size;
+        }
+        if let Some(demo) = &mut self.attract {
+            demo.resize_to(view_size);
+        }
+    }
+    
+    // Keep the world proportionally in place when the window changes size
+    fn on_resize(&mut self, old: Vec2, new: Vec2) {
+        if old.x <= 0.0 || old.y <= 0.0 {
+            return;
+        }
+        let scale = new / old;
+        for particle in &mut self.particles {
+            particle.pos *= scale;
+        }
+        
+        // Last step's positions too, or interpolation would draw a frame
+        // stretched from where things were in the old view
+        for controller in &mut self.players {
+            controller.player.position = from_mac_vec2(to_mac_vec2(controller.player.position) * scale);
+            controller.prev_position = from_mac_vec2(to_mac_vec2(controller.prev_position) * scale);
+        }
+        for enemy in self.enemies.values_mut() {
+            enemy.pos *= scale;
+            enemy.prev_pos *= scale;
+        }
+        for bullet in self.bullets.iter_mut().chain(&mut self.enemy_bullets) {
+            bullet.pos *= scale;
+            bullet.prev_pos *= scale;
+        }
+        for power_up in self.power_ups.values_mut() {
+            power_up.pos *= scale;
+        }
+        for mine in self.mines.values_mut() {
+            mine.pos *= scale;
+        }
+        for telegraph in &mut self.telegraphs {
+            telegraph.pos *= scale;
+        }
+        for field in &mut self.slow_fields {
+            field.pos *= scale;
+        }
+        for explosion in &mut self.explosions {
+            explosion.pos *= scale;
+        }
+        for text in &mut self.floating_texts {
+            text.pos *= scale;
+        }
+        for ring in &mut self.special_rings {
+            ring.pos *= scale;
+        }
+        // Stale streaks would jump across the resized view
+        self.trail.clear();
+        self.enemy_trail.clear();
+    }
+    
+    // Soft caps keep pathological fights from tanking the frame: the
+    // oldest bullets and effects go first (enemy spawns are held back
+    // at the source instead, see `add_enemy`)
+    fn shed_overflow(&mut self) {
+        fn drop_oldest<T>(items: &mut Vec<T>, cap: usize) {
+            if items.len() > cap {
+                items.drain(..items.len() - cap);
+            }
+        }
+        let caps = &self.config.caps;
+        drop_oldest(&mut self.bullets, caps.bullets);
+        drop_oldest(&mut self.enemy_bullets, caps.enemy_bullets);
+        drop_oldest(&mut self.explosions, caps.explosions);
+        drop_oldest(&mut self.floating_texts, caps.floating_texts);
+    }
+    
+    // Enemy `id` slams into pilot `p`: the enemy is destroyed and the pilot loses a heart
+    fn contact_hit(&mut self, p: usize, id: Id) {
+        let Some(enemy) = self.enemies.remove(id) else {
+            return;
+        };
+        self.explosions.push(Explosion::new(ExplosionKind::Standard, enemy.pos, enemy.size * 2.0, &mut self.fx_rng));
+        self.damage_player(p, 1);
+    }
+    
+    // Bump contact: whoever is closing in faster along the line between the
+    // two decides it. A fast enemy still hurts the pilot, a fast pilot rams
+    // the enemy, and anything slower just bounces both apart
+    fn bump(&mut self, p: usize, id: Id) {
+        let player = &mut self.players[p].player;
+        let enemy = &mut self.enemies[id];
+        let player_pos = to_mac_vec2(player.position);
+        let player_vel = to_mac_vec2(player.velocity);
+        let offset = enemy.pos - player_pos;
+        let normal = offset.try_normalize().unwrap_or(Vec2::X);
+        let ramming = player_vel.dot(normal);
+        let charging = -enemy.velocity.dot(normal);
+        
+        if charging >= self.config.ram_speed && charging >= ramming {
+            self.contact_hit(p, id);
+            return;
+        }
+        
+        // Equal-mass elastic bounce: trade the closing velocity along the normal
+        let closing = (player_vel - enemy.velocity).dot(normal);
+        if closing > 0.0 {
+            player.velocity = from_mac_vec2(player_vel - normal * closing);
+            enemy.knock(normal * closing);
+        }
+        
+        // Push the enemy clear so the pair doesn't collide again next step
+        let overlap = player.hitbox_radius + enemy.size - offset.length();
+        enemy.pos += normal * overlap.max(0.0);
+        
+        if ramming >= self.config.ram_speed {
+            enemy.take_hit(self.config.ram_damage, &self.config);
+            if enemy.health <= 0 {
+                let enemy = self.enemies.remove(id).expect("bumped enemy is live");
+                self.on_enemy_killed(&enemy);
+            }
+        }
+    }
+    
+    // Scatter `count` slow fields across the arena, clear of the pilots
+    fn lay_slow_fields(&mut self, count: u32) {
+        let radius = self.config.slow_field_radius;
+        let pilots: Vec<Vec2> = self.players.iter().filter(|c| c.alive()).map(|c| to_mac_vec2(c.player.position)).collect();
+        for _ in 0..count {
+            let mut pos = Vec2::ZERO;
+            for _ in 0..SLOW_FIELD_PLACE_TRIES {
+                pos = Vec2::new(
+                    self.rng.range(radius, (self.view_size.x - radius).max(radius)),
+                    self.rng.range(radius, (self.view_size.y - radius).max(radius)),
+                );
+                if pilots.iter().all(|&p| (p - pos).length() > radius + SLOW_FIELD_CLEARANCE) {
+                    break;
+                }
+            }
+            self.slow_fields.push(SlowField { pos, radius });
+        }
+    }
+    
+    // Anything that started this step inside a slow field only covers part
+    // of the ground it otherwise would. Jumps (blinks, wraps) are left alone.
+    fn apply_slow_fields(&mut self) {
+        if self.slow_fields.is_empty() {
+            return;
+        }
+        let factor = self.config.slow_field_factor.clamp(0.0, 1.0);
+        let fields = &self.slow_fields;
+        let slowed = |prev: Vec2, pos: Vec2| {
+            let step = pos - prev;
+            let inside = fields.iter().any(|f| (prev - f.pos).length() < f.radius);
+            if !inside || step.length() > INTERPOLATION_MAX_JUMP {
+                return pos;
+            }
+            prev + step * factor
+        };
+        // The sniper kit holds its line through the fields
+        let immune = self.config.loadout.slow_field_immune();
+        for controller in self.players.iter_mut().filter(|_| !immune) {
+            let pos = slowed(to_mac_vec2(controller.prev_position), to_mac_vec2(controller.player.position));
+            controller.player.position = from_mac_vec2(pos);
+        }
+        for enemy in self.enemies.values_mut() {
+            enemy.pos = slowed(enemy.prev_pos, enemy.pos);
+        }
+    }
+    
+    // Lose a heart, then a short grace period
+    fn damage_player(&mut self, index: usize, amount: i32) {
+        let controller = &mut self.players[index];
+        controller.health -= amount;
+        controller.invuln_timer = HIT_INVULN_TIME;
+        self.stats.hits_taken += 1;
+        self.hitless = false;
+        self.events.push(GameEvent::PlayerHit {
+            pos: to_mac_vec2(controller.player.position),
+            fatal: !controller.alive(),
+        });
+        
+        // Getting hit breaks the chain back to 1x
+        if self.combo > 1 {
+            self.announcement = Some(Announcement {
+                text: "COMBO LOST".to_owned(),
+                life: 1.0,
+                tier: 0,
+            });
+        }
+        self.combo = 0;
+        self.combo_timer = 0.0;
+        if self.config.lose_level_on_hit {
+            self.weapon_level = self.weapon_level.saturating_sub(1);
+        }
+        
+        if !controller.alive() {
+            let pos = to_mac_vec2(controller.player.position);
+            self.overdrive_timer = 0.0;
+            
+            // A spare life puts them straight back in, on a cleared screen
+            if self.extra_lives_allowed() && self.extra_lives > 0 {
+                self.extra_lives -= 1;
+                self.respawn(index);
+                return;
+            }
+            
+            // Last pilot down: play the last stand, then GAME OVER
+            if self.death.is_none() && self.players.iter().all(|c| !c.alive()) {
+                // Lingers longer than a normal large blast
+                let mut last_stand = Explosion::new(ExplosionKind::Large, pos, 140.0, &mut self.fx_rng);
+                last_stand.life = Lifetime::new(1.5);
+                self.explosions.push(last_stand);
+                self.death = Some(DeathSequence { elapsed: 0.0, pos });
+            }
+        }
+    }
+    
+    // Grant a spare life for each score milestone passed
+    fn award_extra_lives(&mut self) {
+        if !self.extra_lives_allowed() || self.sandbox() {
+            return;
+        }
+        while let Some(&threshold) = self.config.extra_life_scores.get(self.next_life_score) {
+            if self.score < threshold {
+                break;
+            }
+            self.next_life_score += 1;
+            self.extra_lives += 1;
+            self.announcement = Some(Announcement {
+                text: "EXTRA LIFE!".to_owned(),
+                life: 1.2,
+                tier: 1,
+            });
+        }
+    }
+    
+    // Spare lives are off in hardcore whatever the setting says
+    fn extra_lives_allowed(&self) -> bool {
+        self.config.extra_lives && self.config.mode != GameMode::Hardcore
+    }
+    
+    // Spend a life: full hearts where the pilot fell, a long grace period,
+    // and every enemy shot, mine and non-boss enemy wiped away (unscored)
+    fn respawn(&mut self, index: usize) {
+        let controller = &mut self.players[index];
+        controller.health = max_health(self.config.mode);
+        controller.invuln_timer = RESPAWN_INVULN_TIME;
+        controller.player.reset_velocity();
+        
+        self.enemy_bullets.clear();
+        self.mines.clear();
+        for enemy in self.enemies.values().filter(|e| e.kind != EnemyKind::Boss) {
+            self.explosions.push(Explosion::new(ExplosionKind::Standard, enemy.pos, enemy.size * 2.0, &mut self.fx_rng));
+        }
+        self.enemies.retain(|e| e.kind == EnemyKind::Boss);
+        self.announcement = Some(Announcement {
+            text: format!("LIVES LEFT: {}", self.extra_lives),
+            life: 1.2,
+            tier: 0,
+        });
+    }
+    
+    fn shoot(&mut self, player_pos: Vec2, rotation: f32, pierce: u32, mortar: bool) {
+        // Bullet starts from front of ship
+        let bullet_start = Vec2::new(
+            player_pos.x + rotation.cos() * 45.0,
+            player_pos.y + rotation.sin() * 45.0,
+        );
+        
+        // Pierce rounds trade range for speed; mortar shells are lobbed one at a time
+        let weapon_is_blaster = !mortar && pierce == 0;
+        let weapon = if mortar {
+            self.config.mortar_shot
+        } else if pierce > 0 {
+            self.config.pierce_shot
+        } else {
+            self.config.blaster
+        };
+        let accel = if mortar { Vec2::new(0.0, self.config.mortar_gravity) } else { Vec2::ZERO };
+        
+        // The weapon level fans out extra pellets and adds pierce; the
+        // loadout reshapes everything but the lobbed mortar
+        let level = self.config.weapon_levels[self.weapon_level];
+        let loadout = self.config.loadout;
+        let (speed_scale, range_scale) = if mortar { (1.0, 1.0) } else { loadout.shot_scale() };
+        let pellets = if mortar { 1 } else { level.pellets };
+        let look = if weapon_is_blaster { loadout.bullet_look().unwrap_or(weapon.look) } else { weapon.look };
+        for i in 0..pellets {
+            let offset = (i as f32 - (pellets - 1) as f32 / 2.0) * PELLET_SPREAD * loadout.fan_scale();
+            // Bloom comes off the seeded RNG so replays fire the same shots
+            let kick = if weapon.spread > 0.0 {
+                let cone = weapon.spread.to_radians();
+                self.rng.range(-cone, cone)
+            } else {
+                0.0
+            };
+            let angle = rotation + offset + kick;
+            let bullet_velocity = Vec2::new(angle.cos(), angle.sin()) * weapon.speed * speed_scale;
+            
+            self.stats.shots_fired += 1;
+            self.bullets.push(Bullet {
+                pos: bullet_start,
+                prev_pos: bullet_start,
+                velocity: bullet_velocity,
+                life: Lifetime::new(weapon.life * range_scale),
+                reflected: false,
+                pierce: if mortar { 0 } else { pierce + level.pierce + loadout.extra_pierce() },
+                hit_ids: Vec::new(),
+                grazed: false,
+                accel,
+                shell: mortar,
+                look,
+            });
+        }
+    }
+    
+    /// 🎨 Switch quality at runtime, resizing the existing particle field
+    pub fn set_quality(&mut self, quality: Quality) {
+        self.config.quality = quality;
+        let target = quality.particle_count();
+        if self.particles.len() > target {
+            self.particles.truncate(target);
+        } else {
+            let (w, h) = (self.view_size.x, self.view_size.y);
+            let rng = &mut self.fx_rng;
+            self.particles.extend((self.particles.len()..target).map(|_| Particle::random(rng, w, h)));
+        }
+    }
+    
+    // Sum of inverse-distance repulsion from overlapping neighbours, clamped
+    // so it nudges the formation apart without overpowering the chase
+    fn separation_force(&self, id: Id) -> Vec2 {
+        let enemy = &self.enemies[id];
+        let mut push = Vec2::ZERO;
+        
+        for (other_id, other) in self.enemies.iter() {
+            if other_id == id {
+                continue;
+            }
+            let offset = enemy.pos - other.pos;
+            let dist = offset.length();
+            let radius = (enemy.size + other.size) * 1.2;
+            if dist > 0.0 && dist < radius {
+                push += offset / dist * (radius / dist - 1.0);
+            }
+        }
+        
+        (push * SEPARATION_STRENGTH).clamp_length_max(enemy.speed * SEPARATION_MAX_FRACTION)
+    }
+    
+    // Position of the closest living pilot, if any
+    fn nearest_player_pos(&self, from: Vec2) -> Option<Vec2> {
+        self.players
+            .iter()
+            .filter(|c| c.alive())
+            .map(|c| to_mac_vec2(c.player.position))
+            .min_by(|a, b| (*a - from).length().total_cmp(&(*b - from).length()))
+    }
+    
+    fn nearest_enemy_pos(&self, from: Vec2, range: f32) -> Option<Vec2> {
+        self.enemies
+            .values()
+            .map(|e| e.pos)
+            .filter(|pos| (*pos - from).length() < range)
+            .min_by(|a, b| (*a - from).length().total_cmp(&(*b - from).length()))
+    }
+    
+    // Position and velocity of the nearest living pilot
+    fn nearest_player_motion(&self, from: Vec2) -> Option<(Vec2, Vec2)> {
+        self.players
+            .iter()
+            .filter(|c| c.alive())
+            .map(|c| (to_mac_vec2(c.player.position), to_mac_vec2(c.player.velocity)))
+            .min_by(|(a, _), (b, _)| (*a - from).length().total_cmp(&(*b - from).length()))
+    }
+    
+    // Threat score: each enemy within range adds up to 1.0 the closer it is
+    fn compute_threat(&self) -> f32 {
+        self.enemies
+            .values()
+            .filter_map(|enemy| {
+                let player_pos = self.nearest_player_pos(enemy.pos)?;
+                let dist = (enemy.pos - player_pos).length();
+                Some((1.0 - dist / THREAT_RADIUS).max(0.0))
+            })
+            .sum()
+    }
+    
+    // Score multiplier from the current kill combo (1x - 5x)
+    fn combo_multiplier(&self) -> i32 {
+        1 + (self.combo / 5).min(4)
+    }
+    
+    // How far into overdrive we are: 1 at full power, easing to 0 over the
+    // wind-down at the end (and 0 when inactive)
+    fn overdrive_power(&self) -> f32 {
+        (self.overdrive_timer / OVERDRIVE_WIND_DOWN).min(1.0)
+    }
+    
+    // `multiplier` scaled by the current overdrive power
+    fn overdrive_boost(&self, multiplier: f32) -> f32 {
+        1.0 + (multiplier - 1.0) * self.overdrive_power()
+    }
+    
+    // Player shots that crossed an enemy shot this step destroy it and are
+    // spent. Checked in relative motion so fast head-on pairs can't tunnel.
+    fn cancel_bullets(&mut self) {
+        for bullet in &mut self.bullets {
+            let hit = self.enemy_bullets.iter_mut().find(|shot| {
+                let (before, after) = (bullet.prev_pos - shot.prev_pos, bullet.pos - shot.pos);
+                !shot.life.expired() && segment_distance(Vec2::ZERO, before, after) < BULLET_CANCEL_RADIUS
+            });
+            if let Some(shot) = hit {
+                shot.life.end();
+                bullet.life.end();
+                let spark = (bullet.pos + shot.pos) / 2.0;
+                self.explosions.push(Explosion::new(ExplosionKind::Spark, spark, 10.0, &mut self.fx_rng));
+            }
+        }
+        self.bullets.retain(|b| !b.life.expired());
+        self.enemy_bullets.retain(|b| !b.life.expired());
+    }
+    
+    // The lead pilot's velocity and heading `mimic_delay` seconds ago, if
+    // the recording reaches back that far without a jump in it
+    fn mimic_motion(&self) -> Option<(Vec2, f32)> {
+        let then = self.time - self.config.mimic_delay;
+        let now = self.mimic_track.sample_at(then)?;
+        let before = self.mimic_track.sample_at(now.time - MIMIC_SAMPLE_INTERVAL / 2.0)?;
+        let elapsed = now.time - before.time;
+        if elapsed <= 0.0 {
+            return None;
+        }
+        let velocity = (now.pos - before.pos) / elapsed;
+        (velocity.length() < MIMIC_MAX_SPEED).then_some((velocity, now.rotation))
+    }
+    
+    // A Scavenger made off: it takes score and the nearest power-up with it
+    fn scavenger_theft(&mut self, pos: Vec2) {
+        let stolen = self.config.scavenger_steal.min(self.score).max(0);
+        self.score -= stolen;
+        self.stats.scavenger_points -= stolen;
+        let nearest = self
+            .power_ups
+            .iter()
+            .min_by(|(_, a), (_, b)| (a.pos - pos).length().total_cmp(&(b.pos - pos).length()))
+            .map(|(id, _)| id);
+        if let Some(id) = nearest {
+            self.power_ups.remove(id);
+        }
+        self.floating_texts.push(FloatingText {
+            pos,
+            text: format!("STOLEN -{}", stolen),
+            life: Lifetime::new(1.5),
+            color: Color::from_rgba(255, 120, 90, 255),
+        });
+    }
+    
+    // Award score, advance the combo and spawn effects for a destroyed enemy
+    fn on_enemy_killed(&mut self, enemy: &Enemy) {
+        let mut awarded = enemy.score_value * self.combo_multiplier();
+        self.stats.record_kill(enemy.kind, enemy.score_value, awarded);
+        if self.hitless {
+            let bonus = (awarded as f32 * self.config.hitless_bonus) as i32;
+            awarded += bonus;
+            self.stats.hitless_points += bonus;
+        }
+        if enemy.kind == EnemyKind::Scavenger {
+            // Caught before it got away
+            awarded += self.config.scavenger_bonus;
+            self.stats.scavenger_points += self.config.scavenger_bonus;
+        }
+        self.score += awarded;
+        self.kills += 1;
+        self.stall_timer = 0.0;
+        
+        // Chains reward raw tempo: a short window, no multiplier
+        self.chain_count += 1;
+        self.chain_timer = self.config.chain_window;
+        if self.chain_count > 1 {
+            let links = (self.chain_count - 1) as f32;
+            let bonus = (self.config.chain_bonus as f32 * links.powf(self.config.chain_growth)) as i32;
+            self.score += bonus;
+            self.stats.chain_points += bonus;
+            self.floating_texts.push(FloatingText {
+                pos: enemy.pos - Vec2::new(0.0, 24.0),
+                text: format!("CHAIN x{}", self.chain_count),
+                life: Lifetime::new(0.8),
+                color: Color::from_rgba(255, 170, 90, 255),
+            });
+        }
+        self.charge_special(self.config.special_per_kill * self.combo_multiplier() as f32);
+        self.combo += 1;
+        self.combo_timer = COMBO_WINDOW;
+        
+        // Every `combo` kills of one streak kicks in overdrive
+        let threshold = self.config.overdrive.combo;
+        if threshold > 0 && self.combo % threshold == 0 && self.overdrive_timer <= 0.0 {
+            self.overdrive_timer = self.config.overdrive.duration;
+            self.announcement = Some(Announcement {
+                text: "OVERDRIVE!".to_owned(),
+                life: 1.2,
+                tier: 2,
+            });
+        }
+        
+        // Announce when the combo crosses a streak threshold
+        if let Some(tier) = self.config.streak_tiers.iter().position(|(count, _)| *count == self.combo) {
+            self.announcement = Some(Announcement {
+                text: self.config.streak_tiers[tier].1.clone(),
+                life: 1.2,
+                tier,
+            });
+        }
+        
+        self.events.push(GameEvent::EnemyKilled {
+            kind: enemy.kind,
+            pos: enemy.pos,
+            size: enemy.size,
+            points: awarded,
+        });
+        
+        // Splitters burst into a ring of tiny fragments, the blast catching
+        // anything nearby (but not the fragments themselves)
+        if enemy.kind == EnemyKind::Splitter {
+            self.blasts.push(Blast {
+                pos: enemy.pos,
+                radius: SPLITTER_BURST_RADIUS,
+                damage: SPLITTER_BURST_DAMAGE,
+                spare_from: self.enemies_spawned,
+            });
+            for i in 0..3 {
+                let angle = enemy.rotation + i as f32 * std::f32::consts::TAU / 3.0;
+                let offset = Vec2::new(angle.cos(), angle.sin());
+                let mut child = Enemy::new(EnemyKind::SplitterChild, enemy.pos + offset * enemy.size);
+                child.velocity = offset * child.speed;
+                self.add_enemy(child);
+            }
+        }
+        
+        // Carriers drop what they hold; anything else has a chance of a
+        // random power-up (bosses always drop one)
+        let drop = enemy.cargo.or_else(|| {
+            let lucky = enemy.kind == EnemyKind::Boss || self.rng.next_f32() < POWER_UP_DROP_CHANCE;
+            lucky.then(|| PowerUpKind::ALL[self.rng.below(PowerUpKind::ALL.len())])
+        });
+        if let Some(kind) = drop {
+            self.power_ups.insert(PowerUp {
+                pos: enemy.pos,
+                kind,
+                life: Lifetime::new(POWER_UP_LIFETIME),
+            });
+        }
+    }
+    
+    // Blast from a mine: big bang, and enemies caught in it get hurt too
+    fn detonate_mine(&mut self, pos: Vec2) {
+        self.explosions.push(Explosion::new(ExplosionKind::Large, pos, MINE_BLAST_RADIUS, &mut self.fx_rng));
+        self.area_damage(pos, MINE_BLAST_RADIUS, MINE_ENEMY_DAMAGE);
+    }
+    
+    // Damage every enemy within `radius` of `center`; kills score as usual
+    // and any blasts they set off chain on from there
+    fn area_damage(&mut self, center: Vec2, radius: f32, damage: i32) {
+        self.blasts.push(Blast {
+            pos: center,
+            radius,
+            damage,
+            spare_from: self.enemies_spawned,
+        });
+        self.resolve_blasts();
+    }
+    
+    // Set off queued blasts until the chain dies out. Enemies are only ever
+    // removed here and by kills, and each blast spares anything born after
+    // it, so this always ends.
+    fn resolve_blasts(&mut self) {
+        while let Some(blast) = self.blasts.pop() {
+            let mut killed = Vec::new();
+            for (id, enemy) in self.enemies.iter_mut() {
+                if enemy.born >= blast.spare_from || enemy.health <= 0 || enemy.blinking() {
+                    continue;
+                }
+                if (enemy.pos - blast.pos).length() < blast.radius + enemy.size {
+                    enemy.take_hit(blast.damage, &self.config);
+                    if enemy.health <= 0 {
+                        killed.push(id);
+                    }
+                }
+            }
+            for id in killed {
+                if let Some(enemy) = self.enemies.remove(id) {
+                    self.on_enemy_killed(&enemy);
+                }
+            }
+            
+            // Live mines caught in the blast go off too
+            let caught: Vec<Id> = self
+                .mines
+                .iter()
+                .filter(|(_, m)| m.arm_timer <= 0.0 && (m.pos - blast.pos).length() < blast.radius + MINE_RADIUS)
+                .map(|(id, _)| id)
+                .collect();
+            for mine in caught.into_iter().filter_map(|id| self.mines.remove(id)) {
+                self.explosions.push(Explosion::new(ExplosionKind::Large, mine.pos, MINE_BLAST_RADIUS, &mut self.fx_rng));
+                self.blasts.push(Blast {
+                    pos: mine.pos,
+                    radius: MINE_BLAST_RADIUS,
+                    damage: MINE_ENEMY_DAMAGE,
+                    spare_from: self.enemies_spawned,
+                });
+            }
+        }
+    }
+    
+    fn color_unlocked(&self, color: ShipColor) -> bool {
+        color.unlocked_by().is_none_or(|a| self.achievements.is_unlocked(a))
+    }
+    
+    // Step to the next paint the player has unlocked and remember it
+    fn cycle_ship_color(&mut self, forward: bool) {
+        let all = ShipColor::ALL;
+        let current = all.iter().position(|&c| c == self.config.ship_color).unwrap_or(0);
+        let step = if forward { 1 } else { all.len() - 1 };
+        let next = (1..=all.len())
+            .map(|i| all[(current + i * step) % all.len()])
+            .find(|&c| self.color_unlocked(c))
+            .unwrap_or(ShipColor::Blue);
+        self.config.ship_color = next;
+        self.config.save();
+        self.announcement = Some(Announcement {
+            text: format!("SHIP: {}", next.name().to_uppercase()),
+            life: 1.0,
+            tier: 0,
+        });
+    }
+    
+    // Ask for a freeze of `duration`. Requests merge rather than stack
+    // (the longest one wins), and only extend the freeze while there's
+    // budget left, so a burst of impacts can't add up to a stall.
+    fn request_hitstop(&mut self, duration: f32) {
+        if self.reduced_motion() {
+            return;
+        }
+        let room = (self.config.hitstop.budget - self.hitstop_booked).max(0.0);
+        let held = self.hitstop_timer.max(0.0);
+        let extra = (duration - held).clamp(0.0, room);
+        self.hitstop_timer = held + extra;
+        self.hitstop_booked += extra;
+    }
+    
+    // Fires once per wave, on the frame its last enemy goes down
+    fn on_wave_cleared(&mut self) {
+        self.mines.clear();
+        self.slow_fields.clear();
+        self.overdrive_timer = 0.0;
+        let bonus = wave_clear_bonus(self.waves.wave(), self.waves.wave_elapsed());
+        self.score += bonus;
+        self.stats.clear_points += bonus;
+        self.events.push(GameEvent::WaveCleared {
+            bonus,
+            no_hit: self.stats.hits_taken == self.wave_start_hits,
+        });
+        if self.config.mode != GameMode::Hardcore {
+            for controller in self.players.iter_mut().filter(|c| c.alive()) {
+                controller.health = (controller.health + self.config.wave_clear_heal).min(PLAYER_HEALTH);
+            }
+        }
+        
+        self.adapt_difficulty();
+        if self.hitless {
+            self.hitless_waves = self.waves.wave();
+        }
+        self.wave_start_hits = self.stats.hits_taken;
+    }
+    
+    // Rubber-banding: healthy pilots on a big combo get a slightly harder
+    // next wave, struggling ones a slightly easier one. Only normal mode
+    // adapts, so seeded and ranked runs stay comparable.
+    fn adapt_difficulty(&mut self) {
+        let adaptive = self.config.adaptive;
+        if !adaptive.enabled || self.config.mode != GameMode::Normal {
+            return;
+        }
+        let alive: Vec<i32> = self.players.iter().filter(|c| c.alive()).map(|c| c.health).collect();
+        if alive.is_empty() {
+            return;
+        }
+        let health = alive.iter().sum::<i32>() as f32 / (alive.len() as i32 * PLAYER_HEALTH) as f32;
+        let combo = (self.combo_multiplier() - 1) as f32 / 4.0;
+        let clean = if self.stats.hits_taken == self.wave_start_hits { 1.0 } else { 0.0 };
+        let performance = (health + combo + clean) / 3.0;
+        let nudge = ((performance - ADAPTIVE_PAR) / ADAPTIVE_PAR).clamp(-1.0, 1.0) * adaptive.step;
+        self.adaptive_scale = (self.adaptive_scale + nudge).clamp(adaptive.min, adaptive.max.max(adaptive.min));
+    }
+    
+    /// 📣 Take the events the last update raised (nothing in the game
+    /// reads them after `dispatch_events`; this is for tests and embedders)
+    #[allow(dead_code)]
+    pub fn drain_events(&mut self) -> Vec<GameEvent> {
+        std::mem::take(&mut self.events)
+    }
+    
+    // React to everything the last step raised
+    fn dispatch_events(&mut self) {
+        let events = std::mem::take(&mut self.events);
+        for event in &events {
+            match *event {
+                GameEvent::EnemyKilled { kind, pos, size, points } => {
+                    let blast = if kind == EnemyKind::Boss { ExplosionKind::Large } else { ExplosionKind::Standard };
+                    self.explosions.push(Explosion::new(blast, pos, size * 2.0, &mut self.fx_rng));
+                    self.floating_texts.push(FloatingText {
+                        pos,
+                        text: format!("+{}", points),
+                        life: Lifetime::new(1.0),
+                        color: Color::from_rgba(255, 255, 120, 255),
+                    });
+                }
+                GameEvent::PlayerHit { pos, fatal } => {
+                    self.hit_flash = HIT_FLASH_TIME;
+                    // Pilot down - big explosion at player position
+                    if fatal {
+                        self.explosions.push(Explosion::new(ExplosionKind::Large, pos, 80.0, &mut self.fx_rng));
+                    }
+                }
+                GameEvent::PowerUpCollected { kind, pos } => {
+                    self.floating_texts.push(FloatingText {
+                        pos,
+                        text: kind.label().to_owned(),
+                        life: Lifetime::new(1.0),
+                        color: Color::from_rgba(140, 255, 200, 255),
+                    });
+                }
+                GameEvent::WaveCleared { bonus, no_hit } => {
+                    self.wave_clear = Some(WaveClear { bonus, life: WAVE_CLEAR_TIME });
+                    if no_hit && !self.demo {
+                        self.achievements.earn(Achievement::NoHitWave);
+                    }
+                }
+            }
+        }
+        self.events = events;
+    }
+    
+    // Milestones judged on running counters (wave clears are handled above)
+    fn check_achievements(&mut self) {
+        if self.kills >= 1 {
+            self.achievements.earn(Achievement::FirstBlood);
+        }
+        if self.kills >= ACHIEVEMENT_KILLS {
+            self.achievements.earn(Achievement::HundredKills);
+        }
+        if self.time >= ACHIEVEMENT_SURVIVAL {
+            self.achievements.earn(Achievement::Survivor);
+        }
+    }
+    
+    // 0.0 until the stall threshold passes, then ramps to 1.0
+    fn enrage(&self) -> f32 {
+        match self.config.enrage_after {
+            Some(after) => ((self.stall_timer - after) / self.config.enrage_ramp).clamp(0.0, 1.0),
+            None => 0.0,
+        }
+    }
+    
+    fn charge_special(&mut self, amount: f32) {
+        self.special_meter = (self.special_meter + amount).min(1.0);
+    }
+    
+    // Tally the run into its final score and record a new best
+    fn finish_run(&mut self) {
+        self.game_over = true;
+        self.tally_run();
+    }
+    
+    // Last wave cleared: the victory screen instead of game over
+    fn win_run(&mut self) {
+        self.screen = Screen::Victory;
+        self.tally_run();
+    }
+    
+    // Tally the final score; the records it sets are saved after the step
+    fn tally_run(&mut self) {
+        self.breakdown = ScoreBreakdown::tally(&self.stats, self.waves.wave(), self.time);
+        self.score = self.breakdown.total();
+        self.displayed_score = self.score as f32;
+        self.run_ended = true;
+    }
+    
+    // Save whatever bests the finished run set
+    fn record_run(&mut self) {
+        if self.demo || self.practice() {
+            return;
+        }
+        let mode = self.config.mode;
+        // Hardcore runs are always logged, whatever the setting
+        let log = self.config.stats_log || mode == GameMode::Hardcore;
+        if log && !self.sandbox() {
+            append_line(RUN_LOG_FILE, &self.run_log_entry());
+        }
+        let mut store = Store::load(SCORES_FILE);
+        if self.screen == Screen::Victory && !self.sandbox() {
+            store.set(&clear_key(mode, "completed"), true);
+            if self.best_clear_time.is_none_or(|best| self.run_clock < best) {
+                self.best_clear_time = Some(self.run_clock);
+                store.set(&clear_key(mode, "best_time"), format!("{:.3}", self.run_clock));
+            }
+        }
+        // Score and survival are separate records; one run can set both
+        if mode == GameMode::Normal && self.time > self.best_survival {
+            self.best_survival = self.time;
+            store.set("best_survival", self.best_survival);
+        }
+        if mode == GameMode::Normal && self.hitless_waves > self.best_hitless_waves {
+            self.best_hitless_waves = self.hitless_waves;
+            store.set("best_hitless_waves", self.best_hitless_waves);
+        }
+        match mode {
+            GameMode::Normal if self.score > self.best_score => {
+                self.best_score = self.score;
+                store.set("best", self.best_score);
+            }
+            GameMode::Daily if self.score > self.daily_best => {
+                self.daily_best = self.score;
+                store.set("daily_date", &self.today);
+                store.set("daily_best", self.daily_best);
+            }
+            GameMode::Hardcore if self.score > self.hardcore_best => {
+                self.hardcore_best = self.score;
+                store.set(&clear_key(mode, "best"), self.hardcore_best);
+            }
+            _ => {}
+        }
+        store.save();
+    }
+    
+    // Explosions and score popups, which keep playing after a win
+    fn update_effects(&mut self, dt: f32) {
+        for exp in &mut self.explosions {
+            exp.size += dt * exp.kind.growth();
+        }
+        
+        // Floating texts drift upward (and fade as they're reaped)
+        for text in &mut self.floating_texts {
+            text.pos.y -= 40.0 * dt;
+        }
+    }
+    
+    // Every explosion since the last step shoves bullets, enemies and
+    // pilots away from its center
+    fn send_shockwaves(&mut self) {
+        let strength = self.config.shockwave_impulse;
+        let blasts: Vec<(Vec2, f32)> = self
+            .explosions
+            .iter_mut()
+            .filter(|exp| !exp.shoved)
+            .map(|exp| {
+                exp.shoved = true;
+                (exp.pos, exp.size)
+            })
+            .collect();
+        if strength <= 0.0 {
+            return;
+        }
+        
+        for (center, size) in blasts {
+            for bullet in self.bullets.iter_mut().chain(&mut self.enemy_bullets) {
+                bullet.velocity += shockwave_impulse(bullet.pos - center, size, strength);
+            }
+            for enemy in self.enemies.values_mut() {
+                // Big enemies barely budge, as with knockback
+                enemy.knock(shockwave_impulse(enemy.pos - center, size, strength) * KNOCKBACK_REFERENCE_SIZE / enemy.size);
+            }
+            for controller in self.players.iter_mut().filter(|c| c.alive()) {
+                let push = shockwave_impulse(to_mac_vec2(controller.player.position) - center, size, strength);
+                controller.player.velocity += from_mac_vec2(push);
+            }
+        }
+    }
+    
+    // Count down everything that expires on its own and drop what ran
+    // out, in draw-layer order. Shells burst as they go and telegraphed
+    // enemies land.
+    fn reap_transients(&mut self, dt: f32) {
+        let frozen = self.freeze_timer > 0.0;
+        let shells: Vec<Vec2> = reap(&mut self.bullets, dt).into_iter().filter(|b| b.shell).map(|b| b.pos).collect();
+        reap(&mut self.enemy_bullets, if frozen { 0.0 } else { dt });
+        reap_arena(&mut self.power_ups, dt);
+        let landed = reap(&mut self.telegraphs, dt);
+        reap_arena(&mut self.mines, dt);
+        reap(&mut self.explosions, dt);
+        reap(&mut self.floating_texts, dt);
+        
+        for pos in shells {
+            self.explosions.push(Explosion::new(ExplosionKind::Large, pos, MORTAR_BLAST_RADIUS, &mut self.fx_rng));
+            self.area_damage(pos, MORTAR_BLAST_RADIUS, MORTAR_BLAST_DAMAGE);
+        }
+        for telegraph in landed {
+            self.spawn_enemy_at(telegraph.kind, telegraph.pos);
+        }
+    }
+    
+    // One JSON object describing the finished run, for the stats log
+    fn run_log_entry(&self) -> String {
+        format!(
+            "{{\"timestamp\":{},\"mode\":\"{}\",\"coop\":{},\"seed\":\"{:08X}\",\"score\":{},\"wave\":{},\"time\":{:.1},\"kills\":{},\"accuracy\":{:.3}}}",
+            miniquad::date::now() as u64,
+            self.config.mode.name(),
+            self.config.coop,
+            self.seed,
+            self.score,
+            self.waves.wave(),
+            self.time,
+            self.stats.kills(),
+            self.stats.accuracy(),
+        )
+    }
+    
+    // Ramp from full speed down to slow motion over the first 0.3s
+    fn death_time_scale(&self) -> f32 {
+        match &self.death {
+            Some(death) if !self.reduced_motion() => {
+                let t = (death.elapsed / 0.3).min(1.0);
+                1.0 + (DEATH_TIME_SCALE - 1.0) * t
+            }
+            _ => 1.0,
+        }
+    }
+    
+    // Live enemies at which the wave holds its spawns
+    fn enemy_cap(&self) -> usize {
+        self.config.max_concurrent_enemies.min(self.config.caps.enemies)
+    }
+    
+    /// ♿ Single switch for every shake, strobe, flash and slow-mo effect
+    fn reduced_motion(&self) -> bool {
+        self.config.reduced_motion
+    }
+    
+    // Screen-space camera for the world, with last-stand shake and zoom
+    fn world_camera(&self) -> Camera2D {
+        let mut camera = Camera2D::from_display_rect(Rect::new(0.0, 0.0, screen_width(), screen_height()));
+        
+        // Zoomed in, follow the lead pilot but never show past the arena.
+        // The view stays inside it, so edge spawns still arrive unseen.
+        let zoom = self.camera_zoom.max(1.0);
+        if zoom > 1.0 {
+            if let Some(lead) = self.players.first() {
+                let pos = self.interpolate(to_mac_vec2(lead.prev_position), to_mac_vec2(lead.player.position));
+                let half = Vec2::new(screen_width(), screen_height()) / (2.0 * zoom);
+                camera.target = pos.clamp(half, Vec2::new(screen_width(), screen_height()) - half);
+            }
+            camera.zoom *= zoom;
+        }
+        if let Some(death) = self.death.as_ref().filter(|_| !self.reduced_motion()) {
+            let progress = death.elapsed / DEATH_SEQUENCE_TIME;
+            let shake_amount = 12.0 * (1.0 - progress);
+            let shake = Vec2::new((death.elapsed * 60.0).sin(), (death.elapsed * 47.0).cos()) * shake_amount;
+            camera.target += (death.pos - camera.target) * 0.15 * progress + shake;
+            camera.zoom *= 1.0 + 0.1 * progress;
+        }
+        camera
+    }
+    
+    // Dev tools only exist in debug builds with the debug flag set
+    fn dev_tools_enabled(&self) -> bool {
+        cfg!(debug_assertions) && self.config.debug
+    }
+    
+    // Menu navigation: keys, number shortcuts, or click/tap an entry
+    fn update_title(&mut self) {
+        let chosen = self.title_menu.update(Self::title_menu_top());
+        
+        if is_key_pressed(KeyCode::M) {
+            self.config.controls = self.config.controls.toggled();
+            self.config.save();
+        }
+        
+        match chosen.map(|i| TITLE_MENU[i].1) {
+            Some(TitleAction::Play(mode, coop)) => {
+                self.start_run(mode, coop);
+                self.config.save();
+            }
+            Some(TitleAction::Loadout) => {
+                let next = (Loadout::ALL.iter().position(|&l| l == self.config.loadout).unwrap_or(0) + 1) % Loadout::ALL.len();
+                self.config.loadout = Loadout::ALL[next];
+                self.config.save();
+            }
+            Some(TitleAction::Codex) => {
+                self.codex_open = true;
+                self.codex_menu = Menu::new(EnemyKind::ALL.len() + 1).compact(CODEX_ITEM_HEIGHT);
+            }
+            Some(TitleAction::Settings) => self.open_settings(),
+            None => {}
+        }
+    }
+    
+    // Baseline y of the first title menu entry
+    fn title_menu_top() -> f32 {
+        screen_height() / 2.0 - 65.0
+    }
+    
+    // Baseline y of the first codex row
+    fn codex_menu_top() -> f32 {
+        screen_height() / 2.0 - 180.0
+    }
+    
+    // The highlighted row shows its entry; picking a boss met before starts
+    // a practice fight against it. BACK or ESC leaves.
+    fn update_codex(&mut self) {
+        let chosen = self.codex_menu.update(Self::codex_menu_top());
+        if is_key_pressed(KeyCode::Escape) || chosen == Some(EnemyKind::ALL.len()) {
+            self.codex_open = false;
+            return;
+        }
+        let practice = chosen
+            .and_then(|i| EnemyKind::ALL.get(i).copied())
+            .filter(|&kind| kind.is_boss() && self.codex.is_discovered(kind));
+        if let Some(boss) = practice {
+            self.config.practice_boss = boss;
+            self.codex_open = false;
+            self.start_run(GameMode::Practice, false);
+        }
+    }
+    
+    // Baseline y of the first pause menu entry, under the PAUSED heading
+    fn pause_menu_top(&self) -> f32 {
+        self.ui_area().center().y + 10.0
+    }
+    
+    // Escape (or P) resumes; otherwise act on whatever gets picked
+    fn update_pause_menu(&mut self) {
+        if is_key_pressed(KeyCode::Escape) {
+            self.set_paused(false);
+            return;
+        }
+        let Some(i) = self.pause_menu.update(self.pause_menu_top()) else {
+            return;
+        };
+        match PAUSE_MENU[i].1 {
+            PauseAction::Resume => self.set_paused(false),
+            PauseAction::Restart => self.reset(),
+            PauseAction::Settings => self.open_settings(),
+            PauseAction::SaveQuit if !self.can_save() => {}
+            PauseAction::SaveQuit => {
+                self.run_save().save(RUN_SAVE_FILE);
+                self.set_paused(false);
+                if cfg!(target_arch = "wasm32") {
+                    self.screen = Screen::Title;
+                } else {
+                    self.quit = true;
+                }
+            }
+            PauseAction::Quit if cfg!(target_arch = "wasm32") => {
+                self.set_paused(false);
+                self.screen = Screen::Title;
+            }
+            PauseAction::Quit => self.quit = true,
+        }
+    }
+    
+    fn open_settings(&mut self) {
+        self.settings_open = true;
+        self.settings_menu = Menu::new(SETTINGS_MENU.len());
+    }
+    
+    // Baseline y of the first settings row
+    fn settings_menu_top() -> f32 {
+        screen_height() / 2.0 - 90.0
+    }
+    
+    // Left/Right step the highlighted row; picking a row steps it forward.
+    // Every change applies and saves immediately.
+    fn update_settings(&mut self) {
+        if is_key_pressed(KeyCode::Escape) {
+            self.settings_open = false;
+            return;
+        }
+        let chosen = self.settings_menu.update(Self::settings_menu_top());
+        let back = is_key_pressed(KeyCode::Left) || is_key_pressed(KeyCode::A);
+        let forward = is_key_pressed(KeyCode::Right) || is_key_pressed(KeyCode::D);
+        let setting = SETTINGS_MENU[self.settings_menu.selected()];
+        match (chosen, back, forward) {
+            (Some(_), _, _) | (None, false, true) => self.change_setting(setting, true),
+            (None, true, false) => self.change_setting(setting, false),
+            _ => {}
+        }
+    }
+    
+    fn change_setting(&mut self, setting: Setting, forward: bool) {
+        match setting {
+            Setting::ShipColor => self.cycle_ship_color(forward),
+            Setting::Controls => self.config.controls = self.config.controls.toggled(),
+            Setting::Difficulty => {
+                let difficulty = cycle(&Difficulty::ALL, self.config.difficulty, forward);
+                self.config.set_difficulty(difficulty);
+            }
+            Setting::Quality => {
+                let all = [Quality::High, Quality::Medium, Quality::Low];
+                self.set_quality(cycle(&all, self.config.quality, forward));
+            }
+            Setting::ReducedMotion => self.config.reduced_motion = !self.config.reduced_motion,
+            Setting::Back => {
+                self.settings_open = false;
+                return;
+            }
+        }
+        self.config.save();
+    }
+    
+    fn setting_label(&self, setting: Setting) -> String {
+        let (name, value) = match setting {
+            Setting::ShipColor => ("SHIP", self.config.ship_color.name()),
+            Setting::Controls => ("CONTROLS", self.config.controls.name()),
+            Setting::Difficulty => ("DIFFICULTY", self.config.difficulty.name()),
+            Setting::Quality => ("QUALITY", self.config.quality.name()),
+            Setting::ReducedMotion => ("REDUCED MOTION", if self.config.reduced_motion { "on" } else { "off" }),
+            Setting::Back => return "BACK".to_owned(),
+        };
+        format!("{}: {}", name, value.to_uppercase())
+    }
+    
+    /// 🚪 The player chose Quit; the main loop should exit
+    pub fn quit_requested(&self) -> bool {
+        self.quit
+    }
+
+    // This frame's keys and touches for a run in progress. Returns true if
+    // they ended it (restart, or back to the title), in which case nothing
+    // more should happen this frame.
+    fn handle_frame_input(&mut self) -> bool {
+        // Escape leaves sandbox (or a finished run) for the title screen
+        let finished = self.game_over || self.screen == Screen::Victory;
+        if (finished || self.sandbox()) && is_key_pressed(KeyCode::Escape) {
+            self.screen = Screen::Title;
+            self.codex_open = self.practice();
+            return true;
+        }
+        
+        // A finished practice fight heads back to the codex on its own after
+        // a moment; SPACE (or a click) tries it again straight away
+        if finished && self.practice() {
+            if is_key_pressed(KeyCode::Space) || is_mouse_button_pressed(MouseButton::Left) {
+                self.reset();
+            } else if self.game_over_time >= PRACTICE_RESULT_TIME {
+                self.screen = Screen::Title;
+                self.codex_open = true;
+            } else {
+                return false;
+            }
+            return true;
+        }
+        
+        // Restart from the game-over and victory screens
+        if finished {
+            if is_key_pressed(KeyCode::Key1) || is_key_pressed(KeyCode::Key2) {
+                self.start_run(GameMode::Normal, is_key_pressed(KeyCode::Key2));
+            } else if is_key_pressed(KeyCode::R) {
+                self.reset_with_seed(self.seed);
+            } else if is_key_pressed(KeyCode::Space) || is_mouse_button_pressed(MouseButton::Left) {
+                self.reset();
+            } else {
+                return false;
+            }
+            return true;
+        }
+        
+        // Last stand ignores input until game over
+        if self.death.is_some() {
+            return false;
+        }
+        
+        if is_key_pressed(KeyCode::Q) {
+            self.set_quality(self.config.quality.next());
+            self.config.save();
+        }
+        if is_key_pressed(KeyCode::C) {
+            self.cycle_ship_color(true);
+        }
+        if is_key_pressed(KeyCode::F8) {
+            self.config.reduced_motion = !self.config.reduced_motion;
+            self.config.save();
+        }
+        if is_key_pressed(KeyCode::G) {
+            self.config.grid = !self.config.grid;
+            self.config.save();
+        }
+        if is_key_pressed(KeyCode::F9) {
+            self.config.swap_sticks = !self.config.swap_sticks;
+            self.config.save();
+        }
+        if is_key_pressed(KeyCode::F10) {
+            self.config.invert_aim_y = !self.config.invert_aim_y;
+            self.config.save();
+        }
+        
+        if self.dev_tools_enabled() {
+            self.handle_dev_keys();
+        }
+        if self.sandbox() {
+            let cursor = self.cursor_world();
+            for (key, kind) in SANDBOX_SPAWN_KEYS {
+                if is_key_pressed(key) {
+                    self.spawn_enemy_at(kind, cursor);
+                }
+            }
+        }
+        
+        self.handle_input();
+        false
+    }
+    
+    // 🛠️ F1: god mode, F2-F5: spawn Drifter/Chaser/Splitter/Boss at the cursor,
+    // F6: next wave, F7: skip ahead five waves
+    fn handle_dev_keys(&mut self) {
+        if is_key_pressed(KeyCode::F1) {
+            self.god_mode = !self.god_mode;
+        }
+        
+        // Diagnostics toggle separately: F11 the readout, F12 hitboxes,
+        // and Shift with either flips both together
+        let both = is_key_down(KeyCode::LeftShift) || is_key_down(KeyCode::RightShift);
+        if is_key_pressed(KeyCode::F11) || (both && is_key_pressed(KeyCode::F12)) {
+            self.dev_overlay = !self.dev_overlay;
+        }
+        if is_key_pressed(KeyCode::F12) || (both && is_key_pressed(KeyCode::F11)) {
+            self.show_hitboxes = !self.show_hitboxes;
+        }
+        
+        let cursor = self.cursor_world();
+        let spawn_keys = [
+            (KeyCode::F2, EnemyKind::Drifter),
+            (KeyCode::F3, EnemyKind::Chaser),
+            (KeyCode::F4, EnemyKind::Splitter),
+            (KeyCode::F5, EnemyKind::Boss),
+        ];
+        for (key, kind) in spawn_keys {
+            if is_key_pressed(key) {
+                self.spawn_enemy_at(kind, cursor);
+            }
+        }
+        
+        if is_key_pressed(KeyCode::F6) {
+            self.waves.jump_to(self.waves.wave() + 1);
+        }
+        if is_key_pressed(KeyCode::F7) {
+            self.waves.jump_to(self.waves.wave() + 5);
+        }
+    }
+    
+    fn spawn_enemy(&mut self, kind: EnemyKind) {
+        let pos = self.spawn_point(self.spawn_margin(kind));
+        self.spawn_enemy_at(kind, pos);
+    }
+    
+    // Edge buffer for the smallest `kind` could roll, so a new enemy never
+    // starts out past its own buffer
+    fn spawn_margin(&self, kind: EnemyKind) -> f32 {
+        let (_, size, _, _) = kind.stats();
+        let variation = if kind == EnemyKind::Boss { 0.0 } else { self.config.size_variation.clamp(0.0, 0.9) };
+        edge_buffer(&self.config, size * (1.0 - variation))
+    }
+    
+    fn spawn_group(&mut self, group: SpawnGroup) {
+        if group.pattern == SpawnPattern::Edge {
+            for _ in 0..group.count {
+                self.spawn_enemy(group.kind);
+            }
+            return;
+        }
+        let margin = self.spawn_margin(group.kind);
+        for pos in self.formation_points(group.pattern, group.count, margin) {
+            self.spawn_enemy_at(group.kind, pos);
+        }
+    }
+    
+    // Formation slots around the lead pilot, pushed out of every pilot's
+    // spawn-exclusion radius; edge formations start `margin` off-screen
+    fn formation_points(&mut self, pattern: SpawnPattern, count: u32, margin: f32) -> Vec<Vec2> {
+        let center = self.view_size / 2.0;
+        let target = self.nearest_player_pos(center).unwrap_or(center);
+        let radius = self.config.spawn_exclusion_radius;
+        let mut points = match pattern {
+            SpawnPattern::Ring => {
+                let start = self.rng.range(0.0, std::f32::consts::TAU);
+                ring_points(target, radius + FORMATION_RING_MARGIN, count, start)
+            }
+            SpawnPattern::Edge | SpawnPattern::Line | SpawnPattern::Vee => {
+                // Lined up across the approach from an edge, the V's point leading
+                let anchor = self.edge_point(margin);
+                let heading = (target - anchor).normalize_or(Vec2::X);
+                let side = heading.perp() * FORMATION_SPACING;
+                (0..count)
+                    .map(|i| match pattern {
+                        SpawnPattern::Vee => {
+                            let rank = i.div_ceil(2) as f32;
+                            let sign = if i % 2 == 0 { 1.0 } else { -1.0 };
+                            anchor + side * rank * sign - heading * rank * FORMATION_SPACING
+                        }
+                        _ => anchor + side * (i as f32 - (count as f32 - 1.0) / 2.0),
+                    })
+                    .collect()
+            }
+        };
+        for pilot in self.players.iter().filter(|c| c.alive()) {
+            let pilot = to_mac_vec2(pilot.player.position);
+            for point in &mut points {
+                let offset = *point - pilot;
+                if offset.length() < radius {
+                    *point = pilot + offset.normalize_or(Vec2::X) * radius;
+                }
+            }
+        }
+        points
+    }
+    
+    // Random edge point outside the exclusion radius of every pilot
+    fn spawn_point(&mut self, margin: f32) -> Vec2 {
+        self.clear_point(|state| state.edge_point(margin))
+    }
+    
+    // Point rolled by `roll` outside the exclusion radius of every pilot.
+    // After a few misses (a pilot in the way) take the furthest roll instead.
+    fn clear_point(&mut self, mut roll: impl FnMut(&mut Self) -> Vec2) -> Vec2 {
+        let radius = self.config.spawn_exclusion_radius;
+        let mut best = (Vec2::ZERO, f32::MIN);
+        for _ in 0..SPAWN_REROLLS {
+            let pos = roll(self);
+            let clearance = self
+                .nearest_player_pos(pos)
+                .map_or(f32::MAX, |player| (player - pos).length());
+            if clearance >= radius {
+                return pos;
+            }
+            if clearance > best.1 {
+                best = (pos, clearance);
+            }
+        }
+        best.0
+    }
+    
+    // Spawn from edges, `margin` out past them
+    fn edge_point(&mut self, margin: f32) -> Vec2 {
+        let (screen_width, screen_height) = (self.view_size.x, self.view_size.y);
+        match self.rng.below(4) {
+            0 => Vec2::new(self.rng.range(0.0, screen_width), -margin), // Top
+            1 => Vec2::new(self.rng.range(0.0, screen_width), screen_height + margin), // Bottom
+            2 => Vec2::new(-margin, self.rng.range(0.0, screen_height)), // Left
+            _ => Vec2::new(screen_width + margin, self.rng.range(0.0, screen_height)), // Right
+        }
+    }
+    
+    // Random point inside the arena, inset from the edges
+    fn interior_point(&mut self) -> Vec2 {
+        let inset = BURST_EDGE_INSET;
+        Vec2::new(
+            self.rng.range(inset, (self.view_size.x - inset).max(inset)),
+            self.rng.range(inset, (self.view_size.y - inset).max(inset)),
+        )
+    }
+    
+    // Mark where the new wave's opening burst will land, as much of it as
+    // the enemy cap has room for
+    fn telegraph_burst(&mut self) {
+        let room = self.enemy_cap().saturating_sub(self.enemies.len() + self.telegraphs.len());
+        for kind in self.waves.take_burst(room) {
+            let pos = self.clear_point(Self::interior_point);
+            self.telegraphs.push(SpawnTelegraph { kind, pos, life: Lifetime::new(BURST_TELEGRAPH_TIME) });
+        }
+    }
+    
+    // Every `carrier_every` waves one Carrier comes in from the edge as the
+    // wave opens (not in boss fights)
+    fn schedule_carrier(&mut self) {
+        let every = self.config.carrier_every;
+        let boss_fight = matches!(self.config.mode, GameMode::BossRush | GameMode::Practice);
+        if every > 0 && self.waves.wave().is_multiple_of(every) && !boss_fight {
+            self.spawn_enemy(EnemyKind::Carrier);
+        }
+    }
+    
+    fn spawn_enemy_at(&mut self, kind: EnemyKind, pos: Vec2) {
+        let enemy = self.make_enemy(kind, pos);
+        self.add_enemy(enemy);
+    }
+    
+    // A fresh `kind` at `pos`, set up for this run's rules
+    fn make_enemy(&mut self, kind: EnemyKind, pos: Vec2) -> Enemy {
+        let mut enemy = Enemy::new(kind, pos);
+        if kind == EnemyKind::Scavenger {
+            enemy.speed = self.config.scavenger_speed;
+        }
+        if kind == EnemyKind::Carrier {
+            enemy.speed = self.config.carrier_speed;
+            enemy.cargo = Some(PowerUpKind::ALL[self.rng.below(PowerUpKind::ALL.len())]);
+        }
+        if let Some(&(_, poise)) = self.config.poise.iter().find(|(k, _)| *k == kind) {
+            enemy.max_poise = poise;
+            enemy.poise = poise;
+        }
+        
+        // Everything but bosses comes in a spread of sizes
+        let variation = self.config.size_variation.clamp(0.0, 0.9);
+        if kind != EnemyKind::Boss && variation > 0.0 {
+            enemy = enemy.scaled(1.0 + self.rng.range(-variation, variation));
+        }
+        
+        // Boss rush bosses toughen up stage by stage
+        if kind == EnemyKind::Boss && self.config.mode == GameMode::BossRush {
+            let scale = 1.0 + BOSS_RUSH_HEALTH_STEP * self.waves.wave().saturating_sub(1) as f32;
+            enemy.health = (enemy.health as f32 * scale) as i32;
+            enemy.max_health = enemy.health;
+        }
+        
+        // Drifters commit to a heading aimed at the player's spawn-time position
+        if kind == EnemyKind::Drifter {
+            let to_player = self.nearest_player_pos(pos).unwrap_or(pos) - pos;
+            let direction = to_player.normalize_or_zero();
+            enemy.velocity = direction * enemy.speed;
+            enemy.rotation = direction.y.atan2(direction.x);
+        }
+        enemy
+    }
+    
+    // A fresh spawn, its first shot staggered off the run's RNG
+    fn add_enemy(&mut self, mut enemy: Enemy) {
+        if self.enemies.len() >= self.config.caps.enemies {
+            return;
+        }
+        enemy.fire_timer = self.rng.range(1.0, SHOOTER_FIRE_INTERVAL);
+        self.insert_enemy(enemy);
+    }
+    
+    // Every enemy gets an id from the arena, so bullets can remember who
+    // they've hit, and its place in the spawn order
+    fn insert_enemy(&mut self, mut enemy: Enemy) {
+        enemy.born = self.enemies_spawned;
+        self.enemies_spawned += 1;
+        
+        // First ever sighting unlocks the codex entry
+        if !self.demo && self.codex.discover(enemy.kind) {
+            self.announcement = Some(Announcement {
+                text: format!("NEW ENEMY: {}", enemy.kind.title().to_uppercase()),
+                life: 1.5,
+                tier: 0,
+            });
+        }
+        self.enemies.insert(enemy);
+    }
+
+    // This frame's input for every live pilot, read from the window once
+    // per frame. Autopilot and scripted seats get none; they don't read it.
+    fn live_input(&self) -> Vec<PilotInput> {
+        self.players.iter().map(|controller| match controller.control {
+            Control::TwinStick => PilotInput {
+                movement: self.left_joystick.get_input(),
+                aim: self.right_joystick.get_input(),
+                firing: self.right_joystick.active,
+                trigger: self.right_joystick.magnitude(),
+                parry: is_mouse_button_pressed(MouseButton::Right) || is_key_pressed(KeyCode::E),
+                focus: is_key_down(KeyCode::LeftShift),
+                special: is_key_pressed(KeyCode::R) || is_mouse_button_pressed(MouseButton::Middle),
+                dash: is_key_pressed(KeyCode::Space),
+            },
+            Control::Keyboard(keys) => {
+                let axis = |neg: KeyCode, pos: KeyCode| {
+                    is_key_down(pos) as i32 as f32 - is_key_down(neg) as i32 as f32
+                };
+                let movement = JoyVec2::new(axis(keys.left, keys.right), axis(keys.up, keys.down)).normalize();
+                
+                // Keyboard pilots aim where they're heading
+                PilotInput {
+                    movement,
+                    aim: movement,
+                    firing: is_key_down(keys.fire),
+                    trigger: 1.0,
+                    parry: is_key_pressed(keys.parry),
+                    focus: is_key_down(keys.focus),
+                    special: is_key_pressed(keys.special),
+                    dash: is_key_pressed(keys.dash),
+                }
+            }
+            Control::Autopilot | Control::Scripted => PilotInput::default(),
+        }).collect()
+    }
+    
+    // What pilot `i` actually flies with this step: the autopilot decides
+    // for itself, and twin sticks go through sensitivity and smoothing
+    // (and single-stick auto-aim)
+    fn resolve_input(&mut self, i: usize, raw: PilotInput, dt: f32) -> PilotInput {
+        let pos = to_mac_vec2(self.players[i].player.position);
+        match self.players[i].control {
+            Control::Autopilot => self.autopilot_input(pos),
+            Control::TwinStick => {
+                let (sensitivity, smoothing) = (self.config.input_sensitivity, self.config.input_smoothing);
+                let gate = self.config.sticks.gate;
+                let mut input = raw;
+                input.movement = self.move_filter.apply(raw.movement, gate, sensitivity, smoothing, dt);
+                input.aim = self.aim_filter.apply(raw.aim, gate, sensitivity, smoothing, dt);
+                if self.config.invert_aim_y {
+                    input.aim.y = -input.aim.y;
+                }
+                
+                // Single stick: fire at the nearest enemy in range, else just face the heading
+                if self.config.controls == ControlScheme::SingleStick {
+                    let target = self.nearest_enemy_pos(pos, AUTO_AIM_RANGE);
+                    input.aim = target.map_or(input.movement, |t| from_mac_vec2((t - pos).normalize_or_zero()));
+                    input.firing = target.is_some();
+                    input.trigger = 1.0;
+                }
+                input
+            }
+            Control::Keyboard(_) | Control::Scripted => raw,
+        }
+    }
+
+    // Demo pilot: hold a stand-off distance from the nearest enemy while
+    // shooting at it, drift back toward the middle, sidestep close shots
+    fn autopilot_input(&self, pos: Vec2) -> PilotInput {
+        let center = self.view_size / 2.0;
+        let mut steer = (center - pos) / center.length();
+        let target = self.nearest_enemy_pos(pos, f32::MAX);
+        if let Some(target) = target {
+            let offset = target - pos;
+            let closing = ((offset.length() - AUTOPILOT_STANDOFF) / AUTOPILOT_STANDOFF).clamp(-1.0, 1.0);
+            steer += offset.normalize_or_zero() * closing;
+        }
+        let threat = self
+            .enemy_bullets
+            .iter()
+            .map(|b| b.pos)
+            .filter(|b| (*b - pos).length() < AUTOPILOT_DODGE_RANGE)
+            .min_by(|a, b| (*a - pos).length().total_cmp(&(*b - pos).length()));
+        if let Some(shot) = threat {
+            steer += (pos - shot).normalize_or_zero() * 2.0;
+        }
+        
+        let movement = from_mac_vec2(steer.clamp_length_max(1.0));
+        PilotInput {
+            movement,
+            aim: target.map_or(movement, |t| from_mac_vec2((t - pos).normalize_or_zero())),
+            firing: target.is_some(),
+            trigger: 1.0,
+            parry: false,
+            focus: false,
+            special: self.special_meter >= 1.0,
+            dash: false,
+        }
+    }
+    
+    // Title idling: after a while a demo round plays behind the menu until
+    // it ends or any input arrives. False if this step's input was spent
+    // waking the title up.
+    fn update_attract(&mut self, dt: f32) -> bool {
+        if any_press() {
+            self.idle_time = 0.0;
+            return self.attract.take().is_none();
+        }
+        self.idle_time += dt;
+        if self.attract.is_none() && self.idle_time >= ATTRACT_DELAY {
+            self.attract = Some(Box::new(self.attract_demo()));
+        }
+        if let Some(demo) = &mut self.attract {
+            demo.update(dt, &[]);
+            if demo.game_over || demo.screen == Screen::Victory || demo.time >= ATTRACT_LENGTH {
+                self.attract = None;
+                self.idle_time = 0.0;
+            }
+        }
+        true
+    }
+    
+    // A fresh solo run on the same systems, flown by the autopilot
+    fn attract_demo(&self) -> GameState {
+        let mut config = self.config.clone();
+        config.mode = GameMode::Normal;
+        config.coop = false;
+        config.tutorial_completed = true;
+        config.stats_log = false;
+        config.ghost_recording = false;
+        let mut demo = GameState::headless(config, fresh_seed(), self.view_size);
+        demo.demo = true;
+        demo.skip_intro();
+        for controller in &mut demo.players {
+            controller.control = Control::Autopilot;
+        }
+        demo
+    }
+    
+    /// 🎬 Start a solo run (on the script's seed, if it names one) with the
+    /// first pilot driven by `script` instead of the player
+    pub fn play_script(&mut self, script: ScriptedInput) {
+        self.config.mode = GameMode::Normal;
+        self.config.coop = false;
+        self.reset_with_seed(script.seed.unwrap_or_else(|| Self::run_seed(&self.config)));
+        self.tutorial = None;
+        self.skip_intro();
+        self.players[0].control = Control::Scripted;
+        self.script = Some(script);
+    }
+    
+    // Straight to play; the safe-time grace still runs out on its own clock
+    fn skip_intro(&mut self) {
+        self.intro_alpha = 0.0;
+        self.game_started = true;
+    }
+
+    // A press during the intro skips it; once play starts, the skip button
+    // ends the tutorial. Never both on one press.
+    fn skip_ahead(&mut self, pressed: bool, tutorial_skip: bool) {
+        if !self.game_started && pressed {
+            self.skip_intro();
+        } else if self.game_started && self.tutorial.is_some() && tutorial_skip {
+            self.finish_tutorial();
+        }
+    }
+    
+    // Move on once the current step's action has been held long enough
+    fn advance_tutorial(&mut self, dt: f32) {
+        let moving = self.move_filter.value().length() > 0.3;
+        let aiming = self.right_joystick.active;
+        let Some(tutorial) = &mut self.tutorial else {
+            return;
+        };
+        let doing = match tutorial.step {
+            TutorialStep::Move => moving,
+            TutorialStep::Aim => aiming,
+        };
+        if doing {
+            tutorial.progress += dt;
+        }
+        if tutorial.progress < TUTORIAL_HOLD_TIME {
+            return;
+        }
+        match tutorial.step {
+            // Single stick has nothing more to teach after moving
+            TutorialStep::Move if self.config.controls == ControlScheme::SingleStick => self.finish_tutorial(),
+            TutorialStep::Move => {
+                tutorial.step = TutorialStep::Aim;
+                tutorial.progress = 0.0;
+            }
+            TutorialStep::Aim => self.finish_tutorial(),
+        }
+    }
+    
+    // End the walkthrough (done or skipped) for good and let the waves start
+    fn finish_tutorial(&mut self) {
+        self.tutorial = None;
+        self.config.tutorial_completed = true;
+        self.tutorial_finished = true;
+        self.announcement = Some(Announcement {
+            text: "GO!".to_owned(),
+            life: 1.0,
+            tier: 0,
+        });
+    }
+    
+    fn tutorial_skip_rect(&self) -> Rect {
+        let area = self.ui_area();
+        Rect::new(area.center().x - 70.0, area.bottom() - 70.0, 140.0, 40.0)
+    }
+    
+    // Enter, or a click/tap on the SKIP button
+    fn tutorial_skip_pressed(&self) -> bool {
+        let rect = self.tutorial_skip_rect();
+        let tapped = touches()
+            .iter()
+            .any(|t| t.phase == TouchPhase::Started && rect.contains(t.position));
+        let clicked = is_mouse_button_pressed(MouseButton::Left) && rect.contains(Vec2::from(mouse_position()));
+        is_key_pressed(KeyCode::Enter) || tapped || clicked
+    }
+
+    fn set_paused(&mut self, paused: bool) {
+        self.paused = paused;
+        self.unspent_presses.clear();
+        if !paused {
+            self.release_stale_sticks();
+        }
+    }
+    
+    // A touch end that never reaches `handle_input` (dropped by some mobile
+    // browsers, or released while paused) would leave its stick thrusting
+    // forever. Keep each stick only if its finger or button is still down.
+    fn release_stale_sticks(&mut self) {
+        let held: Vec<u64> = touches()
+            .iter()
+            .filter(|t| !matches!(t.phase, TouchPhase::Ended | TouchPhase::Cancelled))
+            .map(|t| t.id)
+            .collect();
+        let mouse_held = touches().is_empty() && is_mouse_button_down(MouseButton::Left);
+        self.release_sticks_unless_held(&held, mouse_held);
+    }
+    
+    // Let go of each stick whose touch isn't among `held` (or, for a mouse
+    // driven stick, whose button isn't `mouse_held`)
+    fn release_sticks_unless_held(&mut self, held: &[u64], mouse_held: bool) {
+        let still_held = |id: Option<u64>| match id {
+            Some(id) => held.contains(&id),
+            None => mouse_held,
+        };
+        
+        if self.left_joystick.active && !still_held(self.left_touch_id) {
+            self.left_joystick.on_touch_end();
+            self.left_touch_id = None;
+            self.move_filter = InputFilter::default();
+        }
+        if self.right_joystick.active && !still_held(self.right_touch_id) {
+            self.right_joystick.on_touch_end();
+            self.right_touch_id = None;
+            self.aim_filter = InputFilter::default();
+        }
+    }
+    
     fn handle_input(&mut self) {
         let touches = touches();
         let screen_width = screen_width();
         let left_side_x = screen_width / 2.0;
+        // `left_joystick` always steers and `right_joystick` always aims;
+        // swapping only changes which half of the screen spawns each
+        // With a single stick the whole screen steers
+        let swap = self.config.swap_sticks;
+        let single = self.config.controls == ControlScheme::SingleStick;
+        let is_move_side = |x: f32| single || (x < left_side_x) != swap;
+
+        // A stick's base stays fully on screen and on its own half, so a
+        // touch near the middle can't put its ring across the divider
+        let radius = self.left_joystick.radius;
+        let screen_height = screen_height();
+        let base_at = |pos: JoyVec2| {
+            let (mut min_x, mut max_x) = (radius, (screen_width - radius).max(radius));
+            if !single {
+                if pos.x < left_side_x {
+                    max_x = (left_side_x - radius).max(min_x);
+                } else {
+                    min_x = (left_side_x + radius).min(max_x);
+                }
+            }
+            JoyVec2::new(pos.x.clamp(min_x, max_x), pos.y.clamp(radius, (screen_height - radius).max(radius)))
+        };
 
         // Handle each touch
         for touch in &touches {
@@ -388,14 +4311,16 @@ impl GameState {
 
             match touch.phase {
                 TouchPhase::Started => {
-                    // Left side = movement joystick
-                    if touch.position.x < left_side_x && self.left_touch_id.is_none() {
-                        self.left_joystick.on_touch_start(pos);
+                    // Movement side (left unless swapped)
+                    if is_move_side(touch.position.x) && self.left_touch_id.is_none() {
+                        self.left_joystick.on_touch_start(base_at(pos));
+                        self.left_joystick.on_touch_move(pos);
                         self.left_touch_id = Some(touch.id);
                     }
-                    // Right side = aim joystick
-                    else if touch.position.x >= left_side_x && self.right_touch_id.is_none() {
-                        self.right_joystick.on_touch_start(pos);
+                    // Aim side
+                    else if !is_move_side(touch.position.x) && self.right_touch_id.is_none() {
+                        self.right_joystick.on_touch_start(base_at(pos));
+                        self.right_joystick.on_touch_move(pos);
                         self.right_touch_id = Some(touch.id);
                     }
                 }
@@ -425,113 +4350,344 @@ impl GameState {
             let pos = from_mac_vec2(Vec2::new(mouse_pos.0, mouse_pos.1));
 
             if is_mouse_button_pressed(MouseButton::Left) {
-                if mouse_pos.0 < left_side_x {
-                    self.left_joystick.on_touch_start(pos);
-                } else {
-                    self.right_joystick.on_touch_start(pos);
+                let stick = if is_move_side(mouse_pos.0) { &mut self.left_joystick } else { &mut self.right_joystick };
+                stick.on_touch_start(base_at(pos));
+                stick.on_touch_move(pos);
+            } else if is_mouse_button_down(MouseButton::Left) {
+                if is_move_side(mouse_pos.0) && self.left_joystick.active {
+                    self.left_joystick.on_touch_move(pos);
+                } else if !is_move_side(mouse_pos.0) && self.right_joystick.active {
+                    self.right_joystick.on_touch_move(pos);
+                }
+            } else if is_mouse_button_released(MouseButton::Left) {
+                self.left_joystick.on_touch_end();
+                self.right_joystick.on_touch_end();
+            }
+        }
+        
+        self.release_stale_sticks();
+    }
+
+    pub fn draw(&mut self) {
+        if self.screen == Screen::Title {
+            self.draw_title();
+            return;
+        }
+        
+        let bloom = self.config.quality.bloom();
+        let aberration = if self.config.quality.hit_aberration() && !self.reduced_motion() {
+            (self.hit_flash / HIT_FLASH_TIME).max(0.0) * HIT_ABERRATION_PIXELS
+        } else {
+            0.0
+        };
+        let offscreen = bloom || aberration > 0.0;
+        if let (true, Some(postfx)) = (offscreen, &mut self.postfx) {
+            postfx.fit_to_screen();
+        }
+        
+        // The world goes through post-processing when needed; overlays stay crisp
+        let camera = self.world_camera();
+        match self.postfx.as_ref().filter(|_| offscreen) {
+            Some(postfx) => {
+                postfx.begin_scene(camera);
+                self.draw_world();
+                postfx.composite(bloom, aberration);
+            }
+            None => {
+                set_camera(&camera);
+                self.draw_world();
+                set_default_camera();
+            }
+        }
+        self.draw_overlay();
+    }
+    
+    fn draw_world(&self) {
+        for layer in DrawLayer::ORDER {
+            self.draw_layer(layer);
+        }
+    }
+    
+    // Everything on one layer; see `DrawLayer` for the order
+    fn draw_layer(&self, layer: DrawLayer) {
+        match layer {
+            DrawLayer::Background => {
+                // Deep space background
+                clear_background(Color::from_rgba(5, 5, 15, 255));
+                if self.config.grid {
+                    self.draw_grid();
+                }
+
+                // Draw breathing particles
+                for particle in &self.particles {
+                    draw_circle(
+                        particle.pos.x,
+                        particle.pos.y,
+                        particle.size,
+                        Color::from_rgba(100, 120, 200, (particle.alpha * 255.0) as u8),
+                    );
+                }
+            }
+            DrawLayer::Ghost => {
+                // Previous run's ghost races alongside this one
+                if let Some(sample) = self.previous_ghost.as_ref().and_then(|g| g.sample_at(self.time)) {
+                    if !self.game_over {
+                        self.draw_ghost_ship(sample.pos, sample.rotation);
+                    }
+                }
+            }
+            DrawLayer::PlayerTrail => {
+                // Draw player trail
+                let quality = self.config.quality;
+                for seg in self.trail.iter() {
+                    let color = Color { a: seg.life * 0.2, ..seg.color };
+                    let size = seg.size * seg.life;
+                    draw_glow_circle(seg.pos, size * 0.6, size * 0.4, color, quality.glow_layers(2));
+                }
+            }
+            DrawLayer::Bullets => {
+                // Draw bullets in their weapon's colours (parried shots turn gold)
+                let quality = self.config.quality;
+                for bullet in &self.bullets {
+                    let pos = self.interpolate(bullet.prev_pos, bullet.pos);
+                    let BulletLook { glow, core, radius, trail } = bullet.look;
+                    if bullet.shell {
+                        // Shells swell toward the top of their arc and shrink as they come down
+                        let flight = 1.0 - bullet.life.fraction();
+                        let r = radius * (1.0 + 0.6 * (flight * std::f32::consts::PI).sin());
+                        draw_glow_circle(pos, r + 4.0, 0.0, Color::from_rgba(glow.0, glow.1, glow.2, 140), quality.glow_layers(1));
+                        draw_circle(pos.x, pos.y, r, Color::from_rgba(core.0, core.1, core.2, 255));
+                        draw_circle(pos.x, pos.y, r * 0.4, Color::from_rgba(255, 240, 200, 255));
+                        continue;
+                    }
+                    // Shots fade over the last stretch of their range
+                    let fade = (bullet.life.fraction() / BULLET_FADE_FRACTION).min(1.0);
+                    let alpha = |a: f32| (a * fade) as u8;
+
+                    // Tracer streak behind the shot, longer the faster it flies
+                    if trail > 0.0 {
+                        let tail = pos - bullet.velocity * trail;
+                        draw_line(tail.x, tail.y, pos.x, pos.y, 3.0, Color::from_rgba(glow.0, glow.1, glow.2, alpha(90.0)));
+                    }
+
+                    // Bullet glow
+                    draw_glow_circle(pos, radius + 3.0, 0.0, Color::from_rgba(glow.0, glow.1, glow.2, alpha(150.0)), quality.glow_layers(1));
+                    draw_circle(pos.x, pos.y, radius, Color::from_rgba(core.0, core.1, core.2, alpha(255.0)));
+                    draw_circle(pos.x, pos.y, radius * 0.4, Color::from_rgba(255, 255, 255, alpha(255.0)));
+                }
+            }
+            DrawLayer::EnemyBullets => {
+                // Draw enemy bullets - hot red
+                let quality = self.config.quality;
+                let frozen = self.freeze_timer > 0.0;
+                for bullet in &self.enemy_bullets {
+                    let BulletLook { glow, core, radius, trail } = bullet.look;
+                    if !frozen && trail > 0.0 {
+                        let tail = bullet.pos - bullet.velocity * trail;
+                        draw_line(tail.x, tail.y, bullet.pos.x, bullet.pos.y, 3.0, Color::from_rgba(glow.0, glow.1, glow.2, 90));
+                    }
+                    draw_glow_circle(bullet.pos, radius + 4.0, 0.0, Color::from_rgba(glow.0, glow.1, glow.2, 120), quality.glow_layers(1));
+                    let core = if frozen { Color::from_rgba(150, 210, 255, 255) } else { Color::from_rgba(core.0, core.1, core.2, 255) };
+                    draw_circle(bullet.pos.x, bullet.pos.y, radius, core);
+                    draw_circle(bullet.pos.x, bullet.pos.y, radius * 0.4, Color::from_rgba(255, 230, 220, 255));
+                }
+            }
+            DrawLayer::PowerUps => {
+                // Draw power-ups as pulsing diamonds, blinking before they expire
+                for power_up in self.power_ups.values() {
+                    let still = self.reduced_motion();
+                    if !still && power_up.life.remaining() < 2.0 && (power_up.life.remaining() * 10.0) as i32 % 2 == 0 {
+                        continue;
+                    }
+                    let (color, label) = power_up.kind.look();
+                    let r = if still { 14.0 } else { 14.0 + (self.time * 5.0).sin() * 2.0 };
+                    let p = power_up.pos;
+                    draw_circle(p.x, p.y, r + 10.0, Color::new(color.r, color.g, color.b, 0.2));
+                    draw_poly(p.x, p.y, 4, r, 45.0, color);
+                    let width = measure_text(label, None, 20, 1.0).width;
+                    draw_text(label, p.x - width / 2.0, p.y + 6.0, 20.0, Color::from_rgba(40, 30, 10, 255));
+                }
+            }
+            DrawLayer::SlowFields => {
+                // Slow fields: a faint pool with slowly turning rings
+                for field in &self.slow_fields {
+                    let p = field.pos;
+                    draw_circle(p.x, p.y, field.radius, Color::from_rgba(90, 140, 255, 40));
+                    draw_circle_lines(p.x, p.y, field.radius, 2.0, Color::from_rgba(120, 170, 255, 120));
+                    let drift = if self.reduced_motion() { 0.0 } else { (self.time * 0.5).fract() };
+                    for ring in 0..2 {
+                        let r = field.radius * ((ring as f32 + drift) / 2.0);
+                        draw_circle_lines(p.x, p.y, r, 1.0, Color::from_rgba(120, 170, 255, 60));
+                    }
+                }
+            }
+            DrawLayer::Telegraphs => {
+                // Burst warnings: a ring closing in on the spot, blinking
+                // faster as the enemy is about to land
+                for telegraph in &self.telegraphs {
+                    let p = telegraph.pos;
+                    let progress = 1.0 - telegraph.life.fraction();
+                    let blink = if self.reduced_motion() { 1.0 } else { (self.time * (8.0 + 16.0 * progress)).sin() * 0.3 + 0.7 };
+                    let color = Color::new(1.0, 0.3, 0.25, 0.8 * blink);
+                    draw_circle_lines(p.x, p.y, TELEGRAPH_RADIUS * (1.0 - 0.6 * progress), 2.0, color);
+                    draw_line(p.x - 6.0, p.y, p.x + 6.0, p.y, 2.0, color);
+                    draw_line(p.x, p.y - 6.0, p.x, p.y + 6.0, 2.0, color);
+                }
+            }
+            DrawLayer::Mines => {
+                // Mines sit on the floor: dim while arming, pulsing red once live
+                let quality = self.config.quality;
+                for mine in self.mines.values() {
+                    if mine.arm_timer > 0.0 {
+                        draw_circle_lines(mine.pos.x, mine.pos.y, MINE_RADIUS, 2.0, Color::from_rgba(160, 160, 170, 150));
+                    } else {
+                        let pulse = if self.reduced_motion() { 1.0 } else { (self.time * 10.0 + mine.pos.x).sin() * 0.3 + 0.7 };
+                        draw_glow_circle(mine.pos, MINE_RADIUS + 6.0, 6.0, Color::new(1.0, 0.2, 0.15, 0.35 * pulse), quality.glow_layers(2));
+                        draw_circle(mine.pos.x, mine.pos.y, MINE_RADIUS, Color::new(0.9 * pulse, 0.15, 0.1, 1.0));
+                        draw_circle(mine.pos.x, mine.pos.y, 3.0, Color::from_rgba(255, 230, 200, 255));
+                    }
+                }
+            }
+            DrawLayer::EnemyTrails => {
+                // Enemy trails, under the enemies themselves
+                let quality = self.config.quality;
+                for seg in &self.enemy_trail {
+                    let color = Color { a: seg.life * 0.18, ..seg.color };
+                    let size = seg.size * 0.5 * seg.life;
+                    draw_glow_circle(seg.pos, size, size * 0.4, color, quality.glow_layers(2));
+                }
+            }
+            DrawLayer::Enemies => {
+                // Draw enemies
+                for enemy in self.enemies.values() {
+                    self.draw_enemy(enemy);
+                }
+            }
+            DrawLayer::SpecialRings => {
+                // Special rings
+                let layered_glow = self.config.quality.layered_glow();
+                for ring in &self.special_rings {
+                    let fade = 1.0 - ring.radius / ring.max_radius;
+                    if layered_glow {
+                        draw_circle_lines(ring.pos.x, ring.pos.y, ring.radius, 18.0, Color::new(0.5, 0.8, 1.0, fade * 0.3));
+                    }
+                    draw_circle_lines(ring.pos.x, ring.pos.y, ring.radius, 5.0, Color::new(0.8, 0.95, 1.0, fade));
+                }
+            }
+            DrawLayer::Explosions => {
+                // Draw explosions
+                let quality = self.config.quality;
+                for explosion in &self.explosions {
+                    let alpha = (explosion.life.remaining().min(1.0) * 255.0) as u8;
+                    let size = explosion.size;
+                    let ((gr, gg, gb), (cr, cg, cb)) = explosion.kind.colors();
+                    draw_glow_circle(explosion.pos, size * 0.7, size * 0.3, Color::from_rgba(gr, gg, gb, alpha), quality.glow_layers(2));
+                    draw_circle(explosion.pos.x, explosion.pos.y, size * 0.4, Color::from_rgba(cr, cg, cb, alpha));
+
+                    // Debris flies outward as the blast burns down
+                    let debris = explosion.kind.debris();
+                    let progress = 1.0 - (explosion.life.remaining() / explosion.kind.life()).min(1.0);
+                    for i in 0..debris {
+                        let angle = explosion.spin + i as f32 * std::f32::consts::TAU / debris as f32;
+                        let fleck = explosion.pos + Vec2::new(angle.cos(), angle.sin()) * size * (0.4 + 0.8 * progress);
+                        draw_circle(fleck.x, fleck.y, 2.5, Color::from_rgba(cr, cg, cb, alpha));
+                    }
+                    if explosion.kind == ExplosionKind::Large {
+                        draw_circle_lines(explosion.pos.x, explosion.pos.y, size, 2.0, Color::from_rgba(gr, gg, gb, alpha / 2));
+                    }
+                }
+            }
+            DrawLayer::FloatingTexts => {
+                // Draw score popups
+                for text in &self.floating_texts {
+                    let alpha = text.life.remaining().clamp(0.0, 1.0);
+                    let width = measure_text(&text.text, None, 22, 1.0).width;
+                    draw_text(
+                        &text.text,
+                        text.pos.x - width / 2.0,
+                        text.pos.y,
+                        22.0,
+                        Color::new(text.color.r, text.color.g, text.color.b, alpha),
+                    );
                 }
-            } else if is_mouse_button_down(MouseButton::Left) {
-                if mouse_pos.0 < left_side_x && self.left_joystick.active {
-                    self.left_joystick.on_touch_move(pos);
-                } else if mouse_pos.0 >= left_side_x && self.right_joystick.active {
-                    self.right_joystick.on_touch_move(pos);
+            }
+            DrawLayer::Players => {
+                // Draw enhanced players (unless game over)
+                if !self.game_over {
+                    for controller in self.players.iter().filter(|c| c.alive()) {
+                        self.draw_player(controller);
+                        self.draw_aim_indicator(controller);
+                    }
+                }
+            }
+            DrawLayer::Hitboxes => {
+                if self.dev_tools_enabled() && self.show_hitboxes {
+                    self.draw_hitboxes();
                 }
-            } else if is_mouse_button_released(MouseButton::Left) {
-                self.left_joystick.on_touch_end();
-                self.right_joystick.on_touch_end();
             }
         }
     }
-
-    pub fn draw(&self) {
-        // Deep space background
-        clear_background(Color::from_rgba(5, 5, 15, 255));
-
-        // Draw breathing particles
-        for particle in &self.particles {
-            draw_circle(
-                particle.pos.x,
-                particle.pos.y,
-                particle.size,
-                Color::from_rgba(100, 120, 200, (particle.alpha * 255.0) as u8),
-            );
+    
+    // Thin outlines of the circles collisions actually test, at simulation
+    // positions (not interpolated) so they show exactly what the last step saw
+    fn draw_hitboxes(&self) {
+        let pilot = Color::from_rgba(80, 255, 120, 220);
+        let hostile = Color::from_rgba(255, 90, 90, 220);
+        let neutral = Color::from_rgba(255, 230, 90, 220);
+        for controller in self.players.iter().filter(|c| c.alive()) {
+            let pos = to_mac_vec2(controller.player.position);
+            draw_circle_lines(pos.x, pos.y, controller.player.hitbox_radius, 1.0, pilot);
         }
-        
-        // Obstacles removed for cleaner gameplay
-
-        // Draw player trail
-        for seg in self.trail.iter() {
-            let alpha = (seg.life * 100.0) as u8;
-            let size = seg.size * seg.life;
-            
-            // Outer glow
-            draw_circle(
-                seg.pos.x,
-                seg.pos.y,
-                size,
-                Color::from_rgba(100, 200, 255, alpha / 3),
-            );
-            
-            // Inner glow
-            draw_circle(
-                seg.pos.x,
-                seg.pos.y,
-                size * 0.6,
-                Color::from_rgba(150, 220, 255, alpha / 2),
-            );
+        for enemy in self.enemies.values() {
+            draw_circle_lines(enemy.pos.x, enemy.pos.y, enemy.size, 1.0, hostile);
         }
-        
-        // Draw bullets
         for bullet in &self.bullets {
-            // Bullet glow
-            draw_circle(bullet.pos.x, bullet.pos.y, 8.0, Color::from_rgba(100, 255, 200, 150));
-            draw_circle(bullet.pos.x, bullet.pos.y, 5.0, Color::from_rgba(150, 255, 220, 255));
-            draw_circle(bullet.pos.x, bullet.pos.y, 2.0, Color::from_rgba(255, 255, 255, 255));
+            draw_circle_lines(bullet.pos.x, bullet.pos.y, BULLET_HIT_RADIUS, 1.0, pilot);
         }
-        
-        // Draw enemies
-        for enemy in &self.enemies {
-            self.draw_enemy(enemy);
+        for bullet in &self.enemy_bullets {
+            draw_circle_lines(bullet.pos.x, bullet.pos.y, ENEMY_BULLET_RADIUS, 1.0, hostile);
         }
-        
-        // Draw explosions
-        for explosion in &self.explosions {
-            let alpha = (explosion.life * 255.0) as u8;
-            draw_circle(
-                explosion.pos.x,
-                explosion.pos.y,
-                explosion.size,
-                Color::from_rgba(255, 150, 50, alpha / 2),
-            );
-            draw_circle(
-                explosion.pos.x,
-                explosion.pos.y,
-                explosion.size * 0.7,
-                Color::from_rgba(255, 200, 100, alpha),
-            );
-            draw_circle(
-                explosion.pos.x,
-                explosion.pos.y,
-                explosion.size * 0.4,
-                Color::from_rgba(255, 255, 200, alpha),
-            );
+        for mine in self.mines.values() {
+            draw_circle_lines(mine.pos.x, mine.pos.y, MINE_RADIUS, 1.0, hostile);
         }
-
-        // Draw enhanced player (unless game over)
-        if !self.game_over {
-            self.draw_player();
+        for power_up in self.power_ups.values() {
+            draw_circle_lines(power_up.pos.x, power_up.pos.y, POWER_UP_RADIUS, 1.0, neutral);
         }
-
+        for obstacle in &self.obstacles {
+            draw_circle_lines(obstacle.pos.x, obstacle.pos.y, obstacle.size, 1.0, neutral);
+        }
+    }
+    
+    fn draw_overlay(&self) {
         // Draw minimal joysticks (only when active, very transparent)
         if self.left_joystick.active && !self.game_over {
-            self.draw_minimal_joystick(&self.left_joystick, Color::from_rgba(100, 200, 255, 60));
+            self.draw_minimal_joystick(&self.left_joystick, Color::from_rgba(100, 200, 255, 255));
         }
         if self.right_joystick.active && !self.game_over {
-            self.draw_minimal_joystick(&self.right_joystick, Color::from_rgba(255, 100, 100, 60));
+            self.draw_minimal_joystick(&self.right_joystick, Color::from_rgba(255, 100, 100, 255));
+        }
+
+        // Danger vignette sits above the world but below the UI
+        if !self.game_over {
+            self.draw_danger_vignette();
+            if self.config.offscreen_indicators {
+                self.draw_offscreen_indicators();
+            }
+        }
+
+        if self.freeze_timer > 0.0 {
+            self.draw_frost_overlay();
+        }
+        if !self.game_over {
+            self.draw_heartbeat();
         }
 
         // Minimal UI - top corners only
         self.draw_ui();
+        if let Some(tutorial) = &self.tutorial {
+            self.draw_tutorial(tutorial);
+        }
+        self.draw_achievement_toast();
         
         // Intro fade
         if self.intro_alpha > 0.0 {
@@ -544,18 +4700,171 @@ impl GameState {
             );
         }
         
+        // Last stand fades toward the game-over overlay
+        if let Some(death) = &self.death {
+            let fade = (death.elapsed / DEATH_SEQUENCE_TIME).powi(2);
+            draw_rectangle(0.0, 0.0, screen_width(), screen_height(), Color::new(0.0, 0.0, 0.0, fade * 0.7));
+        }
+        
+        if self.paused {
+            self.draw_pause();
+        }
+        
         // Game Over screen
         if self.game_over {
             self.draw_game_over();
+            self.draw_ghost_path();
+        }
+        if self.screen == Screen::Victory {
+            self.draw_victory();
+        }
+    }
+    
+    // Light up the half of the screen the current step uses, with a hold meter
+    fn draw_tutorial(&self, tutorial: &Tutorial) {
+        let half = screen_width() / 2.0;
+        let move_on_left = !self.config.swap_sticks;
+        let (on_left, title, detail) = match tutorial.step {
+            TutorialStep::Move => (move_on_left, "MOVE", "Drag on this side to fly"),
+            TutorialStep::Aim => (!move_on_left, "AIM & SHOOT", "Drag on this side to aim - you fire automatically"),
+        };
+        let x = if on_left { 0.0 } else { half };
+        draw_rectangle(x, 0.0, half, screen_height(), Color::from_rgba(100, 180, 255, 28));
+        draw_rectangle_lines(x, 0.0, half, screen_height(), 3.0, Color::from_rgba(100, 180, 255, 120));
+        
+        let center = Vec2::new(x + half / 2.0, self.ui_area().center().y);
+        let title_width = measure_text(title, None, 48, 1.0).width;
+        draw_text(title, center.x - title_width / 2.0, center.y, 48.0, Color::from_rgba(150, 210, 255, 255));
+        let detail_width = measure_text(detail, None, 20, 1.0).width;
+        draw_text(detail, center.x - detail_width / 2.0, center.y + 32.0, 20.0, Color::from_rgba(200, 220, 240, 220));
+        
+        let bar_width = 160.0;
+        let fill = (tutorial.progress / TUTORIAL_HOLD_TIME).min(1.0);
+        draw_rectangle_lines(center.x - bar_width / 2.0, center.y + 50.0, bar_width, 8.0, 1.5, Color::from_rgba(150, 210, 255, 160));
+        draw_rectangle(center.x - bar_width / 2.0, center.y + 50.0, bar_width * fill, 8.0, Color::from_rgba(150, 210, 255, 220));
+        
+        let skip = self.tutorial_skip_rect();
+        draw_rectangle_lines(skip.x, skip.y, skip.w, skip.h, 2.0, Color::from_rgba(200, 200, 220, 160));
+        let label = "SKIP (Enter)";
+        let label_width = measure_text(label, None, 18, 1.0).width;
+        draw_text(label, skip.center().x - label_width / 2.0, skip.center().y + 6.0, 18.0, Color::from_rgba(200, 200, 220, 220));
+    }
+    
+    fn draw_pause(&self) {
+        draw_rectangle(0.0, 0.0, screen_width(), screen_height(), Color::from_rgba(0, 0, 0, 140));
+        if self.settings_open {
+            self.draw_settings();
+            return;
+        }
+        let center = self.ui_area().center();
+        let text = "PAUSED";
+        let width = measure_text(text, None, 70, 1.0).width;
+        draw_text(text, center.x - width / 2.0, center.y - 60.0, 70.0, Color::from_rgba(150, 210, 255, 255));
+        
+        let top = self.pause_menu_top();
+        let labels: Vec<&str> = PAUSE_MENU
+            .iter()
+            .map(|&(label, action)| match action {
+                PauseAction::SaveQuit if !self.can_save() => "SAVE & QUIT (N/A)",
+                _ => label,
+            })
+            .collect();
+        self.pause_menu.draw(top, &labels);
+        let hint = "Up/Down + Enter, 1-5, or click  -  P/ESC to resume";
+        let hint_width = measure_text(hint, None, 18, 1.0).width;
+        let hint_y = self.pause_menu.item_y(top, PAUSE_MENU.len()) + 10.0;
+        draw_text(hint, center.x - hint_width / 2.0, hint_y, 18.0, Color::from_rgba(200, 200, 220, 200));
+    }
+    
+    // Thin lines every `grid_spacing` pixels, shifted by the scroll offset
+    fn draw_grid(&self) {
+        let spacing = self.config.grid_spacing.max(8.0);
+        let [r, g, b, a] = self.config.grid_color;
+        let color = Color::from_rgba(r, g, b, a);
+        let (w, h) = (screen_width(), screen_height());
+        
+        let mut x = self.grid_offset.x;
+        while x < w {
+            draw_line(x, 0.0, x, h, 1.0, color);
+            x += spacing;
         }
+        let mut y = self.grid_offset.y;
+        while y < h {
+            draw_line(0.0, y, w, y, 1.0, color);
+            y += spacing;
+        }
+    }
+    
+    // Achievement toast: slides in from the bottom-right corner and back out
+    fn draw_achievement_toast(&self) {
+        let Some(toast) = self.achievements.toast() else {
+            return;
+        };
+        let slide_in = (toast.age / TOAST_SLIDE_TIME).min(1.0);
+        let slide_out = ((TOAST_TIME - toast.age) / TOAST_SLIDE_TIME).min(1.0);
+        let shown = slide_in.min(slide_out).max(0.0);
+        
+        let area = self.ui_area();
+        let (width, height) = (260.0, 54.0);
+        let x = screen_width() - (screen_width() - area.right() + width + 16.0) * shown;
+        let y = area.bottom() - height - 90.0;
+        draw_rectangle(x, y, width, height, Color::from_rgba(20, 25, 45, 220));
+        draw_rectangle_lines(x, y, width, height, 2.0, Color::from_rgba(255, 210, 90, 200));
+        
+        let heading = if toast.new_unlock { "ACHIEVEMENT UNLOCKED" } else { "ACHIEVEMENT" };
+        draw_text(heading, x + 12.0, y + 20.0, 16.0, Color::from_rgba(255, 210, 90, 255));
+        draw_text(toast.achievement.title(), x + 12.0, y + 42.0, 24.0, WHITE);
     }
     
     fn draw_enemy(&self, enemy: &Enemy) {
-        let pos = enemy.pos;
+        let pos = self.interpolate(enemy.prev_pos, enemy.pos);
         let rotation = enemy.rotation;
         let size = enemy.size;
         
-        // Enemy rocket - menacing red design
+        // Per-kind palette: (body, outline, glow)
+        let (body, outline, glow) = match enemy.kind {
+            EnemyKind::Drifter => ((180, 90, 255), (210, 160, 255), (160, 60, 255)),
+            EnemyKind::Chaser => ((255, 80, 80), (255, 150, 150), (255, 50, 50)),
+            EnemyKind::Splitter | EnemyKind::SplitterChild => ((255, 160, 40), (255, 210, 130), (255, 130, 20)),
+            EnemyKind::Boss => ((255, 60, 200), (255, 160, 230), (255, 40, 180)),
+            EnemyKind::Shooter => ((60, 220, 200), (150, 255, 240), (40, 200, 180)),
+            EnemyKind::Circler => ((230, 230, 70), (255, 255, 170), (220, 220, 40)),
+            EnemyKind::Support => ((90, 160, 255), (170, 210, 255), (70, 140, 255)),
+            EnemyKind::Layer => ((140, 230, 90), (200, 255, 160), (110, 210, 60)),
+            EnemyKind::Blinker => ((200, 120, 255), (240, 200, 255), (170, 90, 255)),
+            EnemyKind::Scavenger => ((230, 200, 60), (255, 240, 150), (255, 210, 40)),
+            EnemyKind::Carrier => ((90, 200, 170), (200, 255, 235), (120, 255, 210)),
+            EnemyKind::Mimic => ((150, 150, 175), (230, 230, 250), (190, 190, 230)),
+        };
+        let fade = enemy.visibility();
+        let alpha = |a: u8| (a as f32 * fade) as u8;
+        
+        // Frozen enemies are iced over
+        let (body, outline, glow) = if self.freeze_timer > 0.0 {
+            ((120, 180, 255), (220, 240, 255), (100, 170, 255))
+        } else {
+            (body, outline, glow)
+        };
+        
+        // Blend toward white right after a hit, and throb while staggered
+        // (a steady half-white for reduced motion)
+        let flash = if self.config.enemy_hit_flash > 0.0 && !self.reduced_motion() {
+            enemy.hit_flash_timer / self.config.enemy_hit_flash
+        } else {
+            0.0
+        };
+        let flash = match (enemy.stagger_timer > 0.0, self.reduced_motion()) {
+            (false, _) => flash,
+            (true, true) => flash.max(0.5),
+            (true, false) => flash.max((self.time * 20.0).sin() * 0.3 + 0.5),
+        };
+        let whiten = |(r, g, b): (u8, u8, u8)| {
+            let lift = |c: u8| (c as f32 + (255.0 - c as f32) * flash) as u8;
+            (lift(r), lift(g), lift(b))
+        };
+        let (body, outline) = (whiten(body), whiten(outline));
+        
+        // Enemy rocket - menacing design
         let front = Vec2::new(
             pos.x + rotation.cos() * size,
             pos.y + rotation.sin() * size,
@@ -571,48 +4880,264 @@ impl GameState {
             pos.y + (rotation - 2.5).sin() * size * 0.6,
         );
         
-        // Red glow
-        draw_circle(pos.x, pos.y, size + 15.0, Color::from_rgba(255, 50, 50, 40));
-        draw_circle(pos.x, pos.y, size + 8.0, Color::from_rgba(255, 80, 80, 80));
+        // Glow
+        let glow_layers = self.config.quality.glow_layers(2);
+        draw_glow_circle(pos, size + 8.0, 7.0, Color::from_rgba(glow.0, glow.1, glow.2, alpha(80)), glow_layers);
+        
+        // Body - Circlers are a spinning four-point blade instead of a rocket
+        if enemy.kind == EnemyKind::Circler {
+            let spin = self.time * 6.0;
+            draw_poly(pos.x, pos.y, 4, size, spin.to_degrees(), Color::from_rgba(body.0, body.1, body.2, 255));
+            draw_poly_lines(pos.x, pos.y, 4, size, spin.to_degrees() + 45.0, 2.0, Color::from_rgba(outline.0, outline.1, outline.2, 255));
+            if enemy.phase == CirclerPhase::Dive {
+                draw_line(pos.x, pos.y, front.x, front.y, 3.0, Color::from_rgba(255, 255, 255, 200));
+            }
+        } else if enemy.kind == EnemyKind::Blinker {
+            // Diamond that fades out and back in around each blink
+            draw_poly(pos.x, pos.y, 4, size, rotation.to_degrees(), Color::from_rgba(body.0, body.1, body.2, alpha(255)));
+            draw_poly_lines(pos.x, pos.y, 4, size, rotation.to_degrees(), 2.0, Color::from_rgba(outline.0, outline.1, outline.2, alpha(255)));
+        } else if enemy.kind == EnemyKind::Support {
+            // Hexagon with a faint ring marking its shield reach
+            let reach = self.config.support_shield_radius;
+            draw_circle_lines(pos.x, pos.y, reach, 1.0, Color::from_rgba(glow.0, glow.1, glow.2, 50));
+            draw_poly(pos.x, pos.y, 6, size, 0.0, Color::from_rgba(body.0, body.1, body.2, 255));
+            draw_poly_lines(pos.x, pos.y, 6, size, 0.0, 2.0, Color::from_rgba(outline.0, outline.1, outline.2, 255));
+        } else {
+            draw_triangle(front, left, right, Color::from_rgba(body.0, body.1, body.2, 255));
+            draw_triangle_lines(front, left, right, 2.0, Color::from_rgba(outline.0, outline.1, outline.2, 255));
+        }
+        
+        // Core; a Carrier's glows with the power-up it holds instead
+        if let Some(cargo) = enemy.cargo {
+            let (color, label) = cargo.look();
+            let pulse = if self.reduced_motion() { 1.0 } else { (self.time * 5.0).sin() * 0.2 + 0.8 };
+            draw_circle(pos.x, pos.y, size * 0.9, Color::new(color.r, color.g, color.b, 0.25 * pulse));
+            draw_poly(pos.x, pos.y, 4, size * 0.45, 45.0, color);
+            let width = measure_text(label, None, 14, 1.0).width;
+            draw_text(label, pos.x - width / 2.0, pos.y + 4.0, 14.0, Color::from_rgba(40, 30, 10, 255));
+        } else {
+            draw_circle(pos.x, pos.y, 4.0, Color::from_rgba(255, 220, 220, alpha(255)));
+        }
+        
+        // Shield bubble from a nearby Support
+        if enemy.shielded {
+            let pulse = if self.reduced_motion() { 1.0 } else { (self.time * 4.0).sin() * 0.15 + 0.85 };
+            draw_circle(pos.x, pos.y, size + 10.0, Color::new(0.4, 0.7, 1.0, 0.12 * pulse));
+            draw_circle_lines(pos.x, pos.y, size + 10.0, 2.0, Color::new(0.6, 0.85, 1.0, 0.7 * pulse));
+        }
+        
+        // Health bar for bosses, and for anything tough enough to need one
+        // once it's been hit
+        if enemy.kind == EnemyKind::Boss || (enemy.max_health > 2 && enemy.health < enemy.max_health) {
+            let bar_width = size * 2.0;
+            let fill = enemy.health.max(0) as f32 / enemy.max_health as f32;
+            let bar_x = pos.x - bar_width / 2.0;
+            let bar_height = (size * 0.1).clamp(3.0, 6.0);
+            let bar_y = pos.y - size * 1.3 - bar_height;
+            draw_rectangle(bar_x, bar_y, bar_width, bar_height, Color::from_rgba(0, 0, 0, 150));
+            draw_rectangle(bar_x, bar_y, bar_width * fill, bar_height, Color::from_rgba(255, 80, 200, 255));
+        }
+    }
+
+    // Icy border that thaws out over the last second of a freeze
+    fn draw_frost_overlay(&self) {
+        let strength = self.freeze_timer.min(1.0);
+        draw_edge_glow(Color::new(0.6, 0.85, 1.0, strength * 0.25), 40.0);
+        draw_rectangle(0.0, 0.0, screen_width(), screen_height(), Color::new(0.5, 0.75, 1.0, strength * 0.06));
+    }
+    
+    // Red edge throbbing in time with a heartbeat while a pilot is down to
+    // their last heart. Stops the moment they heal.
+    fn draw_heartbeat(&self) {
+        let critical = self.players.iter().any(|c| c.alive() && c.health <= 1);
+        if !critical || self.reduced_motion() {
+            return;
+        }
+        let beat = (self.time * HEARTBEAT_RATE * std::f32::consts::TAU).sin().max(0.0).powi(4);
+        draw_edge_glow(Color::new(1.0, 0.1, 0.1, 0.35 * beat), 60.0);
+    }
+
+    // Threat as 0.0 - 1.0: a lone distant enemy doesn't count, ~4 close
+    // ones are the most
+    fn danger(&self) -> f32 {
+        ((self.threat - 0.5) / 3.5).clamp(0.0, 1.0)
+    }
+    
+    // Configured zoom, let out toward the whole arena by speed or danger
+    fn target_zoom(&self) -> f32 {
+        let zoom = self.config.zoom.clamp(1.0, MAX_ZOOM);
+        let speed = self.players.first().map_or(0.0, |lead| lead.player.velocity.length());
+        let urgency = (speed / ZOOM_FAST_SPEED).min(1.0).max(self.danger());
+        1.0 + (zoom - 1.0) * (1.0 - self.config.dynamic_zoom.clamp(0.0, 1.0) * urgency)
+    }
+    
+    // World position under the mouse cursor
+    fn cursor_world(&self) -> Vec2 {
+        self.world_camera().screen_to_world(Vec2::from(mouse_position()))
+    }
+    
+    fn draw_danger_vignette(&self) {
+        let intensity = self.danger();
+        if intensity <= 0.0 {
+            return;
+        }
+        
+        let pulse = if self.reduced_motion() { 1.0 } else { (self.time * 6.0).sin() * 0.25 + 0.75 };
+        let max_alpha = intensity * pulse * 90.0;
+        let w = screen_width();
+        let h = screen_height();
+        
+        // Stacked edge strips fake a soft gradient toward the center
+        let layers = 6;
+        let depth = 60.0;
+        for i in 0..layers {
+            let inset = i as f32 * depth / layers as f32;
+            let thickness = depth / layers as f32;
+            let alpha = (max_alpha * (1.0 - i as f32 / layers as f32)) as u8;
+            let color = Color::from_rgba(255, 30, 30, alpha);
+            draw_rectangle(inset, inset, w - inset * 2.0, thickness, color);
+            draw_rectangle(inset, h - inset - thickness, w - inset * 2.0, thickness, color);
+            draw_rectangle(inset, inset + thickness, thickness, h - (inset + thickness) * 2.0, color);
+            draw_rectangle(w - inset - thickness, inset + thickness, thickness, h - (inset + thickness) * 2.0, color);
+        }
+    }
+
+    fn draw_ghost_ship(&self, pos: Vec2, rotation: f32) {
+        let size = 40.0;
+        let front = pos + Vec2::new(rotation.cos(), rotation.sin()) * size;
+        let left = pos + Vec2::new((rotation + 2.3).cos(), (rotation + 2.3).sin()) * size * 0.7;
+        let right = pos + Vec2::new((rotation - 2.3).cos(), (rotation - 2.3).sin()) * size * 0.7;
+        
+        draw_triangle(front, left, right, Color::from_rgba(200, 200, 255, 35));
+        draw_triangle_lines(front, left, right, 1.5, Color::from_rgba(220, 220, 255, 70));
+    }
+
+    // Faint replay of the run that just ended, drawn over the game-over overlay
+    fn draw_ghost_path(&self) {
+        let mut previous: Option<Vec2> = None;
+        for sample in self.ghost.samples() {
+            if let Some(prev) = previous {
+                // Skip the jump across the screen when the player wrapped
+                if (sample.pos - prev).length() < 200.0 {
+                    draw_line(prev.x, prev.y, sample.pos.x, sample.pos.y, 2.0, Color::from_rgba(200, 200, 255, 40));
+                }
+            }
+            previous = Some(sample.pos);
+        }
         
-        // Body
-        draw_triangle(front, left, right, Color::from_rgba(255, 80, 80, 255));
-        draw_triangle_lines(front, left, right, 2.0, Color::from_rgba(255, 150, 150, 255));
+        // A ghost ship loops along the recorded path
+        if let (Some(first), Some(last)) = (self.ghost.samples().next(), self.ghost.samples().last()) {
+            let span = (last.time - first.time).max(0.001);
+            // Simulation time is frozen on game over, so animate off the wall clock
+            let replay_time = first.time + (get_time() as f32 * 2.0) % span;
+            if let Some(sample) = self.ghost.sample_at(replay_time) {
+                self.draw_ghost_ship(sample.pos, sample.rotation);
+            }
+        }
+    }
+
+    // Arrows pinned to the screen border pointing at enemies outside the view
+    fn draw_offscreen_indicators(&self) {
+        let w = screen_width();
+        let h = screen_height();
+        let center = Vec2::new(w / 2.0, h / 2.0);
+        let margin = 18.0;
+        let camera = self.world_camera();
         
-        // Core
-        draw_circle(pos.x, pos.y, 4.0, Color::from_rgba(255, 200, 200, 255));
+        for enemy in self.enemies.values() {
+            // Judged in screen space, so a zoomed-in view marks enemies
+            // that are still inside the arena
+            let pos = camera.world_to_screen(enemy.pos);
+            let on_screen = pos.x >= 0.0 && pos.x <= w && pos.y >= 0.0 && pos.y <= h;
+            if on_screen {
+                continue;
+            }
+            
+            // Scale the center->enemy ray until it touches the inset border
+            let dir = pos - center;
+            let half = Vec2::new(w / 2.0 - margin, h / 2.0 - margin);
+            let scale_x = if dir.x != 0.0 { half.x / dir.x.abs() } else { f32::MAX };
+            let scale_y = if dir.y != 0.0 { half.y / dir.y.abs() } else { f32::MAX };
+            let edge = center + dir * scale_x.min(scale_y);
+            
+            // Fade out as the enemy gets further beyond the edge
+            let beyond = (pos - edge).length();
+            let alpha = ((1.0 - beyond / 400.0).clamp(0.2, 1.0) * 200.0) as u8;
+            
+            let angle = dir.y.atan2(dir.x);
+            let tip = edge + Vec2::new(angle.cos(), angle.sin()) * 10.0;
+            let left = edge + Vec2::new((angle + 2.5).cos(), (angle + 2.5).sin()) * 10.0;
+            let right = edge + Vec2::new((angle - 2.5).cos(), (angle - 2.5).sin()) * 10.0;
+            draw_triangle(tip, left, right, Color::from_rgba(255, 90, 90, alpha));
+        }
     }
 
+    // Base ring and thumb in `color`, at the configured size and opacities
     fn draw_minimal_joystick(&self, joystick: &Joystick, color: Color) {
+        let style = self.config.sticks;
         let center = to_mac_vec2(joystick.center);
         let current = to_mac_vec2(joystick.current);
 
         // Very subtle outer ring
-        draw_circle_lines(center.x, center.y, joystick.radius, 1.0, color);
+        draw_circle_lines(center.x, center.y, joystick.radius, 1.0, Color { a: style.base_opacity, ..color });
 
-        // Thumb indicator
-        draw_circle(current.x, current.y, 20.0, color);
+        // Thumb indicator, with a brighter core
+        let thumb = style.thumb_radius;
+        draw_circle(current.x, current.y, thumb, Color { a: style.thumb_opacity, ..color });
         draw_circle(
             current.x,
             current.y,
-            15.0,
-            Color::from_rgba(255, 255, 255, 100),
+            thumb * 0.75,
+            Color::new(1.0, 1.0, 1.0, (style.thumb_opacity * 1.65).min(1.0)),
         );
     }
 
-    fn draw_player(&self) {
-        let pos = to_mac_vec2(self.player.position);
-        let rotation = self.player.rotation;
+    // Second seat flies orange, or blue if the lead already picked orange
+    fn seat_color(&self, seat: usize) -> ShipColor {
+        let lead = self.config.ship_color;
+        match (seat % 2, lead) {
+            (0, _) => lead,
+            (_, ShipColor::Orange) => ShipColor::Blue,
+            _ => ShipColor::Orange,
+        }
+    }
+    
+    fn draw_player(&self, controller: &PlayerController) {
+        let pos = self.interpolate(to_mac_vec2(controller.prev_position), to_mac_vec2(controller.player.position));
+        let rotation = controller.player.rotation;
+        
+        // Every hull shade derives from the seat's paint: `lift` > 0 mixes
+        // toward white, < 0 toward black. The hit blink also whitens it.
+        let theme = self.seat_color(controller.seat);
+        let (r, g, b) = theme.base();
+        let steady = self.reduced_motion();
+        let hit = if controller.invuln_timer > 0.0 && !steady {
+            (controller.invuln_timer * 30.0).sin() * 0.25 + 0.25
+        } else {
+            0.0
+        };
+        let shade = |lift: f32, a: u8| {
+            let channel = |c: u8| {
+                let c = c as f32;
+                let v = if lift >= 0.0 { c + (255.0 - c) * lift } else { c * (1.0 + lift) };
+                (v + (255.0 - v) * hit) as u8
+            };
+            Color::from_rgba(channel(r), channel(g), channel(b), a)
+        };
 
-        // Flash if recently hit
-        let flash = if self.safe_time > 0.0 && self.safe_time < 0.3 {
+        // Flash if recently hit (a steady fade instead of a strobe for reduced motion)
+        let flash = if steady && (controller.invuln_timer > 0.0 || (self.safe_time > 0.0 && self.safe_time < 0.3)) {
+            140
+        } else if controller.invuln_timer > 0.0 {
+            ((controller.invuln_timer * 30.0).sin() * 127.0 + 128.0) as u8
+        } else if self.safe_time > 0.0 && self.safe_time < 0.3 {
             ((self.safe_time * 30.0).sin() * 127.0 + 128.0) as u8
         } else {
             255
         };
 
-        // Player size - BIGGER and more imposing
-        let size = 40.0;
+        // Player size - BIGGER and more imposing (hitbox is much smaller)
+        let size = VISUAL_SIZE;
         
         // Calculate ship points - sleeker design
         let front = Vec2::new(
@@ -645,60 +5170,90 @@ impl GameState {
         );
 
         // Massive outer glow - makes it feel powerful
-        draw_circle(pos.x, pos.y, size + 30.0, Color::from_rgba(80, 180, 255, 20));
-        draw_circle(pos.x, pos.y, size + 20.0, Color::from_rgba(100, 200, 255, 40));
-        draw_circle(pos.x, pos.y, size + 10.0, Color::from_rgba(120, 220, 255, 70));
+        draw_glow_circle(pos, size + 10.0, 10.0, shade(0.0, 70), self.config.quality.glow_layers(3));
+        
+        // Overdrive burns white-hot around the hull (steady for reduced motion)
+        let power = self.overdrive_power();
+        if power > 0.0 {
+            let flicker = if steady { 1.0 } else { (self.time * 12.0).sin() * 0.2 + 0.8 };
+            let heat = Color::new(1.0, 0.75, 0.3, 0.35 * power * flicker);
+            draw_glow_circle(pos, size + 18.0, 14.0, heat, self.config.quality.glow_layers(3));
+        }
         
-        // Engine flames FIRST (so they're behind ship)
-        let movement = self.left_joystick.get_input();
+        // Engine flames FIRST (so they're behind ship). Movement is independent
+        // of facing, so split the thrust into forward and sideways parts:
+        // main engines push forward, nose retros brake, wing jets strafe.
+        let movement = controller.movement;
         if movement.x.abs() > 0.1 || movement.y.abs() > 0.1 {
-            let thrust_power = (movement.x * movement.x + movement.y * movement.y).sqrt();
-            let flame_length = thrust_power * 35.0;
-            let pulse = (self.time * 15.0).sin() * 0.2 + 0.8;
-            
-            // Left engine flame
-            let flame_left = Vec2::new(
-                back_left.x - rotation.cos() * 10.0,
-                back_left.y - rotation.sin() * 10.0,
-            );
-            self.draw_engine_flame(flame_left, rotation, flame_length * pulse, thrust_power);
+            let thrust_power = (movement.x * movement.x + movement.y * movement.y).sqrt().min(1.0);
+            let relative = movement.y.atan2(movement.x) - rotation;
+            let forward = relative.cos() * thrust_power;
+            let lateral = relative.sin() * thrust_power;
+            let flame_length = 35.0;
+            let pulse = if steady { 1.0 } else { (self.time * 15.0).sin() * 0.2 + 0.8 };
+            let facing = Vec2::new(rotation.cos(), rotation.sin());
             
-            // Right engine flame
-            let flame_right = Vec2::new(
-                back_right.x - rotation.cos() * 10.0,
-                back_right.y - rotation.sin() * 10.0,
-            );
-            self.draw_engine_flame(flame_right, rotation, flame_length * pulse * 0.95, thrust_power);
+            if forward > 0.1 {
+                // Left, right and center (main thrust) engines
+                let length = flame_length * forward * pulse;
+                self.draw_engine_flame(back_left - facing * 10.0, rotation, length, forward, theme);
+                self.draw_engine_flame(back_right - facing * 10.0, rotation, length * 0.95, forward, theme);
+                self.draw_engine_flame(back_center - facing * 5.0, rotation, length * 1.2, forward, theme);
+            } else if forward < -0.1 {
+                // Retro-thrusters fire out past the nose
+                let braking = -forward;
+                let side = Vec2::new(-facing.y, facing.x) * size * 0.2;
+                let nose = pos + facing * size * 0.6;
+                let length = flame_length * 0.7 * braking * pulse;
+                self.draw_engine_flame(nose + side, rotation + std::f32::consts::PI, length, braking, theme);
+                self.draw_engine_flame(nose - side, rotation + std::f32::consts::PI, length, braking, theme);
+            }
             
-            // Center engine flame (main thrust)
-            let flame_center = Vec2::new(
-                back_center.x - rotation.cos() * 5.0,
-                back_center.y - rotation.sin() * 5.0,
-            );
-            self.draw_engine_flame(flame_center, rotation, flame_length * pulse * 1.2, thrust_power);
+            if lateral.abs() > 0.1 {
+                // Strafe jet on the wing opposite the push
+                let push_angle = rotation + std::f32::consts::FRAC_PI_2 * lateral.signum();
+                let push = Vec2::new(push_angle.cos(), push_angle.sin());
+                let length = flame_length * 0.7 * lateral.abs() * pulse;
+                self.draw_engine_flame(pos - push * size * 0.45, push_angle, length, lateral.abs(), theme);
+            }
+        }
+        
+        // Motion blur: faded copies of the hull trailing back along the
+        // velocity, stronger the faster the ship goes
+        let copies = self.config.quality.motion_blur_copies();
+        let speed = controller.player.velocity.length();
+        let blur = ((speed - MOTION_BLUR_MIN_SPEED) / (MOTION_BLUR_FULL_SPEED - MOTION_BLUR_MIN_SPEED)).clamp(0.0, 1.0);
+        if copies > 0 && blur > 0.0 && !steady {
+            let back = -to_mac_vec2(controller.player.velocity) * MOTION_BLUR_SPAN / copies as f32;
+            for i in 1..=copies {
+                let offset = back * i as f32;
+                let alpha = (flash as f32 * 0.35 * blur * (1.0 - i as f32 / (copies + 1) as f32)) as u8;
+                draw_triangle(front + offset, left_wing + offset, right_wing + offset, shade(0.15, alpha));
+                draw_triangle(left_wing + offset, right_wing + offset, back_center + offset, shade(-0.05, alpha));
+            }
         }
         
         // Ship shadow/depth layer (darker)
-        draw_triangle(front, left_wing, back_left, Color::from_rgba(60, 120, 180, flash));
-        draw_triangle(front, right_wing, back_right, Color::from_rgba(60, 120, 180, flash));
+        draw_triangle(front, left_wing, back_left, shade(-0.45, flash));
+        draw_triangle(front, right_wing, back_right, shade(-0.45, flash));
         
         // Main ship body (brighter)
-        draw_triangle(front, left_wing, right_wing, Color::from_rgba(140, 210, 255, flash));
-        draw_triangle(left_wing, right_wing, back_center, Color::from_rgba(120, 190, 240, flash));
+        draw_triangle(front, left_wing, right_wing, shade(0.15, flash));
+        draw_triangle(left_wing, right_wing, back_center, shade(-0.05, flash));
         
         // Cockpit window (glowing)
         let cockpit = Vec2::new(
             pos.x + rotation.cos() * size * 0.5,
             pos.y + rotation.sin() * size * 0.5,
         );
-        draw_circle(cockpit.x, cockpit.y, 6.0, Color::from_rgba(100, 220, 255, 180));
-        draw_circle(cockpit.x, cockpit.y, 4.0, Color::from_rgba(180, 240, 255, flash));
+        draw_circle(cockpit.x, cockpit.y, 6.0, shade(0.0, 180));
+        draw_circle(cockpit.x, cockpit.y, 4.0, shade(0.5, flash));
         
         // Wing edges (sharp glowing lines)
-        draw_line(front.x, front.y, left_wing.x, left_wing.y, 3.0, Color::from_rgba(200, 240, 255, flash));
-        draw_line(front.x, front.y, right_wing.x, right_wing.y, 3.0, Color::from_rgba(200, 240, 255, flash));
-        draw_line(left_wing.x, left_wing.y, back_left.x, back_left.y, 2.0, Color::from_rgba(180, 230, 255, flash));
-        draw_line(right_wing.x, right_wing.y, back_right.x, back_right.y, 2.0, Color::from_rgba(180, 230, 255, flash));
+        draw_line(front.x, front.y, left_wing.x, left_wing.y, 3.0, shade(0.65, flash));
+        draw_line(front.x, front.y, right_wing.x, right_wing.y, 3.0, shade(0.65, flash));
+        draw_line(left_wing.x, left_wing.y, back_left.x, back_left.y, 2.0, shade(0.5, flash));
+        draw_line(right_wing.x, right_wing.y, back_right.x, back_right.y, 2.0, shade(0.5, flash));
         
         // Energy lines on wings (detail)
         let wing_line_left = Vec2::new(
@@ -709,18 +5264,69 @@ impl GameState {
             pos.x + (rotation - 2.0).cos() * size * 0.5,
             pos.y + (rotation - 2.0).sin() * size * 0.5,
         );
-        draw_line(pos.x, pos.y, wing_line_left.x, wing_line_left.y, 2.0, Color::from_rgba(100, 200, 255, 150));
-        draw_line(pos.x, pos.y, wing_line_right.x, wing_line_right.y, 2.0, Color::from_rgba(100, 200, 255, 150));
+        draw_line(pos.x, pos.y, wing_line_left.x, wing_line_left.y, 2.0, shade(-0.1, 150));
+        draw_line(pos.x, pos.y, wing_line_right.x, wing_line_right.y, 2.0, shade(-0.1, 150));
         
         // Nose tip (bright point)
         draw_circle(front.x, front.y, 4.0, Color::from_rgba(255, 255, 255, flash));
-        draw_circle(front.x, front.y, 2.0, Color::from_rgba(180, 240, 255, flash));
+        draw_circle(front.x, front.y, 2.0, shade(0.5, flash));
         
         // Core center glow
-        draw_circle(pos.x, pos.y, 5.0, Color::from_rgba(200, 240, 255, 200));
+        draw_circle(pos.x, pos.y, 5.0, shade(0.65, 200));
+        
+        // Focus shows the true hitbox so dodges can be threaded precisely
+        if controller.focused {
+            draw_circle_lines(pos.x, pos.y, controller.player.hitbox_radius, 1.5, Color::from_rgba(255, 255, 255, 160));
+            draw_circle(pos.x, pos.y, 4.0, WHITE);
+        }
+        
+        // Ram glow while dashing
+        if controller.dash_timer > 0.0 {
+            let (r, g, b) = theme.flame();
+            let alpha = (controller.dash_timer / DASH_TIME * 200.0) as u8;
+            draw_circle_lines(pos.x, pos.y, size * 0.8, 3.0, Color::from_rgba(r, g, b, alpha));
+        }
+        
+        // Parry shield ring, brighter on a successful reflect
+        if controller.parry_timer > 0.0 || controller.parry_flash > 0.0 {
+            let alpha = if controller.parry_flash > 0.0 { 255 } else { 140 };
+            draw_circle_lines(pos.x, pos.y, PARRY_RADIUS, 3.0, Color::from_rgba(255, 230, 140, alpha));
+            if controller.parry_flash > 0.0 {
+                draw_circle(pos.x, pos.y, PARRY_RADIUS, Color::from_rgba(255, 240, 180, (controller.parry_flash * 400.0) as u8));
+            }
+        }
+    }
+    
+    // Where shots will go, while the aim stick is held
+    fn draw_aim_indicator(&self, controller: &PlayerController) {
+        if controller.control != Control::TwinStick || !self.right_joystick.active {
+            return;
+        }
+        let pos = self.interpolate(to_mac_vec2(controller.prev_position), to_mac_vec2(controller.player.position));
+        let rotation = controller.player.rotation;
+        let facing = Vec2::new(rotation.cos(), rotation.sin());
+        let color = Color::from_rgba(200, 240, 255, 70);
+        
+        match self.config.aim_indicator {
+            AimIndicator::Line => {
+                let start = pos + facing * (VISUAL_SIZE + 10.0);
+                let end = pos + facing * AIM_LINE_LENGTH;
+                draw_line(start.x, start.y, end.x, end.y, 1.5, color);
+            }
+            AimIndicator::Reticle => {
+                let at = pos + facing * AIM_RETICLE_DISTANCE;
+                draw_circle_lines(at.x, at.y, 10.0, 1.5, color);
+                draw_line(at.x - 16.0, at.y, at.x - 6.0, at.y, 1.5, color);
+                draw_line(at.x + 6.0, at.y, at.x + 16.0, at.y, 1.5, color);
+                draw_line(at.x, at.y - 16.0, at.x, at.y - 6.0, 1.5, color);
+                draw_line(at.x, at.y + 6.0, at.x, at.y + 16.0, 1.5, color);
+            }
+            AimIndicator::None => {}
+        }
     }
     
-    fn draw_engine_flame(&self, pos: Vec2, rotation: f32, length: f32, power: f32) {
+    fn draw_engine_flame(&self, pos: Vec2, rotation: f32, length: f32, power: f32, theme: ShipColor) {
+        let (fr, fg, fb) = theme.flame();
         let flame_back = Vec2::new(
             pos.x - rotation.cos() * length,
             pos.y - rotation.sin() * length,
@@ -738,7 +5344,7 @@ impl GameState {
                 pos,
                 flame_back,
                 flame_point,
-                Color::from_rgba(255, 100, 30, (power * 100.0) as u8),
+                Color::from_rgba(fr, fg, fb, (power * 100.0) as u8),
             );
         }
         
@@ -749,30 +5355,174 @@ impl GameState {
         draw_line(pos.x, pos.y, flame_back.x, flame_back.y, length * 0.15, Color::from_rgba(255, 255, 200, (power * 255.0) as u8));
         
         // Flame tip glow
-        draw_circle(flame_back.x, flame_back.y, length * 0.2, Color::from_rgba(255, 150, 50, (power * 80.0) as u8));
+        draw_circle(flame_back.x, flame_back.y, length * 0.2, Color::from_rgba(fr, fg, fb, (power * 80.0) as u8));
         draw_circle(flame_back.x, flame_back.y, length * 0.1, Color::from_rgba(255, 220, 100, (power * 150.0) as u8));
     }
     
+    // Screen minus the configured safe-area insets; all HUD anchors live in here
+    fn ui_area(&self) -> Rect {
+        safe_rect(self.config.safe_area, Vec2::new(screen_width(), screen_height()))
+    }
+    
     fn draw_ui(&self) {
+        let area = self.ui_area();
+        let cx = area.x + area.w / 2.0;
+        
         // Top-center: Score
-        let score_text = format!("SCORE: {}", self.score);
+        let score_text = format!("SCORE: {}", self.displayed_score.round() as i32);
         let font_size = 30.0;
         let score_width = measure_text(&score_text, None, font_size as u16, 1.0).width;
         
         draw_text(
             &score_text,
-            (screen_width() - score_width) / 2.0,
-            40.0,
+            cx - score_width / 2.0,
+            area.y + 40.0,
             font_size,
             Color::from_rgba(100, 255, 150, 255),
         );
         
-        // Timer on player rocket (if not game over)
-        if !self.game_over {
-            let player_pos = to_mac_vec2(self.player.position);
-            let minutes = (self.time / 60.0) as i32;
-            let seconds = (self.time % 60.0) as i32;
-            let time_text = format!("{:02}:{:02}", minutes, seconds);
+        // Wave number under the score
+        if self.waves.wave() > 0 {
+            let wave_text = format!("WAVE {}", self.waves.wave());
+            let wave_width = measure_text(&wave_text, None, 20, 1.0).width;
+            draw_text(
+                &wave_text,
+                cx - wave_width / 2.0,
+                area.y + 65.0,
+                20.0,
+                Color::from_rgba(150, 200, 255, 255),
+            );
+        }
+        
+        // Quiet reminder, opposite the enrage warning, that the hitless bonus is still paying out
+        if self.hitless && self.game_started && !self.sandbox() {
+            let text = "HITLESS";
+            let width = measure_text(text, None, 16, 1.0).width;
+            draw_text(text, cx - width / 2.0 - 90.0, area.y + 65.0, 16.0, Color::from_rgba(255, 215, 120, 150));
+        }
+        
+        // Subtle enrage warning beside the wave number
+        let enrage = self.enrage();
+        if enrage > 0.0 {
+            let pulse = if self.reduced_motion() { 1.0 } else { (self.time * 6.0).sin() * 0.3 + 0.7 };
+            let text = "ENRAGED";
+            let width = measure_text(text, None, 16, 1.0).width;
+            draw_text(
+                text,
+                cx - width / 2.0 + 90.0,
+                area.y + 65.0,
+                16.0,
+                Color::new(1.0, 0.3, 0.2, pulse * (0.4 + 0.6 * enrage)),
+            );
+        }
+        
+        // Combo counter under the wave
+        if self.combo > 1 {
+            let combo_text = format!("COMBO x{}  ({}x score)", self.combo, self.combo_multiplier());
+            let combo_width = measure_text(&combo_text, None, 20, 1.0).width;
+            draw_text(
+                &combo_text,
+                cx - combo_width / 2.0,
+                area.y + 88.0,
+                20.0,
+                Color::from_rgba(255, 200, 100, 255),
+            );
+        }
+        
+        // Streak announcement - big but brief, above the play area center
+        if let Some(announcement) = &self.announcement {
+            let size = 50.0 + announcement.tier as f32 * 8.0;
+            let width = measure_text(&announcement.text, None, size as u16, 1.0).width;
+            let alpha = (announcement.life / 0.4).min(1.0);
+            draw_text(
+                &announcement.text,
+                cx - width / 2.0,
+                area.y + area.h * 0.3,
+                size,
+                Color::new(1.0, 0.6 + 0.1 * announcement.tier as f32, 0.3, alpha),
+            );
+        }
+        
+        // Hearts in the top corners - even seats left, odd seats right
+        for controller in &self.players {
+            let y = heart_center(area, controller.seat, 0).y;
+            // Whatever's left throbs while a lost heart breaks apart
+            let losing = controller.heart_fx.iter().find(|fx| !fx.gained);
+            let pulse = match losing {
+                Some(fx) if !self.reduced_motion() => 1.0 + 0.15 * (fx.age / HEART_LOSS_TIME * std::f32::consts::PI).sin(),
+                _ => 1.0,
+            };
+            for h in 0..max_health(self.config.mode) {
+                let center = heart_center(area, controller.seat, h);
+                match controller.heart_fx.iter().find(|fx| fx.slot == h) {
+                    Some(fx) if fx.gained => self.draw_heart_gain(center, fx.age / HEART_GAIN_TIME),
+                    Some(fx) => self.draw_heart_loss(center, fx.age / HEART_LOSS_TIME, h),
+                    None if h < controller.health => self.draw_heart(center, pulse, HEART_COLOR),
+                    None => self.draw_heart(center, 1.0, HEART_EMPTY_COLOR),
+                }
+            }
+            
+            // Team spare lives follow the first pilot's hearts
+            if controller.seat == 0 && self.config.extra_lives && self.extra_lives > 0 {
+                let x = area.x + 30.0 * PLAYER_HEALTH as f32 + 15.0;
+                draw_text(&format!("+{}", self.extra_lives), x, y + 7.0, 24.0, Color::from_rgba(255, 120, 140, 255));
+            }
+            
+            // Active power-up timers under the hearts
+            let timers = [
+                (PowerUpKind::Pierce, controller.pierce_timer, Color::from_rgba(255, 220, 80, 255)),
+                (PowerUpKind::Mortar, controller.mortar_timer, Color::from_rgba(255, 150, 90, 255)),
+            ];
+            for (row, (kind, timer, color)) in timers.into_iter().filter(|(_, timer, _)| *timer > 0.0).enumerate() {
+                let text = format!("{} {:.0}s", kind.label(), timer.ceil());
+                let x = if controller.seat % 2 == 0 {
+                    area.x + 18.0
+                } else {
+                    area.right() - 18.0 - measure_text(&text, None, 18, 1.0).width
+                };
+                draw_text(&text, x, y + 30.0 + row as f32 * 20.0, 18.0, color);
+            }
+        }
+        
+        // Weapon level, bottom left
+        let max_level = self.config.weapon_levels.len() - 1;
+        let weapon_text = if self.weapon_level == max_level {
+            "WEAPON MAX".to_owned()
+        } else {
+            format!("WEAPON LV {}", self.weapon_level + 1)
+        };
+        let weapon_text = format!("{}  -  {}", self.config.loadout.name().to_uppercase(), weapon_text);
+        draw_text(&weapon_text, area.x + 18.0, area.bottom() - 22.0, 18.0, Color::from_rgba(120, 255, 140, 230));
+        
+        // Special meter, bottom center
+        let bar_width = 200.0;
+        let bar_x = cx - bar_width / 2.0;
+        let bar_y = area.bottom() - 30.0;
+        let ready = self.special_meter >= 1.0;
+        draw_rectangle(bar_x, bar_y, bar_width, 8.0, Color::from_rgba(0, 0, 0, 150));
+        let fill_color = if ready {
+            let pulse = if self.reduced_motion() { 1.0 } else { (self.time * 8.0).sin() * 0.2 + 0.8 };
+            Color::new(0.6 * pulse, 0.9 * pulse, 1.0, 1.0)
+        } else {
+            Color::from_rgba(90, 150, 230, 255)
+        };
+        draw_rectangle(bar_x, bar_y, bar_width * self.special_meter, 8.0, fill_color);
+        let label = if ready { "SPECIAL READY" } else { "SPECIAL" };
+        let label_width = measure_text(label, None, 16, 1.0).width;
+        draw_text(label, cx - label_width / 2.0, bar_y - 4.0, 16.0, fill_color);
+        
+        // Timer on the lead player rocket (if not game over; sandbox has no clock)
+        let lead = self.players.iter().find(|c| c.alive());
+        if let (false, false, Some(lead)) = (self.game_over, self.sandbox(), lead) {
+            let player_pos = to_mac_vec2(lead.player.position);
+            // Boss rush is raced against the clock, so it gets the split timer
+            let time_text = if self.config.mode == GameMode::BossRush {
+                split_text(self.run_clock)
+            } else {
+                let minutes = (self.time / 60.0) as i32;
+                let seconds = (self.time % 60.0) as i32;
+                format!("{:02}:{:02}", minutes, seconds)
+            };
             
             let timer_font_size = 20.0;
             let text_width = measure_text(&time_text, None, timer_font_size as u16, 1.0).width;
@@ -800,37 +5550,377 @@ impl GameState {
             );
         }
         
-        // Safe period indicator
-        if self.safe_time > 0.0 && self.game_started {
-            let safe_text = "Safe Zone";
-            let safe_width = measure_text(safe_text, None, 25 as u16, 1.0).width;
-            let alpha = ((self.safe_time * 3.0).sin() * 127.0 + 128.0) as u8;
-            
-            draw_text(
-                safe_text,
-                (screen_width() - safe_width) / 2.0,
-                screen_height() / 2.0 - 100.0,
-                25.0,
-                Color::from_rgba(100, 255, 150, alpha),
-            );
+        // Wave banner: countdown during the break, flourish as it starts
+        if self.screen == Screen::Playing && !self.game_over && self.safe_time <= 0.0 {
+            match &self.wave_clear {
+                Some(clear) => self.draw_wave_clear(clear),
+                None => self.draw_wave_banner(),
+            }
+        }
+        
+        // Developer overlay
+        if self.dev_tools_enabled() && self.dev_overlay {
+            let dev_text = format!(
+                "FPS {}  wave {}  enemies {}  accuracy {:.0}%{}",
+                get_fps(),
+                self.waves.wave(),
+                self.enemies.len(),
+                self.stats.accuracy() * 100.0,
+                if self.god_mode { "  [GOD MODE]" } else { "" },
+            );
+            draw_text(&dev_text, area.x + 10.0, area.bottom() - 10.0, 18.0, Color::from_rgba(180, 180, 180, 200));
+            if let Some(lead) = self.players.first() {
+                let pilot_text = format!("pos {}  vel {}", lead.player.position, lead.player.velocity);
+                draw_text(&pilot_text, area.x + 10.0, area.bottom() - 30.0, 18.0, Color::from_rgba(180, 180, 180, 200));
+            }
+        }
+        
+        // Hardcore badge: no one should forget what's at stake
+        if self.config.mode == GameMode::Hardcore {
+            let text = "HARDCORE";
+            let width = measure_text(text, None, 22, 1.0).width;
+            draw_text(text, cx - width / 2.0, area.bottom() - 20.0, 22.0, Color::from_rgba(255, 70, 70, 230));
+        }
+        
+        // Sandbox watermark
+        if self.sandbox() {
+            let text = "SANDBOX";
+            let width = measure_text(text, None, 90, 1.0).width;
+            draw_text(text, cx - width / 2.0, area.center().y + 30.0, 90.0, Color::from_rgba(255, 255, 255, 18));
+            let keys = "1-0, -: spawn at cursor   ESC: menu";
+            let keys_width = measure_text(keys, None, 18, 1.0).width;
+            draw_text(keys, cx - keys_width / 2.0, area.bottom() - 50.0, 18.0, Color::from_rgba(200, 200, 200, 150));
+        }
+        
+        // Safe period indicator
+        if self.safe_time > 0.0 && self.game_started {
+            let safe_text = "Safe Zone";
+            let safe_width = measure_text(safe_text, None, 25, 1.0).width;
+            let alpha = if self.reduced_motion() { 220 } else { ((self.safe_time * 3.0).sin() * 127.0 + 128.0) as u8 };
+            
+            draw_text(
+                safe_text,
+                cx - safe_width / 2.0,
+                area.center().y - 100.0,
+                25.0,
+                Color::from_rgba(100, 255, 150, alpha),
+            );
+        }
+        
+        // Game instructions hint
+        if self.time < 5.0 && !self.game_over && self.tutorial.is_none() {
+            let hint = if self.config.controls == ControlScheme::SingleStick {
+                "Drag anywhere to fly - you aim and shoot automatically!"
+            } else if self.config.swap_sticks {
+                "Left joystick to AIM & SHOOT!"
+            } else {
+                "Right joystick to AIM & SHOOT!"
+            };
+            let hint_width = measure_text(hint, None, 20, 1.0).width;
+            let alpha = if self.reduced_motion() { 220 } else { ((self.time * 2.0).sin() * 127.0 + 128.0) as u8 };
+            
+            draw_text(
+                hint,
+                cx - hint_width / 2.0,
+                area.bottom() - 80.0,
+                20.0,
+                Color::from_rgba(255, 200, 100, alpha),
+            );
+        }
+    }
+    
+    fn draw_wave_banner(&self) {
+        let (text, size, alpha) = match self.waves.phase() {
+            WavePhase::Break { remaining } if self.waves.wave() > 0 => {
+                let count = remaining.ceil().max(1.0) as i32;
+                // Each number pops in and fades over its second
+                let alpha = 0.4 + remaining.fract() * 0.6;
+                (format!("Wave {} in {}...", self.waves.wave() + 1, count), 40.0, alpha)
+            }
+            WavePhase::Spawning if self.waves.wave_elapsed() < 1.5 => {
+                let t = self.waves.wave_elapsed();
+                let alpha = (1.0 - t / 1.5).min(1.0);
+                // Slight zoom-out as it fades; finite runs show how far along they are
+                let label = match self.waves.final_wave() {
+                    Some(last) => format!("STAGE {}/{}", self.waves.wave(), last),
+                    None => format!("WAVE {}", self.waves.wave()),
+                };
+                (label, 70.0 + (1.5 - t) * 20.0, alpha)
+            }
+            _ => return,
+        };
+        
+        let area = self.ui_area();
+        let width = measure_text(&text, None, size as u16, 1.0).width;
+        draw_text(
+            &text,
+            area.center().x - width / 2.0,
+            area.center().y - 120.0,
+            size,
+            Color::new(0.6, 0.85, 1.0, alpha),
+        );
+    }
+    
+    // Pops in large and settles while fading out
+    fn draw_wave_clear(&self, clear: &WaveClear) {
+        let t = 1.0 - clear.life / WAVE_CLEAR_TIME;
+        let alpha = (clear.life / 0.5).min(1.0);
+        let size = 70.0 + (1.0 - (t * 6.0).min(1.0)) * 30.0;
+        let area = self.ui_area();
+        let cx = area.center().x;
+        let y = area.center().y - 120.0;
+        
+        let text = "WAVE CLEAR";
+        let width = measure_text(text, None, size as u16, 1.0).width;
+        draw_text(text, cx - width / 2.0, y, size, Color::new(1.0, 0.85, 0.35, alpha));
+        
+        let bonus = format!("+{}", clear.bonus);
+        let bonus_width = measure_text(&bonus, None, 32, 1.0).width;
+        draw_text(&bonus, cx - bonus_width / 2.0, y + 40.0, 32.0, Color::new(1.0, 1.0, 0.6, alpha));
+    }
+    
+    fn draw_heart(&self, center: Vec2, scale: f32, color: Color) {
+        let r = 6.0 * scale;
+        draw_circle(center.x - r, center.y - r * 0.5, r, color);
+        draw_circle(center.x + r, center.y - r * 0.5, r, color);
+        draw_triangle(
+            Vec2::new(center.x - r * 2.0, center.y - r * 0.2),
+            Vec2::new(center.x + r * 2.0, center.y - r * 0.2),
+            Vec2::new(center.x, center.y + r * 1.8),
+            color,
+        );
+    }
+    
+    // A heart `t` (0.0 - 1.0) of the way through breaking: it swells, then
+    // bursts into debris. Reduced motion just fades it out.
+    fn draw_heart_loss(&self, center: Vec2, t: f32, slot: i32) {
+        self.draw_heart(center, 1.0, HEART_EMPTY_COLOR);
+        if self.reduced_motion() {
+            self.draw_heart(center, 1.0, Color { a: 1.0 - t, ..HEART_COLOR });
+            return;
+        }
+        if t < HEART_SWELL_FRACTION {
+            let swell = t / HEART_SWELL_FRACTION;
+            self.draw_heart(center, 1.0 + 0.6 * swell, HEART_COLOR);
+            return;
+        }
+        let burst = (t - HEART_SWELL_FRACTION) / (1.0 - HEART_SWELL_FRACTION);
+        for i in 0..HEART_DEBRIS {
+            // Fixed spread per slot so the shards don't jitter between frames
+            let angle = (i as f32 / HEART_DEBRIS as f32 + slot as f32 * 0.13) * std::f32::consts::TAU;
+            let pos = center + Vec2::from_angle(angle) * burst * 26.0 + Vec2::new(0.0, burst * burst * 18.0);
+            draw_circle(pos.x, pos.y, 3.0 * (1.0 - burst) + 1.0, Color { a: 1.0 - burst, ..HEART_COLOR });
+        }
+    }
+    
+    // A heart `t` of the way through popping in: overshoots, then settles
+    fn draw_heart_gain(&self, center: Vec2, t: f32) {
+        self.draw_heart(center, 1.0, HEART_EMPTY_COLOR);
+        if self.reduced_motion() {
+            self.draw_heart(center, 1.0, Color { a: t, ..HEART_COLOR });
+            return;
+        }
+        let scale = if t < 0.6 { 1.3 * t / 0.6 } else { 1.3 - 0.3 * (t - 0.6) / 0.4 };
+        self.draw_heart(center, scale, HEART_COLOR);
+    }
+    
+    fn draw_title(&self) {
+        clear_background(Color::from_rgba(5, 5, 15, 255));
+        if let Some(demo) = &self.attract {
+            // Demo round, dimmed so the menu still reads on top
+            set_camera(&demo.world_camera());
+            demo.draw_world();
+            set_default_camera();
+            draw_rectangle(0.0, 0.0, screen_width(), screen_height(), Color::from_rgba(5, 5, 15, 140));
+            let label = "DEMO";
+            let width = measure_text(label, None, 24, 1.0).width;
+            draw_text(label, (screen_width() - width) / 2.0, 40.0, 24.0, Color::from_rgba(200, 200, 220, 160));
+        } else {
+            for particle in &self.particles {
+                draw_circle(
+                    particle.pos.x,
+                    particle.pos.y,
+                    particle.size,
+                    Color::from_rgba(100, 120, 200, (particle.alpha * 255.0) as u8),
+                );
+            }
+        }
+        
+        if self.settings_open {
+            self.draw_settings();
+            return;
+        }
+        if self.codex_open {
+            self.draw_codex();
+            return;
         }
         
-        // Game instructions hint
-        if self.time < 5.0 && !self.game_over {
-            let hint = "Right joystick to AIM & SHOOT!";
-            let hint_width = measure_text(hint, None, 20 as u16, 1.0).width;
-            let alpha = ((self.time * 2.0).sin() * 127.0 + 128.0) as u8;
-            
-            draw_text(
-                hint,
-                (screen_width() - hint_width) / 2.0,
-                screen_height() - 80.0,
-                20.0,
-                Color::from_rgba(255, 200, 100, alpha),
-            );
+        let title = "ROCKET";
+        let title_width = measure_text(title, None, 100, 1.0).width;
+        draw_text(
+            title,
+            (screen_width() - title_width) / 2.0,
+            screen_height() / 2.0 - 170.0,
+            100.0,
+            Color::from_rgba(100, 200, 255, 255),
+        );
+        
+        let top = Self::title_menu_top();
+        if let Some(save) = &self.saved_run {
+            // A run put away by save-and-quit, offered before anything else
+            let info = format!("SAVED RUN  -  WAVE {}  -  {}", save.wave, save.score);
+            let width = measure_text(&info, None, 28, 1.0).width;
+            draw_text(&info, (screen_width() - width) / 2.0, top - 60.0, 28.0, Color::from_rgba(255, 200, 100, 230));
+            self.resume_menu.draw(top, &["CONTINUE", "DISCARD"]);
+            let hint = "Up/Down + Enter, 1-2, or click  -  ESC discards";
+            let hint_width = measure_text(hint, None, 18, 1.0).width;
+            draw_text(hint, (screen_width() - hint_width) / 2.0, screen_height() - 30.0, 18.0, Color::from_rgba(150, 170, 210, 210));
+            return;
+        }
+        let loadout_label = format!("LOADOUT: {}", self.config.loadout.name().to_uppercase());
+        let labels: Vec<&str> = TITLE_MENU
+            .iter()
+            .map(|&(label, action)| if action == TitleAction::Loadout { loadout_label.as_str() } else { label })
+            .collect();
+        self.title_menu.draw(top, &labels);
+        
+        // What the picked loadout changes, beside its menu entry
+        if let Some(i) = TITLE_MENU.iter().position(|&(_, action)| action == TitleAction::Loadout) {
+            let info = self.config.loadout.description();
+            draw_text(info, screen_width() / 2.0 + 160.0, self.title_menu.item_y(top, i) - 8.0, 18.0, Color::from_rgba(150, 170, 210, 200));
+        }
+        
+        // Today's daily seed, beside its menu entry
+        if let Some(i) = TITLE_MENU.iter().position(|&(_, action)| action == TitleAction::Play(GameMode::Daily, false)) {
+            let date = today();
+            let info = format!("{}  #{:08X}", date, daily_seed(&date));
+            draw_text(&info, screen_width() / 2.0 + 160.0, self.title_menu.item_y(top, i) - 8.0, 18.0, Color::from_rgba(150, 170, 210, 200));
+        }
+        
+        // Both personal records, score and survival
+        if self.best_score > 0 || self.best_survival > 0.0 {
+            let records = format!("BEST: {}  -  LONGEST RUN: {}", self.best_score, clock_text(self.best_survival));
+            let records_width = measure_text(&records, None, 20, 1.0).width;
+            draw_text(&records, (screen_width() - records_width) / 2.0, screen_height() / 2.0 - 125.0, 20.0, Color::from_rgba(255, 200, 100, 220));
         }
+        
+        let hint = format!(
+            "CONTROLS: {} (M to change)  -  Up/Down + Enter, 1-9, or click",
+            self.config.controls.name().to_uppercase()
+        );
+        let hint_width = measure_text(&hint, None, 18, 1.0).width;
+        draw_text(&hint, (screen_width() - hint_width) / 2.0, screen_height() - 30.0, 18.0, Color::from_rgba(150, 170, 210, 210));
     }
     
+    // Enemy list down the middle, the highlighted entry's sprite and notes
+    // to its right. Undiscovered kinds stay hidden.
+    fn draw_codex(&self) {
+        let top = Self::codex_menu_top();
+        let title = "CODEX";
+        let width = measure_text(title, None, 60, 1.0).width;
+        draw_text(title, (screen_width() - width) / 2.0, top - 50.0, 60.0, Color::from_rgba(150, 210, 255, 255));
+        
+        let labels: Vec<&str> = EnemyKind::ALL
+            .iter()
+            .map(|&kind| if self.codex.is_discovered(kind) { kind.title() } else { "???" })
+            .chain(["BACK"])
+            .collect();
+        self.codex_menu.draw(top, &labels);
+        
+        if let Some(&kind) = EnemyKind::ALL.get(self.codex_menu.selected()) {
+            let x = screen_width() / 2.0 + 180.0;
+            let y = top + 60.0;
+            let text_color = Color::from_rgba(200, 220, 255, 230);
+            if self.codex.is_discovered(kind) {
+                let mut specimen = Enemy::new(kind, Vec2::new(x + 40.0, y));
+                specimen.size = specimen.size.min(CODEX_SPRITE_SIZE);
+                specimen.rotation = self.time * 0.8;
+                self.draw_enemy(&specimen);
+                let (health, _, _, score) = kind.stats();
+                draw_text(kind.title(), x, y + 70.0, 30.0, WHITE);
+                draw_text(&format!("HP {}  -  {} PTS", health, score), x, y + 96.0, 18.0, text_color);
+                draw_text(kind.description(), x, y + 122.0, 18.0, text_color);
+                if kind.is_boss() {
+                    draw_text("ENTER: practice this fight", x, y + 148.0, 18.0, Color::from_rgba(255, 200, 100, 230));
+                }
+            } else {
+                draw_text("Not yet encountered", x, y + 70.0, 22.0, text_color);
+            }
+        }
+        
+        let footer = format!(
+            "DISCOVERED {}/{}  -  ESC: back",
+            self.codex.discovered_count(),
+            EnemyKind::ALL.len()
+        );
+        let footer_width = measure_text(&footer, None, 18, 1.0).width;
+        let footer_y = self.codex_menu.item_y(top, EnemyKind::ALL.len() + 1) + 5.0;
+        draw_text(&footer, (screen_width() - footer_width) / 2.0, footer_y, 18.0, Color::from_rgba(150, 150, 170, 200));
+    }
+
+    fn draw_settings(&self) {
+        let top = Self::settings_menu_top();
+        let title = "SETTINGS";
+        let width = measure_text(title, None, 60, 1.0).width;
+        draw_text(title, (screen_width() - width) / 2.0, top - 70.0, 60.0, Color::from_rgba(150, 210, 255, 255));
+        
+        let labels: Vec<String> = SETTINGS_MENU.iter().map(|&s| self.setting_label(s)).collect();
+        let labels: Vec<&str> = labels.iter().map(String::as_str).collect();
+        self.settings_menu.draw(top, &labels);
+        
+        let hint = "Up/Down to pick, Left/Right or click to change  -  ESC: back";
+        let hint_width = measure_text(hint, None, 18, 1.0).width;
+        let hint_y = self.settings_menu.item_y(top, SETTINGS_MENU.len()) + 10.0;
+        draw_text(hint, (screen_width() - hint_width) / 2.0, hint_y, 18.0, Color::from_rgba(150, 150, 170, 200));
+    }
+
+    fn draw_victory(&self) {
+        draw_rectangle(0.0, 0.0, screen_width(), screen_height(), Color::from_rgba(0, 10, 20, 150));
+        let cx = screen_width() / 2.0;
+        let cy = screen_height() / 2.0;
+        let centered = |text: &str, y: f32, size: f32, color: Color| {
+            let width = measure_text(text, None, size as u16, 1.0).width;
+            draw_text(text, cx - width / 2.0, y, size, color);
+        };
+        
+        let title = match self.config.mode {
+            GameMode::BossRush => "BOSS RUSH CLEAR",
+            GameMode::Practice => "PRACTICE CLEAR",
+            _ => "VICTORY",
+        };
+        centered(title, cy - 150.0, 80.0, Color::from_rgba(100, 255, 150, 255));
+        centered(&format!("CLEARED IN {}", split_text(self.run_clock)), cy - 90.0, 32.0, WHITE);
+        
+        // Best clear (already includes this run)
+        let best_text = match self.best_clear_time {
+            Some(best) if best == self.run_clock => "NEW BEST TIME!".to_owned(),
+            Some(best) => format!("BEST CLEAR: {}", split_text(best)),
+            None => String::new(),
+        };
+        centered(&best_text, cy - 58.0, 24.0, Color::from_rgba(255, 200, 100, 255));
+        
+        // Final score counts up, then the run's stats
+        let progress = (self.game_over_time / TALLY_LINE_TIME).min(1.0);
+        let score_text = format!("FINAL SCORE: {}", (self.score as f32 * progress) as i32);
+        centered(&score_text, cy, 40.0, Color::from_rgba(100, 255, 150, 255));
+        let stats_text = format!(
+            "Kills {}  -  Accuracy {:.0}%  -  Hits taken {}",
+            self.stats.kills(),
+            self.breakdown.accuracy * 100.0,
+            self.stats.hits_taken,
+        );
+        centered(&stats_text, cy + 36.0, 22.0, Color::from_rgba(200, 220, 255, 255));
+        centered(&format!("SEED {:08X}", self.seed), cy + 64.0, 20.0, Color::from_rgba(160, 180, 220, 255));
+        
+        let pulse = if self.reduced_motion() { 220 } else { ((self.game_over_time * 3.0).sin() * 127.0 + 128.0) as u8 };
+        let restart_text = if self.practice() {
+            "Click or Press SPACE to Fight Again  -  ESC: Codex"
+        } else {
+            "Click or Press SPACE to Play Again  -  R: Replay Seed  -  ESC: Menu"
+        };
+        centered(restart_text, cy + 110.0, 25.0, Color::from_rgba(255, 255, 100, pulse));
+    }
+
     fn draw_game_over(&self) {
         // Dark overlay
         draw_rectangle(
@@ -845,55 +5935,934 @@ impl GameState {
         let game_over_text = "GAME OVER";
         let title_size = 80.0;
         let title_width = measure_text(game_over_text, None, title_size as u16, 1.0).width;
+        let cx = screen_width() / 2.0;
+        let cy = screen_height() / 2.0;
         
         draw_text(
             game_over_text,
-            (screen_width() - title_width) / 2.0,
-            screen_height() / 2.0 - 80.0,
+            cx - title_width / 2.0,
+            cy - 190.0,
             title_size,
             Color::from_rgba(255, 80, 80, 255),
         );
         
-        // Final Score
-        let score_text = format!("FINAL SCORE: {}", self.score);
+        // Itemized tally, each line counting up a beat after the previous
+        let b = &self.breakdown;
+        let minutes = (self.time / 60.0) as i32;
+        let seconds = (self.time % 60.0) as i32;
+        let lines = [
+            (format!("Kills x{}", self.stats.kills()), b.kills as f32, false),
+            ("Combo bonus".to_owned(), b.combo as f32, false),
+            ("Graze bonus".to_owned(), b.grazes as f32, false),
+            ("Wave clear bonus".to_owned(), b.clears as f32, false),
+            ("Scavengers".to_owned(), b.scavengers as f32, false),
+            ("Chain bonus".to_owned(), b.chains as f32, false),
+            ("Hitless bonus".to_owned(), b.hitless as f32, false),
+            (format!("Wave bonus (wave {})", self.waves.wave()), b.waves as f32, false),
+            (format!("Survived {:02}:{:02}", minutes, seconds), b.survival as f32, false),
+            (
+                format!("Accuracy ({}/{})", self.stats.shots_hit, self.stats.shots_fired),
+                b.accuracy * 100.0,
+                true,
+            ),
+        ];
+        let line_size = 23.0;
+        for (i, (label, value, percent)) in lines.iter().enumerate() {
+            let progress = ((self.game_over_time - i as f32 * TALLY_STAGGER) / TALLY_LINE_TIME).clamp(0.0, 1.0);
+            if progress <= 0.0 {
+                continue;
+            }
+            let shown = (value * progress) as i32;
+            let value_text = if *percent { format!("{}%", shown) } else { format!("{:+}", shown) };
+            let y = cy - 160.0 + i as f32 * 22.0;
+            let value_width = measure_text(&value_text, None, line_size as u16, 1.0).width;
+            draw_text(label, cx - 200.0, y, line_size, Color::from_rgba(200, 220, 255, 255));
+            draw_text(&value_text, cx + 200.0 - value_width, y, line_size, Color::from_rgba(255, 255, 150, 255));
+        }
+        
+        // Final Score counts up alongside the whole tally
+        let total_time = (lines.len() - 1) as f32 * TALLY_STAGGER + TALLY_LINE_TIME;
+        let progress = (self.game_over_time / total_time).min(1.0);
+        let score_text = format!("FINAL SCORE: {}", (self.score as f32 * progress) as i32);
         let score_size = 40.0;
         let score_width = measure_text(&score_text, None, score_size as u16, 1.0).width;
         
         draw_text(
             &score_text,
-            (screen_width() - score_width) / 2.0,
-            screen_height() / 2.0,
+            cx - score_width / 2.0,
+            cy + 60.0,
             score_size,
             Color::from_rgba(100, 255, 150, 255),
         );
         
-        // Survival Time
-        let minutes = (self.time / 60.0) as i32;
-        let seconds = (self.time % 60.0) as i32;
-        let time_text = format!("Survived: {:02}:{:02}", minutes, seconds);
-        let time_size = 30.0;
-        let time_width = measure_text(&time_text, None, time_size as u16, 1.0).width;
+        // Personal best (already includes this run if it beat it)
+        let daily = self.config.mode == GameMode::Daily;
+        let (best, label) = match self.config.mode {
+            GameMode::Daily => (self.daily_best, "DAILY BEST"),
+            GameMode::Hardcore => (self.hardcore_best, "HARDCORE BEST"),
+            _ => (self.best_score, "BEST"),
+        };
+        let mut best_text = if self.practice() {
+            "PRACTICE  -  NOT RECORDED".to_owned()
+        } else if self.config.mode == GameMode::BossRush {
+            // Boss rush ranks clears by time rather than score
+            match self.best_clear_time {
+                Some(best) => format!("BEST CLEAR: {}", split_text(best)),
+                None => "BEST CLEAR: --".to_owned(),
+            }
+        } else if self.score >= best && self.score > 0 {
+            format!("NEW {}!", label)
+        } else {
+            format!("{}: {}", label, best)
+        };
+        if self.config.mode == GameMode::Normal {
+            let survival = if self.time >= self.best_survival && self.time > 0.0 {
+                format!("NEW LONGEST RUN: {}", clock_text(self.time))
+            } else {
+                format!("LONGEST RUN: {}", clock_text(self.best_survival))
+            };
+            best_text = format!("{}  -  {}", best_text, survival);
+        }
+        let best_size = 24.0;
+        let best_width = measure_text(&best_text, None, best_size as u16, 1.0).width;
         
         draw_text(
-            &time_text,
-            (screen_width() - time_width) / 2.0,
-            screen_height() / 2.0 + 50.0,
-            time_size,
-            Color::from_rgba(200, 220, 255, 255),
+            &best_text,
+            cx - best_width / 2.0,
+            cy + 95.0,
+            best_size,
+            Color::from_rgba(255, 200, 100, 255),
         );
         
+        // Seed so the run can be shared and replayed
+        let seed_text = if daily {
+            format!("DAILY {}  -  SEED {:08X}", self.today, self.seed)
+        } else {
+            format!("SEED {:08X}", self.seed)
+        };
+        let seed_size = 20.0;
+        let seed_width = measure_text(&seed_text, None, seed_size as u16, 1.0).width;
+        
+        draw_text(
+            &seed_text,
+            cx - seed_width / 2.0,
+            cy + 120.0,
+            seed_size,
+            Color::from_rgba(160, 180, 220, 255),
+        );
+        
+        // How far the run got before the first hit
+        if self.config.mode == GameMode::Normal && self.hitless_waves > 0 {
+            let record = if self.hitless_waves >= self.best_hitless_waves { "  -  NEW RECORD!" } else { "" };
+            let hitless_text = format!("HITLESS TO WAVE {}{}", self.hitless_waves, record);
+            let hitless_width = measure_text(&hitless_text, None, 22, 1.0).width;
+            draw_text(&hitless_text, cx - hitless_width / 2.0, cy + 146.0, 22.0, Color::from_rgba(255, 215, 120, 255));
+        }
+        
         // Restart instruction
-        let restart_text = "Click or Press SPACE to Restart";
+        let restart_text = if self.practice() {
+            "Click or Press SPACE to Fight Again  -  ESC: Codex"
+        } else {
+            "Click or Press SPACE to Restart  -  R: Replay Seed  -  1: Solo  2: Co-op  -  ESC: Menu"
+        };
         let restart_size = 25.0;
         let restart_width = measure_text(restart_text, None, restart_size as u16, 1.0).width;
-        let pulse = ((self.time * 3.0).sin() * 127.0 + 128.0) as u8;
+        let pulse = if self.reduced_motion() { 220 } else { ((self.game_over_time * 3.0).sin() * 127.0 + 128.0) as u8 };
         
         draw_text(
             restart_text,
-            (screen_width() - restart_width) / 2.0,
-            screen_height() / 2.0 + 120.0,
+            cx - restart_width / 2.0,
+            cy + 175.0,
             restart_size,
             Color::from_rgba(255, 255, 100, pulse),
         );
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enemy::KNOCKBACK_MAX_SPEED;
+    use crate::joystick::StickGate;
+    use crate::player::HITBOX_RADIUS;
+
+    const VIEW: Vec2 = Vec2::new(1280.0, 720.0);
+    const STEP: f32 = 1.0 / 60.0;
+
+    // A solo run past the intro and walkthrough, built without a window
+    fn headless_run(seed: u32) -> GameState {
+        let config = Config { tutorial_completed: true, ..Config::default() };
+        let mut state = GameState::headless(config, seed, VIEW);
+        state.skip_intro();
+        state
+    }
+
+    fn run_for(state: &mut GameState, seconds: f32, input: &[PilotInput]) {
+        for _ in 0..(seconds / STEP).round() as usize {
+            state.update(STEP, input);
+        }
+    }
+
+    // Ten seconds circling the arena while firing outward
+    fn circling_script(seed: u32) -> ScriptedInput {
+        let mut text = format!("seed {:x}\n", seed);
+        for i in 0..600 {
+            let angle = i as f32 * 0.02;
+            text += &format!("{} {} {} {} {} 1\n", STEP, angle.cos(), angle.sin(), -angle.sin(), angle.cos());
+        }
+        ScriptedInput::parse(&text).unwrap()
+    }
+
+    // Fly `script` from the start of a headless run on its seed
+    fn replay(mut script: ScriptedInput) -> GameState {
+        let mut state = headless_run(script.seed.unwrap_or(1));
+        state.players[0].control = Control::Scripted;
+        while state.advance(&mut script) {}
+        state
+    }
+
+    #[test]
+    fn headless_run_is_laid_out_for_its_view() {
+        let state = headless_run(1);
+        assert_eq!(state.view_size, VIEW);
+        assert_eq!(to_mac_vec2(state.players[0].player.position), Vec2::new(640.0, 360.0));
+    }
+
+    #[test]
+    fn headless_run_steps_without_a_window() {
+        let mut state = headless_run(1);
+        run_for(&mut state, 10.0, &[]);
+        assert!((state.time - 10.0).abs() < 0.01);
+        assert!(state.enemies_spawned > 0);
+        for enemy in state.enemies.values() {
+            assert!(enemy.pos.is_finite());
+        }
+    }
+
+    #[test]
+    fn handed_input_moves_the_pilot() {
+        let mut state = headless_run(1);
+        let right = PilotInput { movement: JoyVec2::new(1.0, 0.0), ..PilotInput::default() };
+        run_for(&mut state, 1.0, &[right]);
+        let pilot = &state.players[0].player;
+        assert!(pilot.position.x > 640.0 + 100.0);
+        assert!(pilot.position.y == 360.0);
+    }
+
+    #[test]
+    fn replaying_a_script_plays_out_the_same_way() {
+        let first = replay(circling_script(0xC0FFEE));
+        let second = replay(circling_script(0xC0FFEE));
+        assert!((first.time - 10.0).abs() < 0.01);
+        assert!(first.stats.shots_fired > 0);
+        assert_eq!(first.stats.shots_fired, second.stats.shots_fired);
+        assert_eq!(first.score, second.score);
+        assert_eq!(first.kills, second.kills);
+        assert_eq!(first.enemies_spawned, second.enemies_spawned);
+        assert_eq!(first.players[0].health, second.players[0].health);
+        let (a, b) = (first.players[0].player.position, second.players[0].player.position);
+        assert_eq!((a.x, a.y), (b.x, b.y));
+    }
+
+    #[test]
+    fn a_finished_script_stops_stepping() {
+        let mut script = ScriptedInput::parse("0.5 1 0 1 0 0").unwrap();
+        let mut state = headless_run(1);
+        state.players[0].control = Control::Scripted;
+        assert!(state.advance(&mut script));
+        assert!(!state.advance(&mut script));
+        assert!((state.time - 0.5).abs() < 1e-6);
+    }
+
+    #[test]
+    fn a_press_reaches_only_the_first_step_of_a_frame() {
+        let dash = PilotInput { dash: true, ..PilotInput::default() };
+        let mut live = LiveInput { pilots: vec![dash], steps: vec![STEP, STEP].into_iter() };
+        assert!(live.next_step().unwrap().pilots[0].dash);
+        assert!(!live.next_step().unwrap().pilots[0].dash);
+        assert!(live.next_step().is_none());
+    }
+
+    #[test]
+    fn a_press_on_a_frame_without_steps_waits_for_the_next() {
+        let parry = PilotInput { parry: true, ..PilotInput::default() };
+        let mut live = LiveInput { pilots: vec![parry], steps: Vec::new().into_iter() };
+        assert!(live.next_step().is_none());
+        let mut next_frame = PilotInput::default();
+        next_frame.latch(&live.pilots[0]);
+        assert!(next_frame.parry);
+    }
+
+    // A `kind` one hit from death, `distance` straight ahead of the pilot
+    fn lone_target(state: &mut GameState, kind: EnemyKind, distance: f32) -> Id {
+        let pos = to_mac_vec2(state.players[0].player.position) + Vec2::new(distance, 0.0);
+        let enemy = Enemy { health: 1, ..state.make_enemy(kind, pos) };
+        state.add_enemy(enemy);
+        state.enemies.iter().map(|(id, _)| id).last().unwrap()
+    }
+
+    const FIRE_RIGHT: PilotInput = PilotInput {
+        movement: JoyVec2 { x: 0.0, y: 0.0 },
+        aim: JoyVec2 { x: 1.0, y: 0.0 },
+        firing: true,
+        trigger: 1.0,
+        parry: false,
+        focus: false,
+        special: false,
+        dash: false,
+    };
+
+    #[test]
+    fn killing_an_enemy_raises_one_enemy_killed_event() {
+        let mut state = headless_run(1);
+        lone_target(&mut state, EnemyKind::Chaser, 200.0);
+        let mut events = Vec::new();
+        for _ in 0..120 {
+            state.update(STEP, &[FIRE_RIGHT]);
+            events.extend(state.drain_events());
+        }
+        let kills: Vec<_> = events.iter().filter(|e| matches!(e, GameEvent::EnemyKilled { .. })).collect();
+        assert_eq!(state.kills, 1);
+        assert_eq!(kills.len(), 1);
+        assert!(matches!(kills[0], GameEvent::EnemyKilled { kind: EnemyKind::Chaser, .. }));
+    }
+
+    #[test]
+    fn a_shot_drifter_is_shoved_then_drifts_on() {
+        let mut state = headless_run(1);
+        let id = lone_target(&mut state, EnemyKind::Drifter, 200.0);
+        let start = state.enemies[id].pos;
+        state.enemies[id].health = 50;
+        state.enemies[id].velocity = Vec2::new(0.0, 40.0);
+        run_for(&mut state, 0.3, &[FIRE_RIGHT]);
+        let drifter = &state.enemies[id];
+        assert!(drifter.health < 50);
+        assert!(drifter.knockback.x > 0.0);
+        
+        run_for(&mut state, 2.0, &[]);
+        let drifter = &state.enemies[id];
+        assert!(drifter.pos.x > start.x + 10.0);
+        assert!(drifter.knockback.length() < 1.0);
+        assert_eq!(drifter.velocity, Vec2::new(0.0, 40.0));
+    }
+
+    #[test]
+    fn shockwaves_shove_hardest_near_the_center() {
+        let (size, strength) = (40.0, 150.0);
+        let near = shockwave_impulse(Vec2::new(20.0, 0.0), size, strength);
+        let edge = shockwave_impulse(Vec2::new(size * SHOCKWAVE_REACH - 1.0, 0.0), size, strength);
+        let beyond = shockwave_impulse(Vec2::new(size * SHOCKWAVE_REACH + 1.0, 0.0), size, strength);
+        assert!(near.length() > edge.length());
+        assert!(edge.x > 0.0 && edge.y == 0.0);
+        assert_eq!(beyond, Vec2::ZERO);
+    }
+
+    #[test]
+    fn chained_blasts_cannot_fling_an_enemy() {
+        let mut state = headless_run(1);
+        let id = lone_target(&mut state, EnemyKind::Drifter, 300.0);
+        state.enemies[id].velocity = Vec2::new(0.0, 40.0);
+        let center = state.enemies[id].pos - Vec2::new(30.0, 0.0);
+        for _ in 0..10 {
+            let blast = Explosion::new(ExplosionKind::Large, center, 80.0, &mut state.fx_rng);
+            state.explosions.push(blast);
+        }
+        state.send_shockwaves();
+        let drifter = &state.enemies[id];
+        assert!(drifter.knockback.length() <= KNOCKBACK_MAX_SPEED + 0.01);
+        assert_eq!(drifter.velocity, Vec2::new(0.0, 40.0));
+        
+        run_for(&mut state, 2.0, &[]);
+        assert!(state.enemies[id].knockback.length() < 1.0);
+    }
+
+    #[test]
+    fn a_resumed_run_matches_the_one_saved() {
+        let mut state = headless_run(7);
+        run_for(&mut state, 8.0, &[FIRE_RIGHT]);
+        assert!(state.enemies.len() > 0);
+        let saved = state.run_save();
+        
+        let mut resumed = headless_run(99);
+        resumed.resume_run(saved.clone());
+        let again = resumed.run_save();
+        assert_eq!(again.enemies, saved.enemies);
+        assert_eq!(again.pilots, saved.pilots);
+        assert_eq!(again.stats, saved.stats);
+        assert_eq!((again.seed, again.score, again.kills, again.wave), (saved.seed, saved.score, saved.kills, saved.wave));
+        assert_eq!(again.weapon_level, saved.weapon_level);
+        for (enemy, saved) in resumed.enemies.values().zip(&saved.enemies) {
+            assert_eq!(enemy.size, saved.size);
+            assert_eq!(enemy.score_value, saved.score_value);
+        }
+    }
+
+    #[test]
+    fn a_kill_scores_its_kind_times_the_combo_multiplier() {
+        for (kind, combo, multiplier) in [(EnemyKind::Chaser, 0, 1), (EnemyKind::Shooter, 7, 2), (EnemyKind::Support, 25, 5)] {
+            let mut state = headless_run(1);
+            state.hitless = false;
+            state.combo = combo;
+            state.on_enemy_killed(&Enemy::new(kind, Vec2::new(100.0, 100.0)));
+            assert_eq!(state.score, kind.stats().3 * multiplier);
+            assert_eq!(state.combo, combo + 1);
+        }
+    }
+
+    #[test]
+    fn a_ring_formation_spaces_its_enemies_evenly() {
+        let mut state = headless_run(1);
+        let pilot = to_mac_vec2(state.players[0].player.position);
+        let points = state.formation_points(SpawnPattern::Ring, 6, 0.0);
+        assert_eq!(points.len(), 6);
+        let angle = |p: Vec2| (p - pilot).to_angle();
+        for (i, point) in points.iter().enumerate() {
+            let radius = (*point - pilot).length();
+            assert!((radius - (state.config.spawn_exclusion_radius + FORMATION_RING_MARGIN)).abs() < 0.01);
+            let next = points[(i + 1) % points.len()];
+            let gap = (angle(next) - angle(*point)).rem_euclid(std::f32::consts::TAU);
+            assert!((gap - std::f32::consts::TAU / 6.0).abs() < 1e-3);
+        }
+    }
+
+    #[test]
+    fn a_wave_opens_with_its_burst_telegraphed() {
+        let mut state = headless_run(1);
+        state.waves.jump_to(8);
+        state.telegraph_burst();
+        assert_eq!(state.telegraphs.len(), 5);
+        let pilot = to_mac_vec2(state.players[0].player.position);
+        for telegraph in &state.telegraphs {
+            assert!((telegraph.pos - pilot).length() >= state.config.spawn_exclusion_radius);
+        }
+    }
+
+    #[test]
+    fn a_burst_waits_for_room_under_the_enemy_cap() {
+        let mut state = headless_run(1);
+        state.config.max_concurrent_enemies = 3;
+        state.waves.jump_to(8);
+        state.telegraph_burst();
+        assert_eq!(state.telegraphs.len(), 3);
+    }
+
+    #[test]
+    fn stacked_hitstop_requests_freeze_once() {
+        let mut state = headless_run(1);
+        for _ in 0..10 {
+            state.request_hitstop(0.05);
+        }
+        assert!((state.hitstop_timer - 0.05).abs() < 1e-6);
+        
+        let mut frozen = 0.0;
+        for _ in 0..30 {
+            let before = state.time;
+            state.update(STEP, &[]);
+            if state.time == before {
+                frozen += STEP;
+            }
+        }
+        assert!((0.05..0.05 + STEP + 1e-4).contains(&frozen));
+    }
+
+    #[test]
+    fn big_enemies_roam_further_past_the_edge() {
+        let config = Config::default();
+        let (small, big) = (edge_buffer(&config, 12.0), edge_buffer(&config, 70.0));
+        assert_eq!(big - small, 58.0);
+        
+        // Both are held just past the edge, the big one with its whole body out
+        let clamped = |margin: f32| {
+            let (mut pos, mut velocity) = (Vec2::new(-500.0, 300.0), Vec2::new(-50.0, 0.0));
+            apply_boundary(EdgePolicy::Clamp, EdgeResponse::Bounce, VIEW, &mut pos, &mut velocity, margin);
+            pos.x
+        };
+        assert_eq!(clamped(small), -small);
+        assert_eq!(clamped(big), -big);
+    }
+
+    #[test]
+    fn a_burst_fires_its_rounds_then_waits_the_full_interval() {
+        let mut state = headless_run(1);
+        let pilot = &mut state.players[0];
+        let delays: Vec<f32> = (0..6).map(|_| pilot.next_shot_delay(3, 0.06, 0.5)).collect();
+        assert_eq!(delays, vec![0.06, 0.06, 0.5, 0.06, 0.06, 0.5]);
+    }
+
+    #[test]
+    fn a_held_trigger_fires_three_round_bursts() {
+        let mut state = headless_run(1);
+        state.config.burst = crate::config::BurstFire { size: 3, delay: 0.05 };
+        let interval = state.config.weapon_levels[0].fire_interval * state.config.loadout.fire_interval_scale();
+        let mut shots = Vec::new();
+        // Long enough for two bursts, too short for a third
+        for _ in 0..((interval + 0.2) / STEP) as usize + 2 {
+            let before = state.stats.shots_fired;
+            state.update(STEP, &[FIRE_RIGHT]);
+            if state.stats.shots_fired > before {
+                shots.push(state.time);
+            }
+        }
+        assert_eq!(shots.len(), 6);
+        assert!(shots[1] - shots[0] < 0.1 && shots[2] - shots[1] < 0.1);
+        assert!(shots[3] - shots[2] >= interval - STEP);
+    }
+
+    // Still Drifters with `health` in a row straight ahead of the pilot
+    fn drifter_row(state: &mut GameState, distances: &[f32], health: i32) -> Vec<Id> {
+        distances
+            .iter()
+            .map(|&distance| {
+                let id = lone_target(state, EnemyKind::Drifter, distance);
+                state.enemies[id].health = health;
+                state.enemies[id].velocity = Vec2::ZERO;
+                id
+            })
+            .collect()
+    }
+
+    #[test]
+    fn a_piercing_shot_damages_every_enemy_it_passes() {
+        for (pierce, damaged) in [(PIERCE_COUNT, 3), (1, 2), (0, 1)] {
+            let mut state = headless_run(1);
+            let row = drifter_row(&mut state, &[150.0, 250.0, 350.0], 5);
+            let pilot = to_mac_vec2(state.players[0].player.position);
+            state.shoot(pilot, 0.0, pierce, false);
+            run_for(&mut state, 1.0, &[]);
+            let hit = row.iter().filter(|&&id| state.enemies[id].health == 4).count();
+            assert_eq!(hit, damaged);
+            assert_eq!(state.stats.shots_hit, 1);
+        }
+    }
+
+    #[test]
+    fn an_enemy_touching_only_the_wings_does_not_hit() {
+        for (distance, hit) in [(VISUAL_SIZE + 15.0, false), (HITBOX_RADIUS + 15.0, true)] {
+            let mut state = headless_run(1);
+            state.safe_time = 0.0;
+            let id = drifter_row(&mut state, &[distance], 5)[0];
+            state.enemies[id].size = 20.0;
+            let health = state.players[0].health;
+            state.update(STEP, &[]);
+            assert_eq!(state.players[0].health < health, hit);
+        }
+    }
+
+    #[test]
+    fn nothing_spawns_inside_the_safe_zone() {
+        let mut state = headless_run(1);
+        state.config.caps.enemies = 500;
+        state.config.spawn_exclusion_radius = 300.0;
+        // Hugging the left wall, where plenty of edge points are too close
+        state.players[0].player.position = JoyVec2::new(60.0, 360.0);
+        let pilot = Vec2::new(60.0, 360.0);
+        for _ in 0..200 {
+            state.spawn_enemy(EnemyKind::Chaser);
+        }
+        assert_eq!(state.enemies.len(), 200);
+        for enemy in state.enemies.values() {
+            assert!((enemy.pos - pilot).length() >= 300.0);
+        }
+    }
+
+    #[test]
+    fn the_hud_stays_inside_a_narrow_safe_area() {
+        let inset = SafeArea { left: 300.0, top: 60.0, right: 340.0, bottom: 48.0 };
+        let area = safe_rect(inset, VIEW);
+        assert_eq!(area, Rect::new(300.0, 60.0, 640.0, 612.0));
+        
+        // Every heart of four pilots, with its full width, fits in the area
+        let inside = |p: Vec2| area.contains(p - Vec2::splat(12.0)) && area.contains(p + Vec2::splat(12.0));
+        for seat in 0..4 {
+            for slot in 0..PLAYER_HEALTH {
+                assert!(inside(heart_center(area, seat, slot)));
+            }
+        }
+        // So does the special meter along the bottom
+        assert!(inside(Vec2::new(area.center().x - 100.0, area.bottom() - 30.0)));
+        assert!(inside(Vec2::new(area.center().x + 100.0, area.bottom() - 30.0)));
+    }
+
+    // The left stick held by touch `id`, pushed fully right
+    fn hold_left_stick(state: &mut GameState, id: u64) {
+        state.left_joystick.on_touch_start(JoyVec2::new(100.0, 500.0));
+        state.left_joystick.on_touch_move(JoyVec2::new(200.0, 500.0));
+        state.left_touch_id = Some(id);
+    }
+
+    #[test]
+    fn a_touch_held_through_a_pause_keeps_steering() {
+        let mut state = headless_run(1);
+        hold_left_stick(&mut state, 3);
+        state.right_joystick.on_touch_start(JoyVec2::new(1000.0, 500.0));
+        state.right_touch_id = Some(4);
+        
+        // Paused: nothing steps. Touch 4 lifts meanwhile; resuming checks
+        // what's still down
+        state.paused = true;
+        state.release_sticks_unless_held(&[3], false);
+        state.paused = false;
+        
+        assert!(state.left_joystick.active);
+        assert_eq!(state.left_touch_id, Some(3));
+        assert_eq!(state.left_joystick.get_input().x, 1.0);
+        assert!(!state.right_joystick.active);
+        assert_eq!(state.right_touch_id, None);
+    }
+
+    #[test]
+    fn a_touch_gone_without_an_end_event_releases_its_stick() {
+        let mut state = headless_run(1);
+        hold_left_stick(&mut state, 7);
+        state.release_sticks_unless_held(&[7, 8], false);
+        assert!(state.left_joystick.active);
+        
+        // Touch 7 vanished from the list without ever reporting Ended
+        state.release_sticks_unless_held(&[8], false);
+        assert!(!state.left_joystick.active);
+        assert_eq!(state.left_touch_id, None);
+        assert_eq!(state.left_joystick.get_input().length(), 0.0);
+    }
+
+    #[test]
+    fn a_hit_ends_the_combo_and_its_multiplier() {
+        let mut state = headless_run(1);
+        state.hitless = false;
+        state.combo = 12;
+        state.combo_timer = 1.0;
+        assert_eq!(state.combo_multiplier(), 3);
+        
+        state.damage_player(0, 1);
+        assert_eq!((state.combo, state.combo_multiplier()), (0, 1));
+        state.on_enemy_killed(&Enemy::new(EnemyKind::Chaser, Vec2::new(100.0, 100.0)));
+        assert_eq!(state.score, EnemyKind::Chaser.stats().3);
+    }
+
+    #[test]
+    fn spawning_waits_at_the_enemy_cap() {
+        let mut state = headless_run(1);
+        state.god_mode = true;
+        state.config.max_concurrent_enemies = 3;
+        for _ in 0..(20.0 / STEP) as usize {
+            state.update(STEP, &[]);
+            assert!(state.enemies.len() + state.telegraphs.len() <= 3);
+        }
+        // Wave 1 has four; the last one waits for room
+        assert_eq!((state.waves.wave(), state.enemies_spawned), (1, 3));
+        
+        let ids: Vec<Id> = state.enemies.iter().map(|(id, _)| id).collect();
+        for id in ids {
+            let enemy = state.enemies.remove(id).unwrap();
+            state.on_enemy_killed(&enemy);
+        }
+        run_for(&mut state, 3.0, &[]);
+        assert_eq!(state.enemies_spawned, 4);
+    }
+
+    #[test]
+    fn a_short_life_shot_expires_before_a_long_life_one() {
+        let mut state = headless_run(1);
+        let pos = to_mac_vec2(state.players[0].player.position);
+        state.shoot(pos, 0.0, 0, false);
+        state.shoot(pos, 0.0, 1, false);
+        assert!(state.config.pierce_shot.life < 1.5 && state.config.blaster.life > 1.5);
+        let blaster_pierce = state.bullets[0].pierce;
+        for _ in 0..(1.5 / STEP) as usize {
+            state.reap_transients(STEP);
+        }
+        assert_eq!(state.bullets.len(), 1);
+        assert_eq!(state.bullets[0].pierce, blaster_pierce);
+    }
+
+    #[test]
+    fn a_press_during_the_intro_starts_the_game() {
+        let config = Config { tutorial_completed: true, ..Config::default() };
+        let mut state = GameState::headless(config, 1, VIEW);
+        run_for(&mut state, 0.1, &[]);
+        assert!(!state.game_started && state.intro_alpha > 0.0);
+        state.skip_ahead(true, false);
+        assert!(state.game_started);
+        assert_eq!(state.intro_alpha, 0.0);
+        // Skipping doesn't cut the grace period short
+        run_for(&mut state, 1.0, &[]);
+        assert_eq!(state.enemies_spawned, 0);
+    }
+
+    #[test]
+    fn a_radial_volley_rings_evenly_at_one_speed() {
+        let look = Config::default().enemy_bullet_look;
+        let mut bullets = Vec::new();
+        fire_pattern(&mut bullets, Vec2::new(100.0, 100.0), BulletPattern::Radial { count: 8 }, 0.0, 0.0, look);
+        assert_eq!(bullets.len(), 8);
+        for (i, bullet) in bullets.iter().enumerate() {
+            let expected = Vec2::from_angle((45.0 * i as f32).to_radians());
+            assert!((bullet.velocity.normalize() - expected).length() < 1e-4);
+            assert!((bullet.velocity.length() - ENEMY_BULLET_SPEED).abs() < 1e-3);
+            assert_eq!(bullet.pos, Vec2::new(100.0, 100.0));
+        }
+    }
+
+    #[test]
+    fn one_mine_blast_hurts_the_whole_cluster() {
+        let mut state = headless_run(1);
+        let ids = drifter_row(&mut state, &[260.0, 300.0, 340.0, 600.0], 5);
+        let pos = to_mac_vec2(state.players[0].player.position) + Vec2::new(300.0, 0.0);
+        state.detonate_mine(pos);
+        for &id in &ids[..3] {
+            assert_eq!(state.enemies[id].health, 5 - MINE_ENEMY_DAMAGE);
+        }
+        assert_eq!(state.enemies[ids[3]].health, 5);
+    }
+
+    #[test]
+    fn a_clamped_pilot_stays_inside() {
+        let mut state = headless_run(1);
+        state.config.edges.player = EdgePolicy::Clamp;
+        let right = PilotInput { movement: JoyVec2::new(1.0, 0.0), ..PilotInput::default() };
+        run_for(&mut state, 4.0, &[right]);
+        let pos = state.players[0].player.position;
+        assert_eq!(pos.x, VIEW.x);
+        assert_eq!(pos.y, 360.0);
+    }
+
+    #[test]
+    fn a_wrapped_enemy_comes_back_on_the_far_side() {
+        let (mut pos, mut velocity) = (Vec2::new(VIEW.x + 31.0, 200.0), Vec2::new(80.0, 0.0));
+        assert!(apply_boundary(EdgePolicy::Wrap, EdgeResponse::Bounce, VIEW, &mut pos, &mut velocity, 30.0));
+        assert_eq!(pos, Vec2::new(-30.0, 200.0));
+        assert_eq!(velocity, Vec2::new(80.0, 0.0));
+    }
+
+    #[test]
+    fn a_destroyed_shot_is_removed_at_the_edge() {
+        let mut state = headless_run(1);
+        state.config.edges.bullets = EdgePolicy::Destroy;
+        let pos = to_mac_vec2(state.players[0].player.position);
+        state.shoot(pos, 0.0, 0, false);
+        state.bullets[0].pos = Vec2::new(VIEW.x - 1.0, 360.0);
+        state.move_bullets(STEP);
+        assert!(state.bullets.is_empty());
+    }
+
+    #[test]
+    fn overlapping_shots_cancel_each_other_in_one_update() {
+        for cancel in [true, false] {
+            let mut state = headless_run(1);
+            state.config.bullet_cancel = cancel;
+            let pos = to_mac_vec2(state.players[0].player.position);
+            state.shoot(pos, 0.0, 0, false);
+            let muzzle = state.bullets[0].pos;
+            let look = state.config.enemy_bullet_look;
+            fire_pattern(&mut state.enemy_bullets, muzzle, BulletPattern::Aimed { count: 1, spread: 0.0 }, std::f32::consts::PI, 0.0, look);
+            state.update(STEP, &[]);
+            assert_eq!(state.bullets.is_empty(), cancel);
+            assert_eq!(state.enemy_bullets.is_empty(), cancel);
+        }
+    }
+
+    #[test]
+    fn a_mini_bomb_clears_only_its_radius() {
+        let mut state = headless_run(1);
+        state.god_mode = true;
+        state.config.special_ring_radius = Some(200.0);
+        let damage = state.config.special_ring_damage;
+        let ids = drifter_row(&mut state, &[100.0, 150.0, 600.0], damage);
+        let pos = to_mac_vec2(state.players[0].player.position);
+        let look = state.config.enemy_bullet_look;
+        let away = BulletPattern::Aimed { count: 1, spread: 0.0 };
+        fire_pattern(&mut state.enemy_bullets, pos + Vec2::new(0.0, 120.0), away, std::f32::consts::FRAC_PI_2, 0.0, look);
+        fire_pattern(&mut state.enemy_bullets, pos + Vec2::new(-550.0, 0.0), away, std::f32::consts::PI, 0.0, look);
+        state.special_meter = 1.0;
+        state.update(STEP, &[PilotInput { special: true, ..PilotInput::default() }]);
+        assert!(state.players[0].invuln_timer > 0.0);
+        while !state.special_rings.is_empty() {
+            state.update(STEP, &[]);
+        }
+        assert!(state.enemies.get(ids[0]).is_none() && state.enemies.get(ids[1]).is_none());
+        assert!(state.enemies.get(ids[2]).is_some());
+        assert_eq!(state.enemy_bullets.len(), 1);
+        assert!(state.enemy_bullets[0].pos.x < pos.x);
+    }
+
+    #[test]
+    fn shots_bloom_only_within_the_spread_cone() {
+        for spread in [0.0, 5.0] {
+            let mut state = headless_run(1);
+            state.config.blaster.spread = spread;
+            let pos = to_mac_vec2(state.players[0].player.position);
+            for _ in 0..50 {
+                state.shoot(pos, 0.7, 0, false);
+            }
+            let deviations: Vec<f32> = state.bullets.iter().map(|b| (b.velocity.to_angle() - 0.7).abs()).collect();
+            let widest = deviations.iter().cloned().fold(0.0, f32::max);
+            if spread == 0.0 {
+                assert!(widest < 1e-5);
+            } else {
+                assert!(widest > 0.0 && widest <= spread.to_radians() + 1e-5);
+            }
+        }
+    }
+
+    #[test]
+    fn three_quick_kills_earn_the_chain_bonus() {
+        let mut state = headless_run(1);
+        state.config.chain_bonus = 20;
+        state.config.chain_growth = 2.0;
+        let enemy = Enemy::new(EnemyKind::Drifter, Vec2::new(200.0, 200.0));
+        for _ in 0..3 {
+            state.on_enemy_killed(&enemy);
+            run_for(&mut state, 0.1, &[]);
+        }
+        // Second link 20 * 1^2, third 20 * 2^2
+        assert_eq!(state.chain_count, 3);
+        assert_eq!(state.stats.chain_points, 20 + 80);
+
+        // A kill after the window has lapsed starts over
+        let window = state.config.chain_window;
+        run_for(&mut state, window, &[]);
+        state.on_enemy_killed(&enemy);
+        assert_eq!(state.chain_count, 1);
+        assert_eq!(state.stats.chain_points, 100);
+    }
+
+    #[test]
+    fn a_lobbed_shell_arcs_under_gravity() {
+        let mut state = headless_run(1);
+        state.config.mortar_shot.spread = 0.0;
+        let (gravity, speed) = (state.config.mortar_gravity, state.config.mortar_shot.speed);
+        let pos = to_mac_vec2(state.players[0].player.position);
+        state.shoot(pos, 0.0, 0, true);
+        let start = state.bullets[0].pos;
+        let mut drops = Vec::new();
+        for n in 1..=10 {
+            state.move_bullets(STEP);
+            let shell = &state.bullets[0];
+            let t = n as f32 * STEP;
+            assert!((shell.pos.x - (start.x + speed * t)).abs() < 1e-2);
+            assert!((shell.velocity.y - gravity * t).abs() < 1e-2);
+            drops.push(shell.pos.y - start.y);
+        }
+        // Each step falls further than the last
+        let falls: Vec<f32> = drops.windows(2).map(|w| w[1] - w[0]).collect();
+        assert!(drops[0] > 0.0);
+        assert!(falls.windows(2).all(|w| w[1] > w[0]));
+    }
+
+    #[test]
+    fn a_capped_trail_never_grows_past_its_cap() {
+        let mut state = headless_run(1);
+        state.config.trail.max_segments = 5;
+        let mut longest = 0;
+        for i in 0..600 {
+            let angle = i as f32 * 0.02;
+            let input = PilotInput { movement: JoyVec2::new(angle.cos(), angle.sin()), ..PilotInput::default() };
+            state.update(STEP, &[input]);
+            longest = longest.max(state.trail.len());
+        }
+        assert_eq!(longest, 5);
+    }
+
+    #[test]
+    fn a_slow_field_holds_back_whatever_is_inside() {
+        let travel = |slowed: bool| {
+            let mut state = headless_run(1);
+            if slowed {
+                let pos = to_mac_vec2(state.players[0].player.position);
+                state.slow_fields.push(SlowField { pos, radius: 500.0 });
+            }
+            let right = PilotInput { movement: JoyVec2::new(1.0, 0.0), ..PilotInput::default() };
+            run_for(&mut state, 0.5, &[right]);
+            state.players[0].player.position.x - 640.0
+        };
+        let (free, slowed) = (travel(false), travel(true));
+        assert!(slowed > 0.0);
+        assert!(slowed < free * 0.9);
+    }
+
+    #[test]
+    fn a_square_gate_corner_reaches_the_pilot_at_full_tilt() {
+        let corner = PilotInput { movement: JoyVec2::new(1.0, 1.0), ..PilotInput::default() };
+        for (gate, expected) in [(StickGate::Square, 1.0), (StickGate::Circle, std::f32::consts::FRAC_1_SQRT_2)] {
+            let mut state = headless_run(1);
+            state.config.sticks.gate = gate;
+            state.config.input_smoothing = 0.0;
+            state.players[0].control = Control::TwinStick;
+            let input = state.resolve_input(0, corner, STEP);
+            assert!((input.movement.x - expected).abs() < 1e-5);
+            assert!((input.movement.y - expected).abs() < 1e-5);
+        }
+    }
+
+    #[test]
+    fn a_resumed_run_carries_on_where_it_was_saved() {
+        let mut state = replay(circling_script(0xBEEF));
+        let pos = to_mac_vec2(state.players[0].player.position) + Vec2::new(300.0, 0.0);
+        let carrier = state.make_enemy(EnemyKind::Carrier, pos);
+        state.add_enemy(carrier);
+        let save = state.run_save();
+
+        let mut resumed = headless_run(1);
+        resumed.resume_run(save.clone());
+        assert_eq!(resumed.run_save().enemies, save.enemies);
+        assert!(save.enemies.iter().any(|e| e.cargo.is_some()));
+        assert_eq!(resumed.rng.next_u64(), state.rng.next_u64());
+        assert_eq!(resumed.waves.rng_state(), state.waves.rng_state());
+    }
+
+    #[test]
+    fn only_todays_daily_save_can_be_resumed() {
+        let state = headless_run(1);
+        let mut save = state.run_save();
+        save.mode = GameMode::Daily;
+        save.seed = daily_seed(&state.today);
+        assert!(state.resumable(&save));
+        save.seed = daily_seed("2000-01-01");
+        assert!(!state.resumable(&save));
+        save.mode = GameMode::Normal;
+        assert!(state.resumable(&save));
+    }
+
+    #[test]
+    fn a_resize_moves_the_whole_arena_with_it() {
+        let mut state = headless_run(1);
+        let ids = drifter_row(&mut state, &[200.0], 3);
+        let pilot = to_mac_vec2(state.players[0].player.position);
+        state.shoot(pilot, 0.0, 0, false);
+        let look = state.config.enemy_bullet_look;
+        fire_pattern(&mut state.enemy_bullets, Vec2::new(400.0, 100.0), BulletPattern::Radial { count: 1 }, 0.0, 0.0, look);
+        state.telegraphs.push(SpawnTelegraph { kind: EnemyKind::Chaser, pos: Vec2::new(1200.0, 700.0), life: Lifetime::new(1.0) });
+        state.slow_fields.push(SlowField { pos: Vec2::new(1000.0, 600.0), radius: 100.0 });
+        let enemy = state.enemies[ids[0]].pos;
+        let (shot, shot_prev) = (state.bullets[0].pos, state.bullets[0].prev_pos);
+
+        state.resize_to(VIEW / 2.0);
+        let half = |v: Vec2| v / 2.0;
+        assert_eq!(to_mac_vec2(state.players[0].player.position), half(pilot));
+        assert_eq!(to_mac_vec2(state.players[0].prev_position), half(pilot));
+        assert_eq!(state.enemies[ids[0]].pos, half(enemy));
+        assert_eq!(state.enemies[ids[0]].prev_pos, half(enemy));
+        assert_eq!((state.bullets[0].pos, state.bullets[0].prev_pos), (half(shot), half(shot_prev)));
+        assert_eq!(state.enemy_bullets[0].pos, Vec2::new(200.0, 50.0));
+        assert_eq!(state.telegraphs[0].pos, Vec2::new(600.0, 350.0));
+        assert_eq!(state.slow_fields[0].pos, Vec2::new(500.0, 300.0));
+    }
+
+    #[test]
+    fn hitstops_either_side_of_a_second_stay_within_budget() {
+        let mut state = headless_run(1);
+        run_for(&mut state, 0.9, &[]);
+        let (budget, window) = (state.config.hitstop.budget, state.config.hitstop.window);
+
+        // A stop asked for every step from just before the 1s mark to just after
+        let mut frozen = 0.0;
+        for _ in 0..(0.3 / STEP).round() as usize {
+            state.request_hitstop(0.05);
+            let before = state.time;
+            state.update(STEP, &[]);
+            if state.time == before {
+                frozen += STEP;
+            }
+        }
+        assert!(frozen <= budget + budget * 0.3 / window + STEP, "froze {frozen}s");
+    }
+}
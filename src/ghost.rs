@@ -0,0 +1,59 @@
+use macroquad::prelude::*;
+use std::collections::VecDeque;
+
+/// 👻 One recorded snapshot of the player
+#[derive(Debug, Clone, Copy)]
+pub struct GhostSample {
+    pub time: f32,
+    pub pos: Vec2,
+    pub rotation: f32,
+}
+
+/// 👻 Fixed-interval ring buffer of the player's path
+#[derive(Debug, Clone, Default)]
+pub struct GhostRecorder {
+    samples: VecDeque<GhostSample>,
+    interval: f32,
+    capacity: usize,
+    next_sample_time: f32,
+}
+
+impl GhostRecorder {
+    pub fn new(interval: f32, capacity: usize) -> Self {
+        Self {
+            samples: VecDeque::with_capacity(capacity),
+            interval,
+            capacity,
+            next_sample_time: 0.0,
+        }
+    }
+
+    /// Record a sample if the interval has elapsed since the last one
+    pub fn record(&mut self, time: f32, pos: Vec2, rotation: f32) {
+        if self.capacity == 0 || time < self.next_sample_time {
+            return;
+        }
+
+        if self.samples.len() == self.capacity {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(GhostSample { time, pos, rotation });
+        self.next_sample_time = time + self.interval;
+    }
+
+    pub fn samples(&self) -> impl Iterator<Item = &GhostSample> {
+        self.samples.iter()
+    }
+
+    /// Latest sample recorded at or before `time`, if the ghost has started
+    pub fn sample_at(&self, time: f32) -> Option<&GhostSample> {
+        let first = self.samples.front()?;
+        let last = self.samples.back()?;
+        if time < first.time || time > last.time + self.interval {
+            return None;
+        }
+
+        let index = self.samples.partition_point(|s| s.time <= time);
+        self.samples.get(index.saturating_sub(1))
+    }
+}
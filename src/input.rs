@@ -0,0 +1,47 @@
+use crate::joystick::Vec2;
+
+/// 🎮 Everything one pilot asked for during a step
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PilotInput {
+    pub movement: Vec2,
+    pub aim: Vec2,
+    pub firing: bool,
+    /// How hard fire is pressed (0.0 - 1.0); scales the fire rate
+    pub trigger: f32,
+    pub parry: bool,
+    /// Held: slow, precise movement with the hitbox shown
+    pub focus: bool,
+    pub special: bool,
+    pub dash: bool,
+}
+
+impl PilotInput {
+    /// Keep presses from an earlier frame that no step has acted on yet
+    pub fn latch(&mut self, earlier: &PilotInput) {
+        self.parry |= earlier.parry;
+        self.special |= earlier.special;
+        self.dash |= earlier.dash;
+    }
+
+    /// A step has seen this frame's presses; later ones only see what's held
+    pub fn spend_presses(&mut self) {
+        self.parry = false;
+        self.special = false;
+        self.dash = false;
+    }
+}
+
+/// 🎮 One step's worth of input: how far to step and what each pilot (in
+/// seat order) asked for. Missing pilots get no input.
+#[derive(Debug, Clone, Default)]
+pub struct StepInput {
+    pub dt: f32,
+    pub pilots: Vec<PilotInput>,
+}
+
+/// 🎮 Where a run's input comes from, one step at a time: the window for a
+/// live run, a script for a replay or a test
+pub trait InputSource {
+    /// Input for the next step; None once the source has run out
+    fn next_step(&mut self) -> Option<StepInput>;
+}
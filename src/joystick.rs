@@ -28,6 +28,12 @@ impl Vec2 {
     }
 }
 
+impl std::fmt::Display for Vec2 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({:.2}, {:.2})", self.x, self.y)
+    }
+}
+
 impl std::ops::Add for Vec2 {
     type Output = Vec2;
 
@@ -79,6 +85,42 @@ impl std::ops::AddAssign for Vec2 {
     }
 }
 
+/// 🕹️ Shape the thumb is confined to. A square gate reaches full tilt on
+/// both axes at once in the corners, which suits pure 8-way movement.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StickGate {
+    Circle,
+    Square,
+}
+
+impl StickGate {
+    pub fn name(self) -> &'static str {
+        match self {
+            StickGate::Circle => "circle",
+            StickGate::Square => "square",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<StickGate> {
+        [StickGate::Circle, StickGate::Square]
+            .into_iter()
+            .find(|g| g.name() == name)
+    }
+
+    /// Pull a stick vector back inside the gate: its length for a circle,
+    /// each axis to -1.0 - 1.0 for a square
+    pub fn clamp(self, v: Vec2) -> Vec2 {
+        match self {
+            StickGate::Circle if v.length() > 1.0 => v.normalize(),
+            StickGate::Circle => v,
+            StickGate::Square => Vec2 {
+                x: v.x.clamp(-1.0, 1.0),
+                y: v.y.clamp(-1.0, 1.0),
+            },
+        }
+    }
+}
+
 /// 🕹️ Joystick structure for touch input
 #[derive(Debug, Clone)]
 pub struct Joystick {
@@ -90,15 +132,18 @@ pub struct Joystick {
     pub active: bool,
     /// Max joystick reach (radius)
     pub radius: f32,
+    /// Circular or per-axis clamping of the thumb
+    pub gate: StickGate,
 }
 
 impl Joystick {
-    pub fn new(radius: f32) -> Self {
+    pub fn new(radius: f32, gate: StickGate) -> Self {
         Self {
             center: Vec2::ZERO,
             current: Vec2::ZERO,
             active: false,
             radius,
+            gate,
         }
     }
 
@@ -117,9 +162,17 @@ impl Joystick {
 
         let mut delta = pos - self.center;
 
-        // Keep joystick inside circle
-        if delta.length() > self.radius {
-            delta = delta.normalize() * self.radius;
+        // Keep joystick inside the gate
+        match self.gate {
+            StickGate::Circle => {
+                if delta.length() > self.radius {
+                    delta = delta.normalize() * self.radius;
+                }
+            }
+            StickGate::Square => {
+                delta.x = delta.x.clamp(-self.radius, self.radius);
+                delta.y = delta.y.clamp(-self.radius, self.radius);
+            }
         }
 
         self.current = self.center + delta;
@@ -131,12 +184,102 @@ impl Joystick {
         self.current = self.center;
     }
 
-    /// 🎮 Get movement vector from -1.0 to 1.0 on both axes
+    /// 🎮 Get movement vector from -1.0 to 1.0 on both axes (a square gate's
+    /// corners give (±1, ±1))
     pub fn get_input(&self) -> Vec2 {
         if !self.active {
             return Vec2::ZERO;
         }
 
-        (self.current - self.center) / self.radius
+        self.gate.clamp((self.current - self.center) / self.radius)
+    }
+
+    /// 🎮 How far the stick is pushed, from 0.0 (centered) to 1.0 (at the rim)
+    pub fn magnitude(&self) -> f32 {
+        self.get_input().length().min(1.0)
+    }
+}
+
+/// 🎚️ Sensitivity and exponential smoothing for one stick's output
+#[derive(Debug, Clone, Copy, Default)]
+pub struct InputFilter {
+    value: Vec2,
+}
+
+impl InputFilter {
+    /// Feed this frame's raw stick vector and return the filtered one.
+    /// `smoothing` is the share of the old value kept per 60Hz frame
+    /// (0.0 passes input straight through); output is clamped to the
+    /// stick's `gate`, so a square gate's corners stay at full tilt.
+    pub fn apply(&mut self, raw: Vec2, gate: StickGate, sensitivity: f32, smoothing: f32, dt: f32) -> Vec2 {
+        let target = gate.clamp(raw * sensitivity);
+
+        if smoothing <= 0.0 {
+            self.value = target;
+        } else {
+            let blend = 1.0 - smoothing.min(0.99).powf(dt * 60.0);
+            self.value += (target - self.value) * blend;
+        }
+        self.value
+    }
+
+    /// Last filtered value
+    pub fn value(&self) -> Vec2 {
+        self.value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A stick of radius 50 pushed from the origin to `to`
+    fn pushed(gate: StickGate, to: Vec2) -> Vec2 {
+        let mut stick = Joystick::new(50.0, gate);
+        stick.on_touch_start(Vec2::ZERO);
+        stick.on_touch_move(to);
+        stick.get_input()
+    }
+
+    #[test]
+    fn a_square_gate_reaches_full_tilt_in_the_corners() {
+        let corner = pushed(StickGate::Square, Vec2::new(80.0, -80.0));
+        assert_eq!((corner.x, corner.y), (1.0, -1.0));
+    }
+
+    #[test]
+    fn a_circle_gate_keeps_the_thumb_on_the_unit_circle() {
+        let corner = pushed(StickGate::Circle, Vec2::new(80.0, -80.0));
+        assert!((corner.length() - 1.0).abs() < 1e-5);
+        assert!((corner.x - std::f32::consts::FRAC_1_SQRT_2).abs() < 1e-5);
+        let inside = pushed(StickGate::Circle, Vec2::new(20.0, 0.0));
+        assert_eq!((inside.x, inside.y), (0.4, 0.0));
+    }
+
+    #[test]
+    fn vectors_print_readably() {
+        let v = Vec2::new(1.0, -2.345);
+        assert_eq!(v.to_string(), "(1.00, -2.35)");
+        assert_eq!(format!("{:?}", v), "Vec2 { x: 1.0, y: -2.345 }");
+    }
+
+    #[test]
+    fn zero_smoothing_passes_input_straight_through() {
+        let mut filter = InputFilter::default();
+        for raw in [Vec2::new(0.3, -0.4), Vec2::new(-1.0, 0.0), Vec2::ZERO] {
+            let out = filter.apply(raw, StickGate::Circle, 1.0, 0.0, 1.0 / 60.0);
+            assert_eq!((out.x, out.y), (raw.x, raw.y));
+        }
+    }
+
+    #[test]
+    fn smoothing_eases_toward_the_input() {
+        let mut filter = InputFilter::default();
+        let first = filter.apply(Vec2::new(1.0, 0.0), StickGate::Circle, 1.0, 0.5, 1.0 / 60.0);
+        assert!((first.x - 0.5).abs() < 1e-5);
+        for _ in 0..60 {
+            filter.apply(Vec2::new(1.0, 0.0), StickGate::Circle, 1.0, 0.5, 1.0 / 60.0);
+        }
+        assert!((filter.value().x - 1.0).abs() < 1e-5);
     }
 }
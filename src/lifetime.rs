@@ -0,0 +1,132 @@
+use crate::arena::{Arena, Id};
+
+/// ⏳ Countdown for something that expires on its own
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Lifetime {
+    remaining: f32,
+    total: f32,
+}
+
+impl Lifetime {
+    pub fn new(seconds: f32) -> Self {
+        Self { remaining: seconds, total: seconds }
+    }
+
+    pub fn tick(&mut self, dt: f32) {
+        self.remaining -= dt;
+    }
+
+    /// Cut it short; gone at the next reap
+    pub fn end(&mut self) {
+        self.remaining = 0.0;
+    }
+
+    pub fn expired(&self) -> bool {
+        self.remaining <= 0.0
+    }
+
+    pub fn remaining(&self) -> f32 {
+        self.remaining
+    }
+
+    /// Share of the lifetime still to go (1.0 when new, 0.0 once expired)
+    pub fn fraction(&self) -> f32 {
+        (self.remaining / self.total).max(0.0)
+    }
+}
+
+/// Anything carrying a [`Lifetime`] that should be reaped once it runs out
+pub trait Transient {
+    /// How fast the lifetime runs down, relative to the clock
+    const DECAY: f32 = 1.0;
+
+    fn lifetime(&mut self) -> &mut Lifetime;
+}
+
+/// 🧹 Count every item down by `dt` and take out the ones that expired,
+/// keeping the rest in order
+pub fn reap<T: Transient>(items: &mut Vec<T>, dt: f32) -> Vec<T> {
+    items
+        .extract_if(.., |item| {
+            let lifetime = item.lifetime();
+            lifetime.tick(dt * T::DECAY);
+            lifetime.expired()
+        })
+        .collect()
+}
+
+/// 🧹 [`reap`] for entities kept in an [`Arena`]
+pub fn reap_arena<T: Transient>(items: &mut Arena<T>, dt: f32) -> Vec<T> {
+    let expired: Vec<Id> = items
+        .iter_mut()
+        .filter_map(|(id, item)| {
+            let lifetime = item.lifetime();
+            lifetime.tick(dt * T::DECAY);
+            lifetime.expired().then_some(id)
+        })
+        .collect();
+    expired.into_iter().filter_map(|id| items.remove(id)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Spark(u32, Lifetime);
+
+    impl Transient for Spark {
+        fn lifetime(&mut self) -> &mut Lifetime {
+            &mut self.1
+        }
+    }
+
+    // Burns down twice as fast as the clock
+    struct Flash(Lifetime);
+
+    impl Transient for Flash {
+        const DECAY: f32 = 2.0;
+
+        fn lifetime(&mut self) -> &mut Lifetime {
+            &mut self.0
+        }
+    }
+
+    #[test]
+    fn a_one_second_lifetime_is_reaped_after_a_second() {
+        let mut sparks = vec![Spark(0, Lifetime::new(1.0))];
+        for _ in 0..3 {
+            assert!(reap(&mut sparks, 0.25).is_empty());
+        }
+        assert_eq!(sparks.len(), 1);
+        assert_eq!(reap(&mut sparks, 0.25).len(), 1);
+        assert!(sparks.is_empty());
+    }
+
+    #[test]
+    fn reaping_keeps_the_survivors_and_the_reaped_in_order() {
+        let lives = [0.5, 2.0, 0.1, 3.0, 0.2];
+        let mut sparks: Vec<Spark> = lives.iter().enumerate().map(|(i, &s)| Spark(i as u32, Lifetime::new(s))).collect();
+        let reaped = reap(&mut sparks, 1.0);
+        assert_eq!(reaped.iter().map(|s| s.0).collect::<Vec<_>>(), vec![0, 2, 4]);
+        assert_eq!(sparks.iter().map(|s| s.0).collect::<Vec<_>>(), vec![1, 3]);
+    }
+
+    #[test]
+    fn arena_reaping_keeps_slot_order() {
+        let mut sparks = Arena::new();
+        for (i, &seconds) in [0.5, 2.0, 0.1, 3.0].iter().enumerate() {
+            sparks.insert(Spark(i as u32, Lifetime::new(seconds)));
+        }
+        let reaped = reap_arena(&mut sparks, 1.0);
+        assert_eq!(reaped.iter().map(|s| s.0).collect::<Vec<_>>(), vec![0, 2]);
+        assert_eq!(sparks.values().map(|s| s.0).collect::<Vec<_>>(), vec![1, 3]);
+    }
+
+    #[test]
+    fn decay_scales_how_fast_a_lifetime_runs_down() {
+        let mut flashes = vec![Flash(Lifetime::new(1.0))];
+        assert!(reap(&mut flashes, 0.45).is_empty());
+        assert!((flashes[0].0.remaining() - 0.1).abs() < 1e-5);
+        assert_eq!(reap(&mut flashes, 0.06).len(), 1);
+    }
+}
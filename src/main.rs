@@ -1,9 +1,26 @@
 mod joystick;
 mod player;
 mod game;
+mod config;
+mod ghost;
+mod enemy;
+mod wave;
+mod postfx;
+mod persistence;
+mod score;
+mod rng;
+mod achievement;
+mod menu;
+mod codex;
+mod input;
+mod script;
+mod arena;
+mod lifetime;
+mod save;
 
 use macroquad::prelude::*;
 use game::GameState;
+use script::ScriptedInput;
 
 fn window_conf() -> Conf {
     Conf {
@@ -18,16 +35,64 @@ fn window_conf() -> Conf {
 #[macroquad::main(window_conf)]
 async fn main() {
     let mut game = GameState::new();
+    if let Some(script) = load_script() {
+        game.play_script(script);
+    }
 
     loop {
+        let frame_start = get_time();
         let dt = get_frame_time();
 
         // Update game state
-        game.update(dt);
+        game.tick(dt);
 
         // Draw everything
         game.draw();
 
+        if game.quit_requested() {
+            break;
+        }
+
+        pace_frame(frame_start, game.fps_cap());
         next_frame().await
     }
 }
+
+/// 🎬 `--script <file>` replays recorded input in place of the player's
+#[cfg(not(target_arch = "wasm32"))]
+fn load_script() -> Option<ScriptedInput> {
+    let path = std::env::args().skip_while(|arg| arg != "--script").nth(1)?;
+    let parsed = std::fs::read_to_string(&path)
+        .map_err(|err| err.to_string())
+        .and_then(|text| ScriptedInput::parse(&text));
+    match parsed {
+        Ok(script) => Some(script),
+        Err(err) => {
+            eprintln!("can't play script {}: {}", path, err);
+            None
+        }
+    }
+}
+
+// No command line in the browser
+#[cfg(target_arch = "wasm32")]
+fn load_script() -> Option<ScriptedInput> {
+    None
+}
+
+/// ⏱️ Sleep off whatever is left of the frame budget. The simulation uses the
+/// measured frame time, so capping only changes smoothness, not game speed.
+#[cfg(not(target_arch = "wasm32"))]
+fn pace_frame(frame_start: f64, fps_cap: Option<u32>) {
+    let Some(fps) = fps_cap.filter(|&fps| fps > 0) else {
+        return;
+    };
+    let remaining = 1.0 / fps as f64 - (get_time() - frame_start);
+    if remaining > 0.0 {
+        std::thread::sleep(std::time::Duration::from_secs_f64(remaining));
+    }
+}
+
+// The browser already paces frames with requestAnimationFrame
+#[cfg(target_arch = "wasm32")]
+fn pace_frame(_frame_start: f64, _fps_cap: Option<u32>) {}
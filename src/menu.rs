@@ -0,0 +1,128 @@
+use macroquad::prelude::*;
+
+// Spacing between entries and the clickable half-width around the center
+const ITEM_HEIGHT: f32 = 50.0;
+const ITEM_HALF_WIDTH: f32 = 150.0;
+const ITEM_FONT_SIZE: f32 = 36.0;
+
+// Number keys pick entries directly, in order
+const SHORTCUT_KEYS: [KeyCode; 9] = [
+    KeyCode::Key1,
+    KeyCode::Key2,
+    KeyCode::Key3,
+    KeyCode::Key4,
+    KeyCode::Key5,
+    KeyCode::Key6,
+    KeyCode::Key7,
+    KeyCode::Key8,
+    KeyCode::Key9,
+];
+
+/// 📋 A centered vertical list of entries, driven by arrow keys, number
+/// shortcuts, or a click/tap on an entry. Layout hangs off `top`, the
+/// baseline of the first entry, so callers can place it anywhere.
+#[derive(Debug, Clone)]
+pub struct Menu {
+    len: usize,
+    selected: usize,
+    item_height: f32,
+}
+
+impl Menu {
+    pub fn new(len: usize) -> Self {
+        Self { len, selected: 0, item_height: ITEM_HEIGHT }
+    }
+
+    /// Pack entries `item_height` apart, text scaled to match, for long lists
+    pub fn compact(mut self, item_height: f32) -> Self {
+        self.item_height = item_height;
+        self
+    }
+
+    // Text size and the clickable band above/below each baseline, all
+    // scaled with the spacing
+    fn scale(&self) -> f32 {
+        self.item_height / ITEM_HEIGHT
+    }
+
+    pub fn selected(&self) -> usize {
+        self.selected
+    }
+
+    /// Start with entry `index` selected
+    pub fn with_selected(mut self, index: usize) -> Self {
+        self.selected = index.min(self.len.saturating_sub(1));
+        self
+    }
+
+    /// Baseline y of entry `index`
+    pub fn item_y(&self, top: f32, index: usize) -> f32 {
+        top + index as f32 * self.item_height
+    }
+
+    /// Entry under `point`, if any
+    pub fn item_at(&self, top: f32, point: Vec2) -> Option<usize> {
+        (0..self.len).find(|&i| {
+            let y = self.item_y(top, i);
+            let scale = self.scale();
+            (point.x - screen_width() / 2.0).abs() < ITEM_HALF_WIDTH && point.y > y - 36.0 * scale && point.y < y + 14.0 * scale
+        })
+    }
+
+    /// 🎮 Move the selection from this frame's input; returns an entry
+    /// once it's chosen
+    pub fn update(&mut self, top: f32) -> Option<usize> {
+        if self.len == 0 {
+            return None;
+        }
+        if is_key_pressed(KeyCode::Up) || is_key_pressed(KeyCode::W) {
+            self.selected = (self.selected + self.len - 1) % self.len;
+        }
+        if is_key_pressed(KeyCode::Down) || is_key_pressed(KeyCode::S) {
+            self.selected = (self.selected + 1) % self.len;
+        }
+
+        let mut chosen = None;
+        if is_key_pressed(KeyCode::Enter) || is_key_pressed(KeyCode::Space) {
+            chosen = Some(self.selected);
+        }
+        for (i, key) in SHORTCUT_KEYS.into_iter().take(self.len).enumerate() {
+            if is_key_pressed(key) {
+                chosen = Some(i);
+            }
+        }
+
+        let taps = touches()
+            .into_iter()
+            .filter(|t| t.phase == TouchPhase::Started)
+            .map(|t| t.position)
+            .chain(is_mouse_button_pressed(MouseButton::Left).then(|| Vec2::from(mouse_position())));
+        for tap in taps {
+            if let Some(i) = self.item_at(top, tap) {
+                chosen = Some(i);
+            }
+        }
+
+        if let Some(i) = chosen {
+            self.selected = i;
+        }
+        chosen
+    }
+
+    /// 🎨 Draw `labels` (one per entry), the selection pulsing in gold
+    pub fn draw(&self, top: f32, labels: &[&str]) {
+        let pulse = (get_time() * 4.0).sin() as f32 * 0.2 + 0.8;
+        let font_size = ITEM_FONT_SIZE * self.scale();
+        for (i, label) in labels.iter().enumerate() {
+            let selected = i == self.selected;
+            let text = if selected { format!("> {} <", label) } else { label.to_string() };
+            let width = measure_text(&text, None, font_size as u16, 1.0).width;
+            let color = if selected {
+                Color::new(1.0, 0.9, 0.4, pulse)
+            } else {
+                Color::from_rgba(180, 190, 220, 200)
+            };
+            draw_text(&text, (screen_width() - width) / 2.0, self.item_y(top, i), font_size, color);
+        }
+    }
+}
@@ -0,0 +1,86 @@
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+// Plain `key=value` lines; unknown keys are kept so older builds don't
+// clobber settings written by newer ones
+#[derive(Debug, Clone, Default)]
+pub struct Store {
+    path: &'static str,
+    values: BTreeMap<String, String>,
+}
+
+impl Store {
+    /// 💾 Load a store from disk (a missing or unreadable file is just empty)
+    pub fn load(path: &'static str) -> Self {
+        let values = read_file(path)
+            .lines()
+            .filter_map(|line| line.split_once('='))
+            .map(|(key, value)| (key.trim().to_owned(), value.trim().to_owned()))
+            .collect();
+        Self { path, values }
+    }
+
+    pub fn get<T: FromStr>(&self, key: &str) -> Option<T> {
+        self.values.get(key)?.parse().ok()
+    }
+
+    pub fn set(&mut self, key: &str, value: impl ToString) {
+        self.values.insert(key.to_owned(), value.to_string());
+    }
+
+    /// 💾 Write the store back; failures are ignored since nothing here is critical
+    pub fn save(&self) {
+        let text: String = self
+            .values
+            .iter()
+            .map(|(key, value)| format!("{}={}\n", key, value))
+            .collect();
+        write_file(self.path, &text);
+    }
+}
+
+/// 📝 Append one line to a log file. The file is opened in append mode for
+/// every write, so a crash can at worst lose the line being written.
+pub fn append_line(path: &'static str, line: &str) {
+    append_file(path, &format!("{}\n", line));
+}
+
+/// 🗑️ Remove a file written here; a missing one is fine
+#[cfg(not(target_arch = "wasm32"))]
+pub fn delete_file(path: &str) {
+    let _ = std::fs::remove_file(path);
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn read_file(path: &str) -> String {
+    std::fs::read_to_string(path).unwrap_or_default()
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn write_file(path: &str, text: &str) {
+    let _ = std::fs::write(path, text);
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn append_file(path: &str, text: &str) {
+    use std::io::Write;
+    let file = std::fs::OpenOptions::new().create(true).append(true).open(path);
+    if let Ok(mut file) = file {
+        let _ = file.write_all(text.as_bytes());
+    }
+}
+
+// No filesystem in the browser build; settings last for the session only
+#[cfg(target_arch = "wasm32")]
+fn read_file(_path: &str) -> String {
+    String::new()
+}
+
+#[cfg(target_arch = "wasm32")]
+fn write_file(_path: &str, _text: &str) {}
+
+#[cfg(target_arch = "wasm32")]
+fn append_file(_path: &str, _text: &str) {}
+
+#[cfg(target_arch = "wasm32")]
+pub fn delete_file(_path: &str) {}
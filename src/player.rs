@@ -5,24 +5,106 @@ pub struct Player {
     pub position: Vec2,
     pub velocity: Vec2,
     pub rotation: f32, // in radians
+    /// Exponential velocity decay rate per second (framerate independent)
+    pub damping: f32,
+    /// Collision radius around `position`, see [`HITBOX_RADIUS`]
+    pub hitbox_radius: f32,
+    pub thrust_curve: ThrustCurve,
 }
 
+/// 📈 How stick deflection maps to thrust. Only the magnitude is reshaped;
+/// the thrust always points where the stick does.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ThrustCurve {
+    /// Thrust proportional to deflection
+    Linear,
+    /// Gentle near the center for fine positioning, full at the rim
+    EaseIn,
+    /// Most of the thrust comes in early, for instant response
+    Snappy,
+}
+
+impl ThrustCurve {
+    pub fn name(self) -> &'static str {
+        match self {
+            ThrustCurve::Linear => "linear",
+            ThrustCurve::EaseIn => "ease_in",
+            ThrustCurve::Snappy => "snappy",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<ThrustCurve> {
+        [ThrustCurve::Linear, ThrustCurve::EaseIn, ThrustCurve::Snappy]
+            .into_iter()
+            .find(|c| c.name() == name)
+    }
+
+    /// Reshape a deflection in 0.0 - 1.0 (0 stays 0, 1 stays 1)
+    pub fn apply(self, magnitude: f32) -> f32 {
+        let m = magnitude.clamp(0.0, 1.0);
+        match self {
+            ThrustCurve::Linear => m,
+            ThrustCurve::EaseIn => m * m,
+            ThrustCurve::Snappy => m.sqrt(),
+        }
+    }
+}
+
+/// Thrust acceleration at full stick deflection (px/s²)
+pub const THRUST: f32 = 400.0;
+
+/// Matches the old per-frame `* 0.98` damping at 60 FPS: -ln(0.98) * 60
+pub const DEFAULT_DAMPING: f32 = 1.2122;
+
+/// Nose-to-center length of the drawn ship; wings and glow reach about this far
+pub const VISUAL_SIZE: f32 = 40.0;
+
+/// The part of the ship that can actually be hit: roughly the cockpit.
+/// Kept well inside `VISUAL_SIZE` so grazing a wing or the glow is not a death.
+pub const HITBOX_RADIUS: f32 = 14.0;
+
 impl Player {
     pub fn new(start_pos: Vec2) -> Self {
         Self {
             position: start_pos,
             velocity: Vec2::ZERO,
             rotation: 0.0,
+            damping: DEFAULT_DAMPING,
+            hitbox_radius: HITBOX_RADIUS,
+            thrust_curve: ThrustCurve::Linear,
         }
     }
 
+    /// Does a circle of `radius` at `point` overlap the hitbox?
+    pub fn collides_with(&self, point: Vec2, radius: f32) -> bool {
+        (point - self.position).length() < self.hitbox_radius + radius
+    }
+
     /// 🚀 Update player physics based on joystick input
+    ///
+    /// Velocity follows `dv/dt = thrust - damping * v`, integrated exactly over
+    /// each step. Holding full thrust therefore approaches the same terminal
+    /// speed `THRUST * curve(|movement|) / damping` (≈330 px/s by default) at any
+    /// framerate.
     pub fn update(&mut self, movement: Vec2, aim: Vec2, dt: f32) {
-        // Apply movement (thrust)
-        self.velocity += movement * 400.0 * dt;
+        // Reshape the deflection but keep the direction (and any boost
+        // beyond full deflection, e.g. overdrive)
+        let deflection = movement.length();
+        let thrust = if deflection > 0.0 {
+            let shaped = self.thrust_curve.apply(deflection) + (deflection - 1.0).max(0.0);
+            movement * (shaped / deflection * THRUST)
+        } else {
+            Vec2::ZERO
+        };
 
-        // Apply some friction/damping
-        self.velocity = self.velocity * 0.98;
+        if self.damping > 0.0 {
+            // Decay toward the terminal velocity for this input
+            let terminal = thrust / self.damping;
+            let decay = (-self.damping * dt).exp();
+            self.velocity = terminal + (self.velocity - terminal) * decay;
+        } else {
+            self.velocity += thrust * dt;
+        }
 
         // Update position
         self.position += self.velocity * dt;
@@ -37,3 +119,73 @@ impl Player {
         self.velocity = Vec2::ZERO;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn damping_does_not_depend_on_step_size() {
+        let mut coarse = Player::new(Vec2::ZERO);
+        let mut fine = Player::new(Vec2::ZERO);
+        coarse.velocity = Vec2::new(300.0, -120.0);
+        fine.velocity = coarse.velocity;
+
+        coarse.update(Vec2::ZERO, Vec2::ZERO, 0.5);
+        for _ in 0..100 {
+            fine.update(Vec2::ZERO, Vec2::ZERO, 0.005);
+        }
+
+        let expected = 300.0 * (-DEFAULT_DAMPING * 0.5).exp();
+        assert!((coarse.velocity.x - expected).abs() < 1e-3);
+        assert!((coarse.velocity.x - fine.velocity.x).abs() < 1e-3);
+        assert!((coarse.velocity.y - fine.velocity.y).abs() < 1e-3);
+    }
+
+    #[test]
+    fn hitbox_is_smaller_than_the_sprite() {
+        let player = Player::new(Vec2::ZERO);
+
+        // A bullet grazing the wingtip misses, one at the cockpit hits
+        assert!(!player.collides_with(Vec2::new(VISUAL_SIZE - 2.0, 0.0), 2.0));
+        assert!(player.collides_with(Vec2::new(HITBOX_RADIUS, 0.0), 2.0));
+    }
+
+    #[test]
+    fn full_thrust_reaches_the_same_speed_at_any_framerate() {
+        let terminal_speed = |dt: f32| {
+            let mut player = Player::new(Vec2::ZERO);
+            let steps = (10.0 / dt) as usize;
+            for _ in 0..steps {
+                player.update(Vec2::new(1.0, 0.0), Vec2::ZERO, dt);
+            }
+            player.velocity.x
+        };
+
+        let slow = terminal_speed(1.0 / 30.0);
+        let fast = terminal_speed(1.0 / 144.0);
+        assert!((slow - THRUST / DEFAULT_DAMPING).abs() < 1.0);
+        assert!((slow - fast).abs() < 0.5);
+    }
+
+    #[test]
+    fn thrust_curves_keep_the_stick_direction() {
+        let stick = Vec2::new(0.3, -0.4);
+        let mut speeds = Vec::new();
+        for curve in [ThrustCurve::Linear, ThrustCurve::EaseIn, ThrustCurve::Snappy] {
+            let mut player = Player { thrust_curve: curve, ..Player::new(Vec2::ZERO) };
+            player.update(stick, Vec2::ZERO, 0.1);
+            let v = player.velocity;
+            assert!((v.x * stick.y - v.y * stick.x).abs() < 1e-4);
+            assert!(v.x * stick.x + v.y * stick.y > 0.0);
+            speeds.push(v.length());
+
+            // No deflection, no thrust
+            let mut idle = Player { thrust_curve: curve, ..Player::new(Vec2::ZERO) };
+            idle.update(Vec2::ZERO, Vec2::ZERO, 0.1);
+            assert_eq!((idle.velocity.x, idle.velocity.y), (0.0, 0.0));
+        }
+        // At half deflection ease-in is gentler and snappy stronger than linear
+        assert!(speeds[1] < speeds[0] && speeds[0] < speeds[2]);
+    }
+}
@@ -0,0 +1,169 @@
+use macroquad::miniquad::{BlendFactor, BlendState, Equation};
+use macroquad::prelude::*;
+
+/// ✨ Screen-space effects: render the world offscreen, then composite it
+/// back with bloom (a blurred copy of its bright areas) and/or an RGB split
+pub struct PostFx {
+    scene: RenderTarget,
+    bloom: Material,
+    aberration: Material,
+    size: (u32, u32),
+}
+
+impl PostFx {
+    /// Returns `None` if the shaders can't be built, so callers fall back to
+    /// plain stacked-circle glows and no screen effects
+    pub fn new() -> Option<Self> {
+        let bloom = load_material(
+            ShaderSource::Glsl {
+                vertex: VERTEX_SHADER,
+                fragment: BLOOM_FRAGMENT_SHADER,
+            },
+            MaterialParams {
+                pipeline_params: PipelineParams {
+                    color_blend: Some(BlendState::new(Equation::Add, BlendFactor::One, BlendFactor::One)),
+                    ..Default::default()
+                },
+                uniforms: vec![UniformDesc::new("TexelSize", UniformType::Float2)],
+                ..Default::default()
+            },
+        )
+        .ok()?;
+
+        let aberration = load_material(
+            ShaderSource::Glsl {
+                vertex: VERTEX_SHADER,
+                fragment: ABERRATION_FRAGMENT_SHADER,
+            },
+            MaterialParams {
+                uniforms: vec![UniformDesc::new("Offset", UniformType::Float2)],
+                ..Default::default()
+            },
+        )
+        .ok()?;
+
+        let size = (screen_width() as u32, screen_height() as u32);
+        Some(Self {
+            scene: Self::make_target(size),
+            bloom,
+            aberration,
+            size,
+        })
+    }
+
+    fn make_target(size: (u32, u32)) -> RenderTarget {
+        let target = render_target(size.0.max(1), size.1.max(1));
+        target.texture.set_filter(FilterMode::Linear);
+        target
+    }
+
+    /// Recreate the offscreen target if the window was resized
+    pub fn fit_to_screen(&mut self) {
+        let size = (screen_width() as u32, screen_height() as u32);
+        if size != self.size {
+            self.scene = Self::make_target(size);
+            self.size = size;
+        }
+    }
+
+    /// Route subsequent draws through `camera` into the offscreen scene
+    pub fn begin_scene(&self, mut camera: Camera2D) {
+        // Flip y so the texture comes out upright when drawn back
+        camera.zoom.y = -camera.zoom.y;
+        camera.render_target = Some(self.scene.clone());
+        set_camera(&camera);
+    }
+
+    /// Draw the scene to the screen, split into offset red/blue channels by
+    /// `aberration` pixels, then the additive bloom layer over it if enabled
+    pub fn composite(&self, bloom: bool, aberration: f32) {
+        set_default_camera();
+        let params = DrawTextureParams {
+            dest_size: Some(vec2(screen_width(), screen_height())),
+            ..Default::default()
+        };
+        let texel = vec2(1.0 / self.size.0 as f32, 1.0 / self.size.1 as f32);
+
+        if aberration > 0.0 {
+            self.aberration.set_uniform("Offset", vec2(aberration, aberration * 0.5) * texel);
+            gl_use_material(&self.aberration);
+            draw_texture_ex(&self.scene.texture, 0.0, 0.0, WHITE, params.clone());
+            gl_use_default_material();
+        } else {
+            draw_texture_ex(&self.scene.texture, 0.0, 0.0, WHITE, params.clone());
+        }
+
+        if bloom {
+            self.bloom.set_uniform("TexelSize", texel);
+            gl_use_material(&self.bloom);
+            draw_texture_ex(&self.scene.texture, 0.0, 0.0, WHITE, params);
+            gl_use_default_material();
+        }
+    }
+}
+
+const VERTEX_SHADER: &str = "#version 100
+attribute vec3 position;
+attribute vec2 texcoord;
+attribute vec4 color0;
+
+varying lowp vec2 uv;
+varying lowp vec4 color;
+
+uniform mat4 Model;
+uniform mat4 Projection;
+
+void main() {
+    gl_Position = Projection * Model * vec4(position, 1);
+    color = color0 / 255.0;
+    uv = texcoord;
+}
+";
+
+// Bright-pass and a wide weighted blur in a single pass
+const BLOOM_FRAGMENT_SHADER: &str = "#version 100
+precision mediump float;
+
+varying vec4 color;
+varying vec2 uv;
+
+uniform sampler2D Texture;
+uniform vec2 TexelSize;
+
+vec3 bright(vec2 p) {
+    vec3 c = texture2D(Texture, p).rgb;
+    float luma = dot(c, vec3(0.299, 0.587, 0.114));
+    return c * smoothstep(0.35, 0.85, luma);
+}
+
+void main() {
+    vec3 sum = vec3(0.0);
+    float total = 0.0;
+    for (int x = -3; x <= 3; x++) {
+        for (int y = -3; y <= 3; y++) {
+            float w = 1.0 / (1.0 + float(x * x + y * y));
+            sum += bright(uv + vec2(float(x), float(y)) * TexelSize * 3.0) * w;
+            total += w;
+        }
+    }
+    gl_FragColor = vec4(sum / total * 1.5, 1.0);
+}
+";
+
+// Red and blue sampled from opposite offsets; green stays put
+const ABERRATION_FRAGMENT_SHADER: &str = "#version 100
+precision mediump float;
+
+varying vec4 color;
+varying vec2 uv;
+
+uniform sampler2D Texture;
+uniform vec2 Offset;
+
+void main() {
+    float r = texture2D(Texture, uv + Offset).r;
+    float g = texture2D(Texture, uv).g;
+    float b = texture2D(Texture, uv - Offset).b;
+    gl_FragColor = vec4(r, g, b, 1.0);
+}
+";
@@ -0,0 +1,94 @@
+use macroquad::miniquad::date;
+
+const SECONDS_PER_DAY: f64 = 86_400.0;
+
+/// 🎲 Small seedable PRNG (SplitMix64). Everything that shapes a run draws
+/// from one of these, so the same seed replays the same spawns and drops.
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u32) -> Self {
+        Self { state: seed as u64 }
+    }
+
+    /// Pick a stream back up where [`Self::state`] left it
+    pub fn from_state(state: u64) -> Self {
+        Self { state }
+    }
+
+    /// Where the stream is, so a saved run can carry on from here
+    pub fn state(&self) -> u64 {
+        self.state
+    }
+
+    /// An independent stream, so one system's draws can't shift another's
+    pub fn fork(&mut self) -> Self {
+        Self { state: self.next_u64() }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform in `0.0..1.0`
+    pub fn next_f32(&mut self) -> f32 {
+        (self.next_u64() >> 40) as f32 / (1u64 << 24) as f32
+    }
+
+    /// Uniform in `low..high`
+    pub fn range(&mut self, low: f32, high: f32) -> f32 {
+        low + (high - low) * self.next_f32()
+    }
+
+    /// Uniform index in `0..n` (`n` must be non-zero)
+    pub fn below(&mut self, n: usize) -> usize {
+        (self.next_u64() % n as u64) as usize
+    }
+
+    /// Fisher-Yates shuffle in place
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i + 1));
+        }
+    }
+}
+
+/// 🎲 A fresh run seed from the wall clock
+pub fn fresh_seed() -> u32 {
+    let mut rng = Rng::new((date::now() * 1000.0) as u64 as u32);
+    rng.next_u64() as u32
+}
+
+/// 📅 Today's UTC date as `YYYY-MM-DD`
+pub fn today() -> String {
+    let days = (date::now() / SECONDS_PER_DAY).floor() as i64;
+    let (year, month, day) = civil_from_days(days);
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+/// 📅 Seed everyone shares on `date` (FNV-1a of the date string)
+pub fn daily_seed(date: &str) -> u32 {
+    date.bytes()
+        .fold(0x811C_9DC5, |hash, byte| (hash ^ byte as u32).wrapping_mul(0x0100_0193))
+}
+
+// Days since 1970-01-01 to a (year, month, day) on the Gregorian calendar
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = (if mp < 10 { mp + 3 } else { mp - 9 }) as u32;
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
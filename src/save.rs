@@ -0,0 +1,301 @@
+use crate::config::{GameMode, Loadout};
+use crate::enemy::EnemyKind;
+use crate::game::PowerUpKind;
+use crate::persistence::{delete_file, Store};
+use crate::score::RunStats;
+
+// Bumped whenever the layout below changes; a save from any other version
+// is thrown away rather than misread
+const SAVE_VERSION: u32 = 3;
+
+/// 🧑‍🚀 One pilot as saved: where they were and how many hearts were left
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SavedPilot {
+    pub x: f32,
+    pub y: f32,
+    pub health: i32,
+}
+
+/// 👾 One live enemy as saved, with everything it rolled at spawn (size,
+/// speed, worth, fire timer, cargo) so it comes back exactly as it was
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SavedEnemy {
+    pub kind: EnemyKind,
+    pub x: f32,
+    pub y: f32,
+    pub vx: f32,
+    pub vy: f32,
+    pub rotation: f32,
+    pub health: i32,
+    pub max_health: i32,
+    pub size: f32,
+    pub speed: f32,
+    pub score_value: i32,
+    pub fire_timer: f32,
+    pub cargo: Option<PowerUpKind>,
+}
+
+/// 💾 A run put away by save-and-quit: enough to pick it up at the same
+/// wave with the same pilots, enemies, score and kit. Timers, shots in
+/// flight and effects start fresh on resume.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RunSave {
+    pub mode: GameMode,
+    pub coop: bool,
+    pub loadout: Loadout,
+    pub seed: u32,
+    /// The run's RNG and the wave RNG mid-stream, so a seeded run resumes
+    /// drawing exactly where it left off
+    pub rng: u64,
+    pub wave_rng: u64,
+    pub score: i32,
+    pub kills: i32,
+    pub time: f32,
+    pub run_clock: f64,
+    pub weapon_level: usize,
+    pub special_meter: f32,
+    pub extra_lives: u32,
+    pub next_life_score: usize,
+    pub hitless: bool,
+    pub hitless_waves: u32,
+    pub stats: RunStats,
+    pub wave: u32,
+    /// Spawns the wave still had to send, as a wave-table line
+    pub queue: String,
+    pub wave_elapsed: f32,
+    pub pilots: Vec<SavedPilot>,
+    pub enemies: Vec<SavedEnemy>,
+}
+
+impl RunSave {
+    /// 💾 Write the save to `path`, replacing any earlier one
+    pub fn save(&self, path: &'static str) {
+        let mut store = Store::load(path);
+        store.set("version", SAVE_VERSION);
+        store.set("mode", self.mode.name());
+        store.set("coop", self.coop);
+        store.set("loadout", self.loadout.name());
+        store.set("seed", self.seed);
+        store.set("rng", self.rng);
+        store.set("wave_rng", self.wave_rng);
+        store.set("score", self.score);
+        store.set("kills", self.kills);
+        store.set("time", self.time);
+        store.set("run_clock", self.run_clock);
+        store.set("weapon_level", self.weapon_level);
+        store.set("special_meter", self.special_meter);
+        store.set("extra_lives", self.extra_lives);
+        store.set("next_life_score", self.next_life_score);
+        store.set("hitless", self.hitless);
+        store.set("hitless_waves", self.hitless_waves);
+        let stats = &self.stats;
+        store.set("shots_fired", stats.shots_fired);
+        store.set("shots_hit", stats.shots_hit);
+        let kills: Vec<String> = stats.kills_by_kind.iter().map(|(kind, count)| format!("{} {}", kind.name(), count)).collect();
+        store.set("kills_by_kind", kills.join(","));
+        store.set("kill_points", stats.kill_points);
+        store.set("combo_points", stats.combo_points);
+        store.set("graze_points", stats.graze_points);
+        store.set("clear_points", stats.clear_points);
+        store.set("scavenger_points", stats.scavenger_points);
+        store.set("chain_points", stats.chain_points);
+        store.set("hitless_points", stats.hitless_points);
+        store.set("hits_taken", stats.hits_taken);
+        store.set("wave", self.wave);
+        store.set("queue", &self.queue);
+        store.set("wave_elapsed", self.wave_elapsed);
+        let pilots: Vec<String> = self.pilots.iter().map(|p| format!("{} {} {}", p.x, p.y, p.health)).collect();
+        store.set("pilots", pilots.join(","));
+        let enemies: Vec<String> = self
+            .enemies
+            .iter()
+            .map(|e| {
+                format!(
+                    "{} {} {} {} {} {} {} {} {} {} {} {} {}",
+                    e.kind.name(),
+                    e.x,
+                    e.y,
+                    e.vx,
+                    e.vy,
+                    e.rotation,
+                    e.health,
+                    e.max_health,
+                    e.size,
+                    e.speed,
+                    e.score_value,
+                    e.fire_timer,
+                    e.cargo.map_or("-", PowerUpKind::name)
+                )
+            })
+            .collect();
+        store.set("enemies", enemies.join(","));
+        store.save();
+    }
+
+    /// 📂 Read the save at `path`. A missing file gives None; one from
+    /// another version, or that doesn't parse, is deleted and gives None.
+    pub fn load(path: &'static str) -> Option<Self> {
+        let store = Store::load(path);
+        store.get::<u32>("version")?;
+        let save = Self::read(&store);
+        if save.is_none() {
+            delete_file(path);
+        }
+        save
+    }
+
+    /// 🗑️ Drop the save at `path`, once it's been resumed or turned down
+    pub fn discard(path: &'static str) {
+        delete_file(path);
+    }
+
+    fn read(store: &Store) -> Option<Self> {
+        if store.get::<u32>("version")? != SAVE_VERSION {
+            return None;
+        }
+        let stats = RunStats {
+            shots_fired: store.get("shots_fired")?,
+            shots_hit: store.get("shots_hit")?,
+            kills_by_kind: list(store, "kills_by_kind", |parts| {
+                Some((EnemyKind::from_name(parts.first()?)?, parts.get(1)?.parse().ok()?))
+            })?,
+            kill_points: store.get("kill_points")?,
+            combo_points: store.get("combo_points")?,
+            graze_points: store.get("graze_points")?,
+            clear_points: store.get("clear_points")?,
+            scavenger_points: store.get("scavenger_points")?,
+            chain_points: store.get("chain_points")?,
+            hitless_points: store.get("hitless_points")?,
+            hits_taken: store.get("hits_taken")?,
+        };
+        Some(Self {
+            mode: GameMode::from_name(&store.get::<String>("mode")?)?,
+            coop: store.get("coop")?,
+            loadout: Loadout::from_name(&store.get::<String>("loadout")?)?,
+            seed: store.get("seed")?,
+            rng: store.get("rng")?,
+            wave_rng: store.get("wave_rng")?,
+            score: store.get("score")?,
+            kills: store.get("kills")?,
+            time: store.get("time")?,
+            run_clock: store.get("run_clock")?,
+            weapon_level: store.get("weapon_level")?,
+            special_meter: store.get("special_meter")?,
+            extra_lives: store.get("extra_lives")?,
+            next_life_score: store.get("next_life_score")?,
+            hitless: store.get("hitless")?,
+            hitless_waves: store.get("hitless_waves")?,
+            stats,
+            wave: store.get("wave")?,
+            queue: store.get("queue").unwrap_or_default(),
+            wave_elapsed: store.get("wave_elapsed")?,
+            pilots: list(store, "pilots", |parts| {
+                let [x, y, health] = parts else { return None };
+                Some(SavedPilot { x: x.parse().ok()?, y: y.parse().ok()?, health: health.parse().ok()? })
+            })?,
+            enemies: list(store, "enemies", |parts| {
+                let [kind, x, y, vx, vy, rotation, health, max_health, size, speed, score_value, fire_timer, cargo] = parts else {
+                    return None;
+                };
+                Some(SavedEnemy {
+                    kind: EnemyKind::from_name(kind)?,
+                    x: x.parse().ok()?,
+                    y: y.parse().ok()?,
+                    vx: vx.parse().ok()?,
+                    vy: vy.parse().ok()?,
+                    rotation: rotation.parse().ok()?,
+                    health: health.parse().ok()?,
+                    max_health: max_health.parse().ok()?,
+                    size: size.parse().ok()?,
+                    speed: speed.parse().ok()?,
+                    score_value: score_value.parse().ok()?,
+                    fire_timer: fire_timer.parse().ok()?,
+                    cargo: match *cargo {
+                        "-" => None,
+                        name => Some(PowerUpKind::from_name(name)?),
+                    },
+                })
+            })?,
+        })
+    }
+}
+
+// Comma-separated entries of space-separated parts under `key`; None if
+// the key is missing or any entry fails to parse
+fn list<T>(store: &Store, key: &str, parse: impl Fn(&[&str]) -> Option<T>) -> Option<Vec<T>> {
+    let text: String = store.get(key)?;
+    text.split(',')
+        .filter(|entry| !entry.trim().is_empty())
+        .map(|entry| parse(&entry.split_whitespace().collect::<Vec<_>>()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PATH: &str = "target/run_save_round_trip.txt";
+
+    #[test]
+    fn a_save_reads_back_as_it_was_written() {
+        let save = RunSave {
+            mode: GameMode::Hardcore,
+            coop: true,
+            loadout: Loadout::Sniper,
+            seed: 0xC0FFEE,
+            rng: 0x9E37_79B9_7F4A_7C15,
+            wave_rng: 42,
+            score: 4210,
+            kills: 57,
+            time: 183.25,
+            run_clock: 183.2504,
+            weapon_level: 2,
+            special_meter: 0.6,
+            extra_lives: 1,
+            next_life_score: 1,
+            hitless: false,
+            hitless_waves: 3,
+            stats: RunStats {
+                shots_fired: 900,
+                shots_hit: 412,
+                kills_by_kind: vec![(EnemyKind::Chaser, 40), (EnemyKind::Drifter, 17)],
+                hits_taken: 2,
+                ..RunStats::default()
+            },
+            wave: 7,
+            queue: "chaser 3, drifter 4 line".to_owned(),
+            wave_elapsed: 12.5,
+            pilots: vec![SavedPilot { x: 100.5, y: 200.0, health: 2 }, SavedPilot { x: 640.0, y: 360.0, health: 0 }],
+            enemies: vec![SavedEnemy {
+                kind: EnemyKind::Splitter,
+                x: -12.0,
+                y: 33.3,
+                vx: 40.0,
+                vy: -1.5,
+                rotation: 2.1,
+                health: 7,
+                max_health: 11,
+                size: 41.7,
+                speed: 63.2,
+                score_value: 140,
+                fire_timer: 1.25,
+                cargo: Some(PowerUpKind::Freeze),
+            }],
+        };
+        RunSave::discard(PATH);
+        save.save(PATH);
+        let loaded = RunSave::load(PATH);
+        RunSave::discard(PATH);
+        assert_eq!(loaded, Some(save));
+    }
+
+    #[test]
+    fn a_save_from_another_version_is_thrown_away() {
+        const OLD: &str = "target/run_save_old_version.txt";
+        let mut store = Store::load(OLD);
+        store.set("version", SAVE_VERSION - 1);
+        store.save();
+        assert_eq!(RunSave::load(OLD), None);
+        assert!(Store::load(OLD).get::<u32>("version").is_none());
+    }
+}
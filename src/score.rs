@@ -0,0 +1,112 @@
+use crate::enemy::EnemyKind;
+
+// End-of-run bonuses
+const WAVE_BONUS: i32 = 500; // Per wave cleared
+const SURVIVAL_BONUS: f32 = 10.0; // Per second alive
+
+// Wave-clear bonus: per wave number, doubled for an instant clear and
+// tapering to the base at the par time
+const CLEAR_BONUS: f32 = 100.0;
+const CLEAR_PAR_TIME: f32 = 30.0;
+
+/// 🏁 Bonus for clearing `wave` after `elapsed` seconds of spawning
+pub fn wave_clear_bonus(wave: u32, elapsed: f32) -> i32 {
+    let speed = (1.0 - elapsed / CLEAR_PAR_TIME).clamp(0.0, 1.0);
+    (CLEAR_BONUS * wave as f32 * (1.0 + speed)) as i32
+}
+
+/// 📊 Counters gathered during a run for the game-over breakdown
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RunStats {
+    pub shots_fired: u32,
+    pub shots_hit: u32,
+    pub kills_by_kind: Vec<(EnemyKind, u32)>,
+    /// Base score of everything destroyed
+    pub kill_points: i32,
+    /// Extra earned on top of the base through the combo multiplier
+    pub combo_points: i32,
+    /// Bonus from enemy bullets grazed
+    pub graze_points: i32,
+    /// Bonus from clearing waves quickly
+    pub clear_points: i32,
+    /// Scavengers caught, less what escaping ones stole
+    pub scavenger_points: i32,
+    /// Bonus from back-to-back kill chains
+    pub chain_points: i32,
+    /// Extra kill score earned before the first hit
+    pub hitless_points: i32,
+    /// Hits taken by any pilot
+    pub hits_taken: u32,
+}
+
+impl RunStats {
+    pub fn record_kill(&mut self, kind: EnemyKind, base: i32, awarded: i32) {
+        match self.kills_by_kind.iter_mut().find(|(k, _)| *k == kind) {
+            Some((_, count)) => *count += 1,
+            None => self.kills_by_kind.push((kind, 1)),
+        }
+        self.kill_points += base;
+        self.combo_points += awarded - base;
+    }
+
+    pub fn kills(&self) -> u32 {
+        self.kills_by_kind.iter().map(|(_, count)| count).sum()
+    }
+
+    /// Fraction of fired shots that hit something (0.0 - 1.0)
+    pub fn accuracy(&self) -> f32 {
+        if self.shots_fired == 0 {
+            return 0.0;
+        }
+        (self.shots_hit as f32 / self.shots_fired as f32).min(1.0)
+    }
+}
+
+/// 🧾 Itemized final score
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ScoreBreakdown {
+    pub kills: i32,
+    pub combo: i32,
+    pub grazes: i32,
+    pub clears: i32,
+    pub scavengers: i32,
+    pub chains: i32,
+    pub hitless: i32,
+    pub waves: i32,
+    pub survival: i32,
+    pub accuracy: f32,
+}
+
+impl ScoreBreakdown {
+    /// Tally a finished run that reached `wave` and lasted `time` seconds
+    pub fn tally(stats: &RunStats, wave: u32, time: f32) -> Self {
+        Self {
+            kills: stats.kill_points,
+            combo: stats.combo_points,
+            grazes: stats.graze_points,
+            clears: stats.clear_points,
+            scavengers: stats.scavenger_points,
+            chains: stats.chain_points,
+            hitless: stats.hitless_points,
+            waves: wave.saturating_sub(1) as i32 * WAVE_BONUS,
+            survival: (time * SURVIVAL_BONUS) as i32,
+            accuracy: stats.accuracy(),
+        }
+    }
+
+    pub fn total(&self) -> i32 {
+        self.kills + self.combo + self.grazes + self.clears + self.scavengers + self.chains + self.hitless + self.waves + self.survival
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accuracy_is_hits_over_shots() {
+        let stats = RunStats { shots_fired: 40, shots_hit: 10, ..RunStats::default() };
+        assert_eq!(stats.accuracy(), 0.25);
+        assert_eq!(RunStats::default().accuracy(), 0.0);
+    }
+}
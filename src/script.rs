@@ -0,0 +1,81 @@
+use crate::input::{InputSource, PilotInput, StepInput};
+use crate::joystick::Vec2;
+
+/// 🎬 One simulation step of recorded input
+#[derive(Debug, Clone, Copy)]
+pub struct ScriptFrame {
+    pub dt: f32,
+    pub movement: Vec2,
+    pub aim: Vec2,
+    pub firing: bool,
+}
+
+impl ScriptFrame {
+    // The frame as the first pilot's input: full trigger, no presses
+    fn input(&self) -> PilotInput {
+        PilotInput {
+            movement: self.movement,
+            aim: self.aim,
+            firing: self.firing,
+            trigger: 1.0,
+            ..PilotInput::default()
+        }
+    }
+}
+
+/// 🎬 Input played back frame by frame instead of read from the player, so
+/// a run can be reproduced exactly (pair it with a fixed seed)
+#[derive(Debug, Clone, Default)]
+pub struct ScriptedInput {
+    /// Seed the run should use, from an optional `seed <hex>` line
+    pub seed: Option<u32>,
+    frames: Vec<ScriptFrame>,
+    next: usize,
+}
+
+impl ScriptedInput {
+    /// Parse a script: one `<dt> <move x> <move y> <aim x> <aim y> <fire 0/1>`
+    /// line per step. Blank lines and `#` comments are skipped.
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut script = Self::default();
+        for (line_no, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            if let Some(seed) = line.strip_prefix("seed ") {
+                let seed = u32::from_str_radix(seed.trim(), 16)
+                    .map_err(|_| format!("line {}: bad seed '{}'", line_no + 1, seed.trim()))?;
+                script.seed = Some(seed);
+                continue;
+            }
+
+            let fields: Vec<f32> = line
+                .split_whitespace()
+                .map(|field| field.parse().map_err(|_| format!("line {}: bad number '{}'", line_no + 1, field)))
+                .collect::<Result<_, _>>()?;
+            let [dt, move_x, move_y, aim_x, aim_y, fire] = fields[..] else {
+                return Err(format!("line {}: expected 6 fields, got {}", line_no + 1, fields.len()));
+            };
+            if dt <= 0.0 {
+                return Err(format!("line {}: dt must be positive", line_no + 1));
+            }
+            script.frames.push(ScriptFrame {
+                dt,
+                movement: Vec2::new(move_x, move_y),
+                aim: Vec2::new(aim_x, aim_y),
+                firing: fire != 0.0,
+            });
+        }
+        Ok(script)
+    }
+}
+
+// A script flies the first pilot only, one frame per step
+impl InputSource for ScriptedInput {
+    fn next_step(&mut self) -> Option<StepInput> {
+        let frame = self.frames.get(self.next).copied()?;
+        self.next += 1;
+        Some(StepInput { dt: frame.dt, pilots: vec![frame.input()] })
+    }
+}
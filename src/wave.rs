@@ -0,0 +1,559 @@
+use crate::enemy::EnemyKind;
+use crate::rng::Rng;
+
+/// 🌊 Hand-authored opening waves: `<wave>: <kind> <count> [<pattern>], ...`.
+/// Entries with a pattern arrive together in that formation; `slow <count>`
+/// lays that many slow fields for the wave, `burst <count>` sends that many
+/// of the trickled enemies in together at the start, and `trickle <seconds>`
+/// fixes the gap between the rest.
+const WAVE_TABLE: &str = "
+# Learn to move and shoot
+1: drifter 4
+2: drifter 4, chaser 2
+3: chaser 5
+4: drifter 4 line, chaser 3, splitter 1
+5: chaser 4, boss 1
+# Second act - swarms and splitters
+6: drifter 6 v, splitter 2, circler 2, burst 3
+7: chaser 6, splitter 2, shooter 1, layer 2, slow 1
+8: drifter 6, chaser 5, shooter 2, circler 2, blinker 2, scavenger 1, burst 5, trickle 1.2
+9: chaser 6 ring, splitter 4, shooter 2, circler 3, support 1, slow 2
+10: chaser 6, splitter 2, shooter 2, boss 1
+";
+
+/// 👹 Boss rush stages: every stage is a boss fight, escorts and boss
+/// count growing as it goes
+const BOSS_RUSH_TABLE: &str = "
+1: boss 1
+2: boss 1, shooter 2
+3: boss 1, circler 3 ring, support 1
+4: boss 2
+5: boss 2, shooter 2, support 2
+";
+
+// Breather between waves and the trickle rate within one
+const WAVE_BREAK: f32 = 3.0;
+const SPAWN_INTERVAL: (f32, f32) = (0.8, 2.0);
+
+// Share of a procedural wave's trickle sent in as its opening burst
+const PROCEDURAL_BURST_FRACTION: f32 = 0.3;
+
+/// 📐 How a group of enemies enters the arena
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpawnPattern {
+    Edge, // One at a time from random screen edges
+    Line, // A row marching in from one edge
+    Ring, // Surrounding the pilot at a safe distance
+    Vee,  // A chevron pointed at the pilot
+}
+
+impl SpawnPattern {
+    /// Lowercase name used in data tables
+    pub fn name(self) -> &'static str {
+        match self {
+            SpawnPattern::Edge => "edge",
+            SpawnPattern::Line => "line",
+            SpawnPattern::Ring => "ring",
+            SpawnPattern::Vee => "v",
+        }
+    }
+
+    /// Parse the lowercase name used in data tables
+    pub fn from_name(name: &str) -> Option<SpawnPattern> {
+        match name {
+            "edge" => Some(SpawnPattern::Edge),
+            "line" => Some(SpawnPattern::Line),
+            "ring" => Some(SpawnPattern::Ring),
+            "v" => Some(SpawnPattern::Vee),
+            _ => None,
+        }
+    }
+}
+
+/// Enemies that arrive together: a formation, or a single edge spawn
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SpawnGroup {
+    pub kind: EnemyKind,
+    pub count: u32,
+    pub pattern: SpawnPattern,
+}
+
+/// Enemy kinds and counts for one wave
+#[derive(Debug, Clone, PartialEq)]
+pub struct WaveSpec {
+    /// Trickled in one by one from the edges
+    pub enemies: Vec<(EnemyKind, u32)>,
+    /// Set pieces, each arriving all at once
+    pub formations: Vec<SpawnGroup>,
+    /// Slow fields laid down when the wave starts
+    pub slow_fields: u32,
+    /// Trickled enemies sent in together, telegraphed, as the wave starts
+    pub burst: u32,
+    /// Seconds between the remaining trickle spawns (randomized if unset)
+    pub trickle: Option<f32>,
+}
+
+/// Parse a wave table; waves must be listed in order starting at 1
+pub fn parse_wave_table(table: &str) -> Result<Vec<WaveSpec>, String> {
+    let mut waves = Vec::new();
+
+    for (line_no, line) in table.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let (number, entries) = line
+            .split_once(':')
+            .ok_or_else(|| format!("line {}: missing ':'", line_no + 1))?;
+        let number: usize = number
+            .trim()
+            .parse()
+            .map_err(|_| format!("line {}: bad wave number", line_no + 1))?;
+        if number != waves.len() + 1 {
+            return Err(format!("line {}: expected wave {}", line_no + 1, waves.len() + 1));
+        }
+
+        let mut enemies = Vec::new();
+        let mut formations = Vec::new();
+        let mut slow_fields = 0;
+        let mut burst = 0;
+        let mut trickle = None;
+        for entry in entries.split(',') {
+            let mut parts = entry.split_whitespace();
+            let (Some(name), Some(count), pattern, None) = (parts.next(), parts.next(), parts.next(), parts.next()) else {
+                return Err(format!("line {}: expected '<kind> <count> [<pattern>]'", line_no + 1));
+            };
+            let bad_count = || format!("line {}: bad count '{}'", line_no + 1, count);
+            match (name, pattern) {
+                ("slow", None) => {
+                    slow_fields = count.parse().map_err(|_| bad_count())?;
+                    continue;
+                }
+                ("burst", None) => {
+                    burst = count.parse().map_err(|_| bad_count())?;
+                    continue;
+                }
+                ("trickle", None) => {
+                    let seconds: f32 = count.parse().map_err(|_| bad_count())?;
+                    if seconds <= 0.0 {
+                        return Err(bad_count());
+                    }
+                    trickle = Some(seconds);
+                    continue;
+                }
+                _ => {}
+            }
+            let kind = EnemyKind::from_name(name)
+                .ok_or_else(|| format!("line {}: unknown enemy '{}'", line_no + 1, name))?;
+            let count = count.parse().map_err(|_| bad_count())?;
+            let pattern = match pattern {
+                Some(name) => SpawnPattern::from_name(name)
+                    .ok_or_else(|| format!("line {}: unknown pattern '{}'", line_no + 1, name))?,
+                None => SpawnPattern::Edge,
+            };
+            match pattern {
+                SpawnPattern::Edge => enemies.push((kind, count)),
+                _ => formations.push(SpawnGroup { kind, count, pattern }),
+            }
+        }
+        waves.push(WaveSpec { enemies, formations, slow_fields, burst, trickle });
+    }
+
+    Ok(waves)
+}
+
+/// Relative spawn odds for procedural waves, normalized to sum to 1.
+/// Early waves are all Drifters; Chasers and Splitters ramp in, and
+/// Shooters and Circlers join from wave 5, Layers from wave 6,
+/// Supports from wave 7, Blinkers and Scavengers from wave 8 and Mimics
+/// from wave 9.
+pub fn spawn_weights(wave: u32) -> Vec<(EnemyKind, f32)> {
+    let w = wave as f32;
+    let raw = [
+        (EnemyKind::Drifter, (1.0 - 0.1 * (w - 1.0)).max(0.15)),
+        (EnemyKind::Chaser, (0.15 * (w - 1.0)).clamp(0.0, 1.0)),
+        (EnemyKind::Splitter, (0.1 * (w - 2.0)).clamp(0.0, 0.6)),
+        (EnemyKind::Shooter, (0.1 * (w - 4.0)).clamp(0.0, 0.5)),
+        (EnemyKind::Circler, (0.1 * (w - 4.0)).clamp(0.0, 0.5)),
+        (EnemyKind::Support, (0.05 * (w - 6.0)).clamp(0.0, 0.3)),
+        (EnemyKind::Layer, (0.05 * (w - 5.0)).clamp(0.0, 0.3)),
+        (EnemyKind::Blinker, (0.05 * (w - 7.0)).clamp(0.0, 0.3)),
+        (EnemyKind::Scavenger, (0.03 * (w - 7.0)).clamp(0.0, 0.15)),
+        (EnemyKind::Mimic, (0.03 * (w - 8.0)).clamp(0.0, 0.15)),
+    ];
+    let total: f32 = raw.iter().map(|(_, weight)| weight).sum();
+    raw.into_iter()
+        .filter(|(_, weight)| *weight > 0.0)
+        .map(|(kind, weight)| (kind, weight / total))
+        .collect()
+}
+
+/// Pick from normalized weights with `roll` in `0.0..1.0`
+pub fn pick_weighted(weights: &[(EnemyKind, f32)], roll: f32) -> EnemyKind {
+    let mut cumulative = 0.0;
+    for &(kind, weight) in weights {
+        cumulative += weight;
+        if roll < cumulative {
+            return kind;
+        }
+    }
+    // Rounding can leave the sum a hair under 1.0
+    weights.last().map_or(EnemyKind::Drifter, |&(kind, _)| kind)
+}
+
+/// Where the wave cycle currently is
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum WavePhase {
+    /// Quiet gap before the next wave starts
+    Break { remaining: f32 },
+    /// Enemies from the current wave are trickling in
+    Spawning,
+}
+
+/// 🌊 Sequences waves from the authored table, then procedurally
+/// (or, for a finite run, stops after the table's last wave)
+pub struct WaveManager {
+    table: Vec<WaveSpec>,
+    endless: bool,
+    wave: u32,
+    queue: Vec<SpawnGroup>,
+    burst: Vec<EnemyKind>,
+    trickle: Option<f32>,
+    spawn_timer: f32,
+    phase: WavePhase,
+    wave_elapsed: f32,
+    slow_fields: u32,
+    rng: Rng,
+}
+
+impl WaveManager {
+    /// Compositions and spawn pacing are drawn from `rng`. Unless
+    /// `endless`, the run is over once the authored waves are cleared.
+    pub fn new(rng: Rng, endless: bool) -> Self {
+        Self {
+            table: parse_wave_table(WAVE_TABLE).expect("embedded wave table is valid"),
+            endless,
+            wave: 0,
+            queue: Vec::new(),
+            burst: Vec::new(),
+            trickle: None,
+            spawn_timer: 0.0,
+            phase: WavePhase::Break { remaining: 0.0 },
+            wave_elapsed: 0.0,
+            slow_fields: 0,
+            rng,
+        }
+    }
+
+    /// The boss rush stages, ending after the last one
+    pub fn boss_rush(rng: Rng) -> Self {
+        Self {
+            table: parse_wave_table(BOSS_RUSH_TABLE).expect("embedded boss rush table is valid"),
+            ..Self::new(rng, false)
+        }
+    }
+
+    /// A single wave holding just `boss`, for practicing it
+    pub fn practice(rng: Rng, boss: EnemyKind) -> Self {
+        Self {
+            table: vec![WaveSpec {
+                enemies: vec![(boss, 1)],
+                formations: Vec::new(),
+                slow_fields: 0,
+                burst: 0,
+                trickle: None,
+            }],
+            ..Self::new(rng, false)
+        }
+    }
+
+    /// A finite run whose last wave has been cleared
+    pub fn is_complete(&self) -> bool {
+        !self.endless && self.wave as usize >= self.table.len() && matches!(self.phase, WavePhase::Break { .. })
+    }
+
+    /// Number of waves in a finite run
+    pub fn final_wave(&self) -> Option<u32> {
+        (!self.endless).then_some(self.table.len() as u32)
+    }
+
+    /// Current wave number (0 before the first wave starts)
+    pub fn wave(&self) -> u32 {
+        self.wave
+    }
+
+    pub fn phase(&self) -> WavePhase {
+        self.phase
+    }
+
+    /// Seconds since the current wave started spawning
+    pub fn wave_elapsed(&self) -> f32 {
+        self.wave_elapsed
+    }
+
+    /// Slow fields the current wave calls for
+    pub fn slow_fields(&self) -> u32 {
+        self.slow_fields
+    }
+
+    /// Composition for a wave: authored if in the table, otherwise drawn
+    /// from the wave's spawn weights
+    pub fn composition(&mut self, wave: u32) -> WaveSpec {
+        if let Some(spec) = self.table.get(wave as usize - 1) {
+            return spec.clone();
+        }
+
+        let weights = spawn_weights(wave);
+        let mut enemies: Vec<(EnemyKind, u32)> = Vec::new();
+        for _ in 0..6 + wave {
+            let kind = pick_weighted(&weights, self.rng.next_f32());
+            match enemies.iter_mut().find(|(k, _)| *k == kind) {
+                Some((_, count)) => *count += 1,
+                None => enemies.push((kind, 1)),
+            }
+        }
+        if wave.is_multiple_of(5) {
+            enemies.push((EnemyKind::Boss, 1 + wave / 20));
+        }
+        // Every third procedural wave gets a slow field
+        let slow_fields = u32::from(wave.is_multiple_of(3));
+        let trickled: u32 = enemies.iter().map(|(_, count)| count).sum();
+        let burst = (trickled as f32 * PROCEDURAL_BURST_FRACTION) as u32;
+        WaveSpec { enemies, formations: Vec::new(), slow_fields, burst, trickle: None }
+    }
+
+    /// Advance timers; returns a group to spawn this frame, if any
+    pub fn update(&mut self, dt: f32, enemies_alive: usize) -> Option<SpawnGroup> {
+        match self.phase {
+            WavePhase::Break { .. } if self.is_complete() => None,
+            WavePhase::Break { remaining } => {
+                let remaining = remaining - dt;
+                if remaining > 0.0 {
+                    self.phase = WavePhase::Break { remaining };
+                    return None;
+                }
+                self.start_wave(self.wave + 1);
+                None
+            }
+            WavePhase::Spawning => {
+                self.wave_elapsed += dt;
+                if self.queue.is_empty() {
+                    // Wave is over once the last spawn (and its children) are gone
+                    if enemies_alive == 0 {
+                        self.phase = WavePhase::Break { remaining: WAVE_BREAK };
+                    }
+                    return None;
+                }
+
+                self.spawn_timer -= dt;
+                if self.spawn_timer > 0.0 {
+                    return None;
+                }
+                self.spawn_timer = self.trickle_interval();
+                self.queue.pop()
+            }
+        }
+    }
+
+    /// The current wave's opening burst, at most `room` of it (the rest
+    /// joins the trickle). Empty once taken.
+    pub fn take_burst(&mut self, room: usize) -> Vec<EnemyKind> {
+        let mut burst = std::mem::take(&mut self.burst);
+        let single = |kind| SpawnGroup { kind, count: 1, pattern: SpawnPattern::Edge };
+        if burst.len() > room {
+            self.queue.extend(burst.drain(room..).map(single));
+            self.rng.shuffle(&mut self.queue);
+        }
+        burst
+    }
+
+    fn trickle_interval(&mut self) -> f32 {
+        match self.trickle {
+            Some(seconds) => seconds,
+            None => self.rng.range(SPAWN_INTERVAL.0, SPAWN_INTERVAL.1),
+        }
+    }
+
+    /// Spawns the current wave has yet to send, in wave-table entry
+    /// syntax (empty once it's all out)
+    pub fn pending(&self) -> String {
+        let entries: Vec<String> = self
+            .queue
+            .iter()
+            .map(|group| format!("{} {} {}", group.kind.name(), group.count, group.pattern.name()))
+            .collect();
+        entries.join(", ")
+    }
+
+    /// Pick up `wave` partway through: `pending` (as from [`Self::pending`])
+    /// is all it still has to send, `elapsed` seconds in
+    pub fn resume(&mut self, wave: u32, pending: &str, elapsed: f32) -> Result<(), String> {
+        let spec = if pending.trim().is_empty() {
+            None
+        } else {
+            parse_wave_table(&format!("1: {}", pending))?.pop()
+        };
+        self.wave = wave.max(1);
+        self.slow_fields = 0;
+        self.burst.clear();
+        self.queue = spec.map_or_else(Vec::new, |spec| {
+            let single = |kind| SpawnGroup { kind, count: 1, pattern: SpawnPattern::Edge };
+            spec.enemies
+                .iter()
+                .flat_map(|&(kind, count)| std::iter::repeat_n(single(kind), count as usize))
+                .chain(spec.formations)
+                .collect()
+        });
+        self.rng.shuffle(&mut self.queue);
+        self.trickle = self.table.get(self.wave as usize - 1).and_then(|spec| spec.trickle);
+        self.spawn_timer = self.trickle_interval();
+        self.phase = WavePhase::Spawning;
+        self.wave_elapsed = elapsed;
+        Ok(())
+    }
+
+    /// Where the composition RNG is, for a saved run
+    pub fn rng_state(&self) -> u64 {
+        self.rng.state()
+    }
+
+    /// Carry on drawing compositions from a saved [`Self::rng_state`]
+    pub fn restore_rng(&mut self, state: u64) {
+        self.rng = Rng::from_state(state);
+    }
+
+    /// Abandon the current wave and start `wave` immediately (dev tool)
+    pub fn jump_to(&mut self, wave: u32) {
+        self.start_wave(wave.max(1));
+    }
+
+    fn start_wave(&mut self, wave: u32) {
+        self.wave = wave;
+        let spec = self.composition(wave);
+        self.slow_fields = spec.slow_fields;
+        let single = |kind| SpawnGroup { kind, count: 1, pattern: SpawnPattern::Edge };
+        self.queue = spec
+            .enemies
+            .iter()
+            .flat_map(|&(kind, count)| std::iter::repeat_n(single(kind), count as usize))
+            .chain(spec.formations)
+            .collect();
+        self.rng.shuffle(&mut self.queue);
+
+        // The burst comes out of the trickle; the trickle then waits its
+        // turn rather than landing on top of it
+        let mut burst = spec.burst as usize;
+        self.burst.clear();
+        self.queue.retain(|group| {
+            let take = burst > 0 && group.pattern == SpawnPattern::Edge;
+            if take {
+                burst -= 1;
+                self.burst.push(group.kind);
+            }
+            !take
+        });
+        self.trickle = spec.trickle;
+        self.spawn_timer = if self.burst.is_empty() { 0.0 } else { self.trickle_interval() };
+        self.phase = WavePhase::Spawning;
+        self.wave_elapsed = 0.0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_wave_table_parses() {
+        let waves = parse_wave_table(WAVE_TABLE).unwrap();
+        assert_eq!(waves.len(), 10);
+        assert_eq!(
+            waves[0],
+            WaveSpec { enemies: vec![(EnemyKind::Drifter, 4)], formations: Vec::new(), slow_fields: 0, burst: 0, trickle: None }
+        );
+        assert_eq!(waves[3].formations, vec![SpawnGroup { kind: EnemyKind::Drifter, count: 4, pattern: SpawnPattern::Line }]);
+        assert_eq!((waves[7].burst, waves[7].trickle), (5, Some(1.2)));
+        assert!(parse_wave_table(BOSS_RUSH_TABLE).is_ok());
+    }
+
+    #[test]
+    fn bad_tables_name_the_line_and_problem() {
+        let error = |table: &str| parse_wave_table(table).unwrap_err();
+        assert_eq!(error("x: drifter 1"), "line 1: bad wave number");
+        assert_eq!(error("1: drifter 1\n3: chaser 1"), "line 2: expected wave 2");
+        assert_eq!(error("1: dragon 2"), "line 1: unknown enemy 'dragon'");
+        assert_eq!(error("1: drifter 2 spiral"), "line 1: unknown pattern 'spiral'");
+        assert_eq!(error("1: drifter lots"), "line 1: bad count 'lots'");
+        assert_eq!(error("1: drifter"), "line 1: expected '<kind> <count> [<pattern>]'");
+        assert_eq!(error("1: trickle 0"), "line 1: bad count '0'");
+    }
+
+    #[test]
+    fn spawn_weights_sum_to_one() {
+        for wave in [1, 5, 9, 30] {
+            let total: f32 = spawn_weights(wave).iter().map(|(_, weight)| weight).sum();
+            assert!((total - 1.0).abs() < 1e-5);
+        }
+    }
+
+    #[test]
+    fn hard_enemies_never_come_in_the_first_wave() {
+        let weights = spawn_weights(1);
+        assert_eq!(weights, vec![(EnemyKind::Drifter, 1.0)]);
+        for roll in [0.0, 0.5, 0.999] {
+            assert_eq!(pick_weighted(&weights, roll), EnemyKind::Drifter);
+        }
+    }
+
+    #[test]
+    fn a_roll_past_the_rounded_total_picks_the_last_kind() {
+        let weights = spawn_weights(12);
+        let last = weights.last().unwrap().0;
+        assert_eq!(pick_weighted(&weights, 0.0), weights[0].0);
+        assert_eq!(pick_weighted(&weights, 1.0), last);
+    }
+
+    // A manager whose first wave is `spec`, started
+    fn started(spec: &str) -> WaveManager {
+        let mut waves = WaveManager::new(Rng::new(1), false);
+        waves.table = parse_wave_table(spec).unwrap();
+        waves.update(0.0, 0);
+        waves
+    }
+
+    #[test]
+    fn the_burst_comes_out_of_the_trickle() {
+        let mut waves = started("1: chaser 8, drifter 3 line, burst 5");
+        assert_eq!(waves.phase(), WavePhase::Spawning);
+        assert_eq!(waves.queue.len(), 4);
+        assert!(waves.queue.iter().any(|group| group.pattern == SpawnPattern::Line));
+        assert_eq!(waves.take_burst(10), vec![EnemyKind::Chaser; 5]);
+        assert!(waves.take_burst(10).is_empty());
+    }
+
+    #[test]
+    fn burst_beyond_the_room_rejoins_the_trickle() {
+        let mut waves = started("1: chaser 6, burst 5");
+        assert_eq!(waves.take_burst(2).len(), 2);
+        assert_eq!(waves.queue.len(), 4);
+        assert!(waves.queue.iter().all(|group| *group == SpawnGroup { kind: EnemyKind::Chaser, count: 1, pattern: SpawnPattern::Edge }));
+    }
+
+    #[test]
+    fn a_wave_resumes_with_what_it_had_pending() {
+        let waves = started("1: chaser 2, shooter 1, drifter 4 line");
+        let pending = waves.pending();
+        let mut resumed = WaveManager::new(Rng::new(2), false);
+        resumed.resume(1, &pending, 4.0).unwrap();
+
+        let sorted = |queue: &[SpawnGroup]| {
+            let mut entries: Vec<String> = queue.iter().map(|g| format!("{:?}", g)).collect();
+            entries.sort();
+            entries
+        };
+        assert_eq!(sorted(&resumed.queue), sorted(&waves.queue));
+        assert!(resumed.queue.contains(&SpawnGroup { kind: EnemyKind::Drifter, count: 4, pattern: SpawnPattern::Line }));
+        assert_eq!((resumed.wave(), resumed.phase(), resumed.wave_elapsed()), (1, WavePhase::Spawning, 4.0));
+    }
+}